@@ -0,0 +1,68 @@
+// ABOUTME: Build script recording the resolved FROST crate versions and git commit
+// ABOUTME: Exposes them to build_info as compile-time environment variables
+
+use std::path::Path;
+use std::process::Command;
+
+// Crates whose serialization formats the module's packages depend on
+const TRACKED_CRATES: [(&str, &str); 3] = [
+    ("frost-core", "FROST_CORE_VERSION"),
+    ("frost-secp256k1", "FROST_SECP256K1_VERSION"),
+    ("frost-secp256k1-tr", "FROST_SECP256K1_TR_VERSION"),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    watch_git_head();
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+
+    let lockfile = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    for (name, variable) in TRACKED_CRATES {
+        let version = locked_version(&lockfile, name).unwrap_or_else(|| "unknown".to_string());
+        println!("cargo:rustc-env={}={}", variable, version);
+    }
+
+    println!("cargo:rustc-env=GIT_COMMIT={}", git_commit().unwrap_or_else(|| "unknown".to_string()));
+}
+
+// The version of `name` in Cargo.lock, found from its `name = "..."` line
+fn locked_version(lockfile: &str, name: &str) -> Option<String> {
+    let mut lines = lockfile.lines();
+    while let Some(line) = lines.next() {
+        if line.trim() == format!("name = \"{}\"", name) {
+            let version = lines.next()?.trim().strip_prefix("version = \"")?;
+            return Some(version.trim_end_matches('"').to_string());
+        }
+    }
+    None
+}
+
+// HEAD only names the current branch, so a new commit on it changes the
+// branch's ref file, or packed-refs once refs are packed, and not HEAD itself.
+// Paths that don't exist are skipped since cargo would rerun on every build.
+fn watch_git_head() {
+    let mut watched = vec!["../.git/HEAD".to_string(), "../.git/packed-refs".to_string()];
+    let head = std::fs::read_to_string("../.git/HEAD").unwrap_or_default();
+    if let Some(reference) = head.trim().strip_prefix("ref: ") {
+        watched.push(format!("../.git/{}", reference));
+    }
+
+    for path in watched {
+        if Path::new(&path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+// GIT_COMMIT from the environment wins, for builds from a source archive
+fn git_commit() -> Option<String> {
+    if let Ok(commit) = std::env::var("GIT_COMMIT") {
+        return Some(commit);
+    }
+
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok().map(|commit| commit.trim().to_string())
+}
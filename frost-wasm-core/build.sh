@@ -12,21 +12,37 @@ if ! command -v wasm-pack &> /dev/null; then
     curl https://rustwasm.github.io/wasm-pack/installer/init.sh -sSf | sh
 fi
 
+# Pass --verify-only to build just signature verification (no keygen/signing).
+# Pass --parallel to build with WASM threads for parallel share verification;
+# this needs a nightly toolchain and a host with SharedArrayBuffer.
+OUT_DIR="../src/wasm"
+CARGO_ARGS=()
+WASM_PACK=(wasm-pack)
+if [ "$1" = "--verify-only" ]; then
+    OUT_DIR="../src/wasm-verify"
+    CARGO_ARGS=(--no-default-features)
+elif [ "$1" = "--parallel" ]; then
+    OUT_DIR="../src/wasm-parallel"
+    CARGO_ARGS=(--features parallel -Z build-std=panic_abort,std)
+    WASM_PACK=(rustup run nightly wasm-pack)
+    export RUSTFLAGS="-C target-feature=+atomics,+bulk-memory,+mutable-globals"
+fi
+
 # Build the WASM module
 echo "📦 Compiling Rust to WASM..."
-wasm-pack build --target web --out-dir ../src/wasm --scope frost
+"${WASM_PACK[@]}" build --target web --out-dir "$OUT_DIR" --scope frost -- "${CARGO_ARGS[@]}"
 
 # Clean up unnecessary files
 echo "🧹 Cleaning up..."
-rm -f ../src/wasm/.gitignore ../src/wasm/README.md
+rm -f "$OUT_DIR/.gitignore" "$OUT_DIR/README.md"
 
 # Verify build
-if [ -f "../src/wasm/frost_wasm_core_bg.wasm" ]; then
+if [ -f "$OUT_DIR/frost_wasm_core_bg.wasm" ]; then
     echo "✅ WASM module built successfully"
-    echo "📊 WASM bundle size: $(du -h ../src/wasm/frost_wasm_core_bg.wasm | cut -f1)"
+    echo "📊 WASM bundle size: $(du -h "$OUT_DIR/frost_wasm_core_bg.wasm" | cut -f1)"
 else
     echo "❌ WASM build failed"
     exit 1
 fi
 
-echo "🎉 Build complete! WASM module available at src/wasm/"
\ No newline at end of file
+echo "🎉 Build complete! WASM module available at ${OUT_DIR#../}/"
\ No newline at end of file
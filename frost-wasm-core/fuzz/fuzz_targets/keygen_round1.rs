@@ -0,0 +1,27 @@
+// ABOUTME: Fuzzes keygen_round1 with arbitrary state, participant, and package inputs
+// ABOUTME: Every input must come back as a FrostResult, never a panic
+
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    state_json: String,
+    participant_id: String,
+    pubkey_hex: Option<String>,
+    extra_entropy: Option<Vec<u8>>,
+    revealed_round1_json: Option<String>,
+}
+
+fuzz_target!(|input: Input| {
+    let output = frost_wasm_core::keygen_round1(
+        &input.state_json,
+        &input.participant_id,
+        input.pubkey_hex,
+        input.extra_entropy,
+        input.revealed_round1_json,
+    );
+    let result: serde_json::Value = serde_json::from_str(&output).expect("output is a FrostResult");
+    assert!(result["success"].is_boolean());
+});
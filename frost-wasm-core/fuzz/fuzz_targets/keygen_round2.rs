@@ -0,0 +1,23 @@
+// ABOUTME: Fuzzes keygen_round2 with arbitrary state and round 1 package inputs
+// ABOUTME: Every input must come back as a FrostResult, never a panic
+
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    state_json: String,
+    participant_id: String,
+    round1_packages_json: String,
+}
+
+fuzz_target!(|input: Input| {
+    let output = frost_wasm_core::keygen_round2(
+        &input.state_json,
+        &input.participant_id,
+        &input.round1_packages_json,
+    );
+    let result: serde_json::Value = serde_json::from_str(&output).expect("output is a FrostResult");
+    assert!(result["success"].is_boolean());
+});
@@ -0,0 +1,27 @@
+// ABOUTME: Fuzzes signing_round2 with arbitrary state, key package, and signing package inputs
+// ABOUTME: Every input must come back as a FrostResult, never a panic
+
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    state_json: String,
+    participant_id: String,
+    key_package_json: String,
+    signing_package_json: String,
+    group_public_key_json: Option<String>,
+}
+
+fuzz_target!(|input: Input| {
+    let output = frost_wasm_core::signing_round2(
+        &input.state_json,
+        &input.participant_id,
+        &input.key_package_json,
+        &input.signing_package_json,
+        input.group_public_key_json,
+    );
+    let result: serde_json::Value = serde_json::from_str(&output).expect("output is a FrostResult");
+    assert!(result["success"].is_boolean());
+});
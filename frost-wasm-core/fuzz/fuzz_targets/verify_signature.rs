@@ -0,0 +1,23 @@
+// ABOUTME: Fuzzes verify_signature with an arbitrary message, signature, and group key
+// ABOUTME: Every input must come back as a FrostResult, never a panic
+
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    message: Vec<u8>,
+    signature_json: String,
+    group_public_key_json: String,
+}
+
+fuzz_target!(|input: Input| {
+    let output = frost_wasm_core::verify_signature(
+        &input.message,
+        &input.signature_json,
+        &input.group_public_key_json,
+    );
+    let result: serde_json::Value = serde_json::from_str(&output).expect("output is a FrostResult");
+    assert!(result["success"].is_boolean());
+});
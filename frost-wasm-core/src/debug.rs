@@ -0,0 +1,274 @@
+// ABOUTME: Diagnostics for FROST signatures that fail to verify and for diverging states
+// ABOUTME: Reports verification intermediates and per-entry state differences by hash
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use k256::elliptic_curve::{sec1::ToEncodedPoint, PrimeField};
+
+use frost_core::{keys::PublicKeyPackage, Ciphersuite};
+use frost_secp256k1::{self as frost, Secp256K1Sha256};
+
+use crate::{check_input_sizes, check_message_size, check_present, FrostError, FrostResult};
+
+// SerializeElement and SerializeScalar sizes, RFC 9591 section 6.5
+const ELEMENT_LEN: usize = 33;
+const SCALAR_LEN: usize = 32;
+
+// The verification equation's values for one signature (RFC 9591 section
+// 5.3): the challenge c = H2(R || PK || msg) and R' = zG - cPK, which must
+// equal the signature's R. Points are compressed SEC1 hex, the challenge is
+// big-endian scalar hex.
+#[derive(Serialize, Deserialize)]
+pub struct SignatureDebug {
+    pub valid: bool,
+    pub signature_r: String,
+    pub computed_r: String,
+    pub challenge: String,
+    pub r_matches: bool,
+}
+
+/// Verify a FROST signature and report the values verification computed
+///
+/// Takes the same inputs as `verify_signature`. A challenge that differs from
+/// the signers' points at a different message or group key; a matching
+/// challenge with `r_matches` false points at a bad share or commitment.
+/// Verification involves no secrets, so nothing sensitive is returned. The
+/// values are for the plain ciphersuite, not taproot signatures.
+#[wasm_bindgen]
+pub fn verify_signature_debug(message: &[u8], signature_json: &str, group_public_key_json: &str) -> String {
+    let result = (|| -> Result<SignatureDebug, FrostError> {
+        check_input_sizes(&[
+            ("signature_json", signature_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        check_message_size(message)?;
+
+        let signature: frost::Signature = serde_json::from_str(signature_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signature: {}", e)))?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+
+        let signature_bytes = signature.serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signature: {}", e)))?;
+        let group_key_bytes = group_public_key.verifying_key().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?;
+        if signature_bytes.len() != ELEMENT_LEN + SCALAR_LEN {
+            return Err(FrostError::MalformedInput(
+                format!("Expected a {}-byte signature, got {} bytes", ELEMENT_LEN + SCALAR_LEN, signature_bytes.len())
+            ));
+        }
+        let (r_bytes, z_bytes) = signature_bytes.split_at(ELEMENT_LEN);
+
+        let group_key = k256::PublicKey::from_sec1_bytes(&group_key_bytes)
+            .map_err(|_| FrostError::MalformedInput("Invalid group key point".to_string()))?
+            .to_projective();
+        let z = Option::<k256::Scalar>::from(k256::Scalar::from_repr(k256::FieldBytes::clone_from_slice(z_bytes)))
+            .ok_or(FrostError::MalformedInput("Signature z is not a valid scalar".to_string()))?;
+
+        let challenge = Secp256K1Sha256::H2(&[r_bytes, &group_key_bytes, message].concat());
+        let computed_r = k256::ProjectivePoint::GENERATOR * z - group_key * challenge;
+        let computed_r = computed_r.to_affine().to_encoded_point(true);
+
+        Ok(SignatureDebug {
+            valid: group_public_key.verifying_key().verify(message, &signature).is_ok(),
+            signature_r: hex::encode(r_bytes),
+            computed_r: hex::encode(computed_r.as_bytes()),
+            challenge: hex::encode(challenge.to_bytes()),
+            r_matches: computed_r.as_bytes() == r_bytes,
+        })
+    })();
+
+    match result {
+        Ok(debug) => {
+            serde_json::to_string(&FrostResult::ok(debug)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SignatureDebug>::err(e)).unwrap()
+        }
+    }
+}
+
+// One difference between two states: a top-level field, or with `key` an
+// entry of one of its maps. `a` and `b` are content hashes of each side's
+// value, absent where that side has no such field or entry.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct StateDifference {
+    pub field: String,
+    pub key: Option<String>,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StateDiff {
+    pub kind: String,
+    pub identical: bool,
+    pub differences: Vec<StateDifference>,
+}
+
+/// Compare two `KeygenState` or two `SigningState` values
+///
+/// Each state may be bare or wrapped in the `FrostResult` the round functions
+/// return. Map fields such as `round1_packages` are compared entry by entry,
+/// so the diff names the participants whose packages differ or are missing.
+/// Values are reported only as truncated SHA-256 hashes; states carry nonces
+/// and secret packages, and those never appear in the output.
+#[wasm_bindgen]
+pub fn diff_states(a_json: &str, b_json: &str) -> String {
+    let result = (|| -> Result<StateDiff, FrostError> {
+        check_input_sizes(&[("a_json", a_json), ("b_json", b_json)])?;
+        check_present(&[("a_json", a_json), ("b_json", b_json)])?;
+
+        let (kind_a, a) = parse_state("a_json", a_json)?;
+        let (kind_b, b) = parse_state("b_json", b_json)?;
+        if kind_a != kind_b {
+            return Err(FrostError::MalformedInput(
+                format!("Cannot compare a {} state with a {} state", kind_a, kind_b)
+            ));
+        }
+
+        let mut fields: Vec<&String> = a.keys().chain(b.keys()).collect();
+        fields.sort();
+        fields.dedup();
+
+        let mut differences = Vec::new();
+        for field in fields {
+            match (a.get(field), b.get(field)) {
+                (Some(Value::Object(entries_a)), Some(Value::Object(entries_b))) => {
+                    let mut keys: Vec<&String> = entries_a.keys().chain(entries_b.keys()).collect();
+                    keys.sort();
+                    keys.dedup();
+                    for key in keys {
+                        let (entry_a, entry_b) = (entries_a.get(key), entries_b.get(key));
+                        if entry_a != entry_b {
+                            differences.push(StateDifference {
+                                field: field.clone(),
+                                key: Some(key.clone()),
+                                a: entry_a.map(content_hash),
+                                b: entry_b.map(content_hash),
+                            });
+                        }
+                    }
+                }
+                (value_a, value_b) if value_a != value_b => differences.push(StateDifference {
+                    field: field.clone(),
+                    key: None,
+                    a: value_a.map(content_hash),
+                    b: value_b.map(content_hash),
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(StateDiff {
+            kind: kind_a.to_string(),
+            identical: differences.is_empty(),
+            differences,
+        })
+    })();
+
+    match result {
+        Ok(diff) => {
+            serde_json::to_string(&FrostResult::ok(diff)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<StateDiff>::err(e)).unwrap()
+        }
+    }
+}
+
+// Parse a state, unwrapping a FrostResult envelope, and tell keygen from
+// signing by its kind tag, or for older states by the fields only that kind has
+fn parse_state(field: &str, json: &str) -> Result<(&'static str, serde_json::Map<String, Value>), FrostError> {
+    let mut value: Value = serde_json::from_str(json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to parse {}: {}", field, e)))?;
+    if value.get("success").is_some() {
+        value = value.get_mut("data").map(Value::take).unwrap_or(Value::Null);
+    }
+
+    let Value::Object(state) = value else {
+        return Err(FrostError::MalformedInput(format!("{} does not hold a state", field)));
+    };
+    match state.get("kind").and_then(Value::as_str) {
+        Some("keygen") => Ok(("keygen", state)),
+        Some("signing") => Ok(("signing", state)),
+        Some(_) => Err(FrostError::MalformedInput(format!("{} has an unknown ceremony kind", field))),
+        // States saved before the kind tag
+        None if state.contains_key("max_participants") && state.contains_key("key_packages") => Ok(("keygen", state)),
+        None if state.contains_key("signers") && state.contains_key("signature_shares") => Ok(("signing", state)),
+        None => Err(FrostError::MalformedInput(format!("{} is neither a keygen nor a signing state", field))),
+    }
+}
+
+// First 8 bytes of SHA-256 over a value's JSON, enough to tell values apart
+// without revealing them
+fn content_hash(value: &Value) -> String {
+    let digest = Sha256::digest(value.to_string().as_bytes());
+    hex::encode(&digest[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn debug_values(message: &[u8], signature: &frost::Signature, group_public_key: &PublicKeyPackage<Secp256K1Sha256>) -> SignatureDebug {
+        let result: FrostResult<SignatureDebug> = serde_json::from_str(&verify_signature_debug(
+            message,
+            &serde_json::to_string(signature).unwrap(),
+            &serde_json::to_string(group_public_key).unwrap(),
+        )).unwrap();
+        result.data.unwrap()
+    }
+
+    #[test]
+    fn recomputed_nonce_point_explains_the_verification_result() {
+        let mut rng = rand::thread_rng();
+        let signing_key = frost::SigningKey::new(&mut rng);
+        let group_public_key = PublicKeyPackage::new(BTreeMap::new(), frost::VerifyingKey::from(&signing_key));
+        let signature = signing_key.sign(&mut rng, b"signed message");
+
+        let good = debug_values(b"signed message", &signature, &group_public_key);
+        assert!(good.valid);
+        assert!(good.r_matches);
+        assert_eq!(good.computed_r, good.signature_r);
+
+        let wrong_message = debug_values(b"another message", &signature, &group_public_key);
+        assert!(!wrong_message.valid);
+        assert!(!wrong_message.r_matches);
+        assert_ne!(wrong_message.challenge, good.challenge);
+    }
+
+    #[test]
+    fn state_diff_names_differing_entries_without_their_contents() {
+        let a = serde_json::json!({
+            "message": [1, 2, 3],
+            "current_round": 2,
+            "signers": ["alice", "bob"],
+            "round1_packages": {"alice": "secret-nonces-alice", "bob": "secret-nonces-bob"},
+            "signature_shares": {},
+            "final_signature": null,
+        });
+        let mut b = a.clone();
+        b["round1_packages"]["bob"] = "secret-nonces-bob-retried".into();
+        b["current_round"] = 1.into();
+        let wrapped_b = serde_json::to_string(&FrostResult::ok(b)).unwrap();
+
+        let output = diff_states(&a.to_string(), &wrapped_b);
+        assert!(!output.contains("secret-nonces"));
+        let result: FrostResult<StateDiff> = serde_json::from_str(&output).unwrap();
+        let diff = result.data.unwrap();
+        assert_eq!(diff.kind, "signing");
+        assert!(!diff.identical);
+        let changed: Vec<(&str, Option<&str>)> = diff.differences.iter()
+            .map(|difference| (difference.field.as_str(), difference.key.as_deref()))
+            .collect();
+        assert_eq!(changed, [("current_round", None), ("round1_packages", Some("bob"))]);
+
+        let same: FrostResult<StateDiff> = serde_json::from_str(&diff_states(&a.to_string(), &a.to_string())).unwrap();
+        assert!(same.data.unwrap().identical);
+    }
+}
@@ -0,0 +1,345 @@
+// ABOUTME: Threshold ECDH between the FROST group key and a peer pubkey
+// ABOUTME: Signers contribute proven partial points that combine into the NIP-44 conversation key
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use k256::elliptic_curve::{ops::Reduce, point::AffineCoordinates, sec1::ToEncodedPoint, Field, PrimeField};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use frost_core::keys::PublicKeyPackage;
+use frost_secp256k1::Secp256K1Sha256;
+
+use crate::nip44::{conversation_key_from_shared_x, lift_x};
+use crate::nostr::decode_fixed;
+use crate::{
+    check_input_sizes, check_present, ensure_threshold_met, identifier_from_hex, identifier_scalar,
+    identifier_to_hex, interpolate_at_zero, parse_key_package, sec1_point, secure_rng, FrostError, FrostResult,
+};
+
+// Separates ECDH share proofs from every other hash the crate computes
+const ECDH_PROOF_DOMAIN: &[u8] = b"frost-wasm-core/ecdh-dleq/v1";
+
+// A threshold ECDH session with one peer. `verifying_shares` holds the
+// signers' public shares from the group key package, against which each
+// partial's proof is checked.
+#[derive(Serialize, Deserialize)]
+pub struct EcdhState {
+    pub peer_pubkey: String,
+    pub threshold: u16,
+    pub signers: Vec<String>,
+    pub verifying_shares: BTreeMap<String, String>,
+    pub partials: BTreeMap<String, EcdhPartial>,
+}
+
+// One signer's `D_i = s_i * P` as compressed SEC1 hex, with a Chaum-Pedersen
+// proof `c || z` that it uses the same secret as the verifying share
+// `Y_i = s_i * G`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EcdhPartial {
+    pub point: String,
+    pub proof: String,
+}
+
+/// Open a threshold ECDH session between the group key and `peer_pubkey_hex`
+///
+/// `signers_json` is a JSON array of the identifiers (hex) taking part, at
+/// least `threshold` of them, all from `group_public_key_json`. Each signer
+/// then runs `ecdh_round2` with its key package, and `ecdh_aggregate`
+/// combines their partial points into the NIP-44 conversation key. No party
+/// learns the group secret or another signer's share; whoever runs the
+/// aggregation learns the conversation key, as any NIP-44 sender must.
+#[wasm_bindgen]
+pub fn ecdh_round1(peer_pubkey_hex: &str, signers_json: &str, group_public_key_json: &str, threshold: u16) -> String {
+    let result = (|| -> Result<EcdhState, FrostError> {
+        check_input_sizes(&[
+            ("peer_pubkey_hex", peer_pubkey_hex),
+            ("signers_json", signers_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        lift_x(peer_pubkey_hex)?;
+
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        let signers: Vec<String> = serde_json::from_str(signers_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signers: {}", e)))?;
+
+        let mut verifying_shares = BTreeMap::new();
+        for signer in &signers {
+            let identifier = identifier_from_hex(signer)?;
+            let verifying_share = group_public_key.verifying_shares().get(&identifier).ok_or(
+                FrostError::InvalidParticipant(format!("{} is not a member of the group", signer))
+            )?;
+            let verifying_share = verifying_share.serialize()
+                .map_err(|e| FrostError::MalformedInput(format!("Invalid verifying share: {}", e)))?;
+            if verifying_shares.insert(identifier_to_hex(&identifier), hex::encode(verifying_share)).is_some() {
+                return Err(FrostError::InvalidParticipant(format!("{} is listed twice", signer)));
+            }
+        }
+        ensure_threshold_met(threshold, verifying_shares.len())?;
+
+        Ok(EcdhState {
+            peer_pubkey: peer_pubkey_hex.to_lowercase(),
+            threshold,
+            signers: verifying_shares.keys().cloned().collect(),
+            verifying_shares,
+            partials: BTreeMap::new(),
+        })
+    })();
+
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<EcdhState>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Add this signer's partial ECDH point to the session
+///
+/// Computes `s_i * P` for the signer's share `s_i` and the peer's point `P`,
+/// with a proof that it matches the signer's verifying share. Only the
+/// partial and proof enter the state; the share never leaves this call.
+#[wasm_bindgen]
+pub fn ecdh_round2(state_json: &str, key_package_json: &str) -> String {
+    let result = (|| -> Result<EcdhState, FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("key_package_json", key_package_json),
+        ])?;
+        check_present(&[("key_package_json", key_package_json)])?;
+
+        let mut state = parse_ecdh_state(state_json)?;
+        let key_package = parse_key_package(key_package_json)?;
+        let signer = identifier_to_hex(key_package.identifier());
+
+        let expected_share = state.verifying_shares.get(&signer).ok_or(
+            FrostError::InvalidParticipant(format!("{} is not a signer in this session", signer))
+        )?;
+        let verifying_share = key_package.verifying_share().serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid verifying share: {}", e)))?;
+        if hex::encode(&verifying_share) != *expected_share {
+            return Err(FrostError::InvalidParticipant(
+                format!("Key package for {} belongs to another group", signer)
+            ));
+        }
+        if *key_package.min_signers() != state.threshold {
+            return Err(FrostError::InvalidParticipant(
+                format!("Key package for {} was not issued for a threshold of {}", signer, state.threshold)
+            ));
+        }
+        if state.partials.contains_key(&signer) {
+            return Err(FrostError::InvalidStateTransition(
+                format!("{} already contributed a partial", signer)
+            ));
+        }
+
+        let share_bytes = Zeroizing::new(key_package.signing_share().serialize());
+        let share = Option::<k256::Scalar>::from(k256::Scalar::from_repr(k256::FieldBytes::clone_from_slice(&share_bytes)))
+            .map(Zeroizing::new)
+            .ok_or(FrostError::MalformedInput("Signing share is not a valid scalar".to_string()))?;
+        let peer = lift_x(&state.peer_pubkey)?.to_projective();
+        let partial = peer * *share;
+
+        // Chaum-Pedersen: the same nonce k commits on G and on P
+        let nonce = Zeroizing::new(k256::Scalar::random(&mut secure_rng()?));
+        let challenge = proof_challenge(
+            &sec1_point(&verifying_share)?, &peer, &partial,
+            &(k256::ProjectivePoint::GENERATOR * *nonce), &(peer * *nonce),
+        );
+        let response = *nonce + challenge * *share;
+
+        state.partials.insert(signer, EcdhPartial {
+            point: point_hex(&partial),
+            proof: hex::encode([challenge.to_bytes(), response.to_bytes()].concat()),
+        });
+        Ok(state)
+    })();
+
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<EcdhState>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Combine every signer's partial into the NIP-44 conversation key, as hex
+///
+/// Each partial's proof is checked against the signer's verifying share, so
+/// a signer sending a wrong point is named with `InvalidParticipant` instead
+/// of silently spoiling the key. The partials are Lagrange-interpolated to
+/// `s * P`, whose x coordinate is the ECDH shared secret. The result equals
+/// `nip44_conversation_key` run by the peer against the group's x-only key.
+#[wasm_bindgen]
+pub fn ecdh_aggregate(state_json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
+
+        let state = parse_ecdh_state(state_json)?;
+        let missing: Vec<&str> = state.signers.iter()
+            .filter(|signer| !state.partials.contains_key(*signer))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Missing ECDH partials from {}", missing.join(", "))
+            ));
+        }
+
+        let peer = lift_x(&state.peer_pubkey)?.to_projective();
+        let mut points = Vec::with_capacity(state.signers.len());
+        for signer in &state.signers {
+            let verifying_share = state.verifying_shares.get(signer).ok_or(
+                FrostError::InvalidStateTransition(format!("No verifying share for {}", signer))
+            )?;
+            let verifying_share = sec1_point(&hex::decode(verifying_share)
+                .map_err(|e| FrostError::MalformedInput(format!("Invalid verifying share: {}", e)))?)?;
+            let partial = &state.partials[signer];
+            let point = sec1_point(&hex::decode(&partial.point)
+                .map_err(|e| FrostError::MalformedInput(format!("Invalid partial from {}: {}", signer, e)))?)?;
+
+            if !proof_is_valid(&partial.proof, &verifying_share, &peer, &point) {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Invalid ECDH partial from {}", signer)
+                ));
+            }
+            points.push((identifier_scalar(&identifier_from_hex(signer)?)?, point));
+        }
+
+        let shared = interpolate_at_zero(&points)?.to_affine();
+        let shared_x = Zeroizing::new(<[u8; 32]>::from(shared.x()));
+        Ok(hex::encode(conversation_key_from_shared_x(&shared_x).as_slice()))
+    })();
+
+    match result {
+        Ok(conversation_key) => {
+            serde_json::to_string(&FrostResult::ok(conversation_key)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+fn parse_ecdh_state(state_json: &str) -> Result<EcdhState, FrostError> {
+    let state_result: FrostResult<EcdhState> = serde_json::from_str(state_json)
+        .map_err(|e| FrostError::MalformedInput(e.to_string()))?;
+
+    state_result.data.ok_or(FrostError::InvalidStateTransition(
+        "Invalid state provided".to_string()
+    ))
+}
+
+fn point_hex(point: &k256::ProjectivePoint) -> String {
+    hex::encode(point.to_affine().to_encoded_point(true).as_bytes())
+}
+
+// c = H(domain || Y_i || P || D_i || kG || kP), reduced to a scalar
+fn proof_challenge(
+    verifying_share: &k256::ProjectivePoint,
+    peer: &k256::ProjectivePoint,
+    partial: &k256::ProjectivePoint,
+    nonce_g: &k256::ProjectivePoint,
+    nonce_p: &k256::ProjectivePoint,
+) -> k256::Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(ECDH_PROOF_DOMAIN);
+    for point in [verifying_share, peer, partial, nonce_g, nonce_p] {
+        hasher.update(point.to_affine().to_encoded_point(true).as_bytes());
+    }
+    <k256::Scalar as Reduce<k256::U256>>::reduce_bytes(&hasher.finalize())
+}
+
+// Recompute kG = zG - cY_i and kP = zP - cD_i and check they hash back to c
+fn proof_is_valid(
+    proof_hex: &str,
+    verifying_share: &k256::ProjectivePoint,
+    peer: &k256::ProjectivePoint,
+    partial: &k256::ProjectivePoint,
+) -> bool {
+    let Ok(proof) = decode_fixed::<64>(proof_hex, "ECDH proof") else {
+        return false;
+    };
+    let scalar = |bytes: &[u8]| Option::<k256::Scalar>::from(
+        k256::Scalar::from_repr(k256::FieldBytes::clone_from_slice(bytes))
+    );
+    let (Some(challenge), Some(response)) = (scalar(&proof[..32]), scalar(&proof[32..])) else {
+        return false;
+    };
+
+    let nonce_g = k256::ProjectivePoint::GENERATOR * response - *verifying_share * challenge;
+    let nonce_p = *peer * response - *partial * challenge;
+    proof_challenge(verifying_share, peer, partial, &nonce_g, &nonce_p) == challenge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frost_secp256k1 as frost;
+
+    const PEER_SECRET: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+
+    fn data<T: serde::de::DeserializeOwned>(result_json: &str) -> T {
+        let result: FrostResult<T> = serde_json::from_str(result_json).unwrap();
+        result.data.unwrap_or_else(|| panic!("{:?}", result.error))
+    }
+
+    fn wrap(state: &EcdhState) -> String {
+        serde_json::to_string(&FrostResult::ok(state)).unwrap()
+    }
+
+    // A 2-of-3 group split from a known secret, with the secret as hex
+    fn split_group() -> (String, Vec<String>, String) {
+        let mut rng = rand::thread_rng();
+        let signing_key = frost::SigningKey::new(&mut rng);
+        let (shares, group_public_key) = frost::keys::split(
+            &signing_key, 3, 2, frost::keys::IdentifierList::Default, &mut rng,
+        ).unwrap();
+        let shares = shares.values().map(|share| serde_json::to_string(share).unwrap()).collect();
+        (
+            hex::encode(signing_key.serialize()),
+            shares,
+            serde_json::to_string(&group_public_key).unwrap(),
+        )
+    }
+
+    fn xonly_pubkey(secret_key_hex: &str) -> String {
+        let secret_key = k256::SecretKey::from_slice(&hex::decode(secret_key_hex).unwrap()).unwrap();
+        hex::encode(secret_key.public_key().as_affine().x())
+    }
+
+    fn signer_identifiers(shares: &[String]) -> Vec<String> {
+        shares.iter()
+            .map(|share| identifier_to_hex(parse_key_package(share).unwrap().identifier()))
+            .collect()
+    }
+
+    #[test]
+    fn aggregation_names_a_signer_whose_partial_is_wrong() {
+        let (_, shares, group_public_key) = split_group();
+        let signers = signer_identifiers(&shares[..2]);
+
+        let mut state: EcdhState = data(&ecdh_round1(
+            &xonly_pubkey(PEER_SECRET), &serde_json::to_string(&signers).unwrap(), &group_public_key, 2,
+        ));
+
+        let early: FrostResult<String> = serde_json::from_str(&ecdh_aggregate(&wrap(&state))).unwrap();
+        assert!(early.error.unwrap().contains("Missing ECDH partials"));
+
+        for share in &shares[..2] {
+            state = data(&ecdh_round2(&wrap(&state), share));
+        }
+        let swapped = state.partials[&signers[1]].point.clone();
+        state.partials.get_mut(&signers[0]).unwrap().point = swapped;
+
+        let result: FrostResult<String> = serde_json::from_str(&ecdh_aggregate(&wrap(&state))).unwrap();
+        assert!(result.error.unwrap().contains(&format!("Invalid ECDH partial from {}", signers[0])));
+    }
+}
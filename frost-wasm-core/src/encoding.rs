@@ -0,0 +1,130 @@
+// ABOUTME: Strict hex and base64url codecs and signature encodings shared by the module and its callers
+// ABOUTME: Malformed encodings are rejected as MalformedInput instead of guessed at
+
+use wasm_bindgen::prelude::*;
+use base64::{engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD}, Engine};
+use frost_secp256k1 as frost;
+
+use crate::{check_input_sizes, FrostError, FrostResult};
+
+// Encodings signature_to_format produces
+const SIGNATURE_FORMATS: [&str; 3] = ["compact64", "hex", "base64"];
+
+// Hex decoding used for every hex input the module accepts. Either case is
+// accepted; odd lengths and non-hex characters are rejected.
+pub(crate) fn hex_to_bytes(value_hex: &str, field: &str) -> Result<Vec<u8>, FrostError> {
+    hex::decode(value_hex)
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid {} hex: {}", field, e)))
+}
+
+/// Encode bytes as lowercase hex, the form every hex output of this module takes
+#[wasm_bindgen]
+pub fn encode_hex(bytes: &[u8]) -> String {
+    serde_json::to_string(&FrostResult::ok(hex::encode(bytes))).unwrap()
+}
+
+/// Decode hex into bytes, rejecting odd lengths and non-hex characters
+#[wasm_bindgen]
+pub fn decode_hex(value_hex: &str) -> String {
+    let result = (|| -> Result<Vec<u8>, FrostError> {
+        check_input_sizes(&[("value_hex", value_hex)])?;
+        hex_to_bytes(value_hex, "value")
+    })();
+
+    match result {
+        Ok(bytes) => {
+            serde_json::to_string(&FrostResult::ok(bytes)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<Vec<u8>>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Encode bytes as unpadded base64url (RFC 4648 section 5)
+#[wasm_bindgen]
+pub fn encode_base64url(bytes: &[u8]) -> String {
+    serde_json::to_string(&FrostResult::ok(URL_SAFE_NO_PAD.encode(bytes))).unwrap()
+}
+
+/// Decode unpadded base64url into bytes
+///
+/// Padding, the standard alphabet's `+` and `/`, whitespace, and encodings
+/// with nonzero trailing bits are all rejected, so each byte string has
+/// exactly one accepted encoding.
+#[wasm_bindgen]
+pub fn decode_base64url(value_base64url: &str) -> String {
+    let result = (|| -> Result<Vec<u8>, FrostError> {
+        check_input_sizes(&[("value_base64url", value_base64url)])?;
+        URL_SAFE_NO_PAD.decode(value_base64url)
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid base64url: {}", e)))
+    })();
+
+    match result {
+        Ok(bytes) => {
+            serde_json::to_string(&FrostResult::ok(bytes)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<Vec<u8>>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Re-encode a signature for consumers that expect another encoding
+///
+/// `signature_json` is a FROST signature as ceremonies return it (65 bytes:
+/// compressed `R`, then `z`) or a 64-byte BIP340 signature hex string, such as
+/// a taproot ceremony's. `format` is one of:
+///
+/// - `"compact64"`: the 64-byte BIP340 form `x(R) || z` as hex, as Nostr's
+///   `sig` field and Bitcoin key-path witnesses take it
+/// - `"hex"`: the signature's bytes as lowercase hex
+/// - `"base64"`: the signature's bytes as padded standard base64
+///
+/// BIP340 verifiers lift `x(R)` to the even-y point, so a FROST signature
+/// whose `R` has odd y has no `compact64` form and is rejected rather than
+/// returned in a form that won't verify. Other formats are `MalformedInput`
+/// errors naming the supported ones.
+#[wasm_bindgen]
+pub fn signature_to_format(signature_json: &str, format: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("signature_json", signature_json), ("format", format)])?;
+        let signature_hex: String = serde_json::from_str(signature_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Signature must be a JSON hex string: {}", e)))?;
+        let signature = hex_to_bytes(&signature_hex, "signature")?;
+        match signature.len() {
+            64 => {}
+            65 => {
+                frost::Signature::deserialize(&signature)
+                    .map_err(|e| FrostError::MalformedInput(format!("Invalid signature: {}", e)))?;
+            }
+            len => {
+                return Err(FrostError::MalformedInput(format!("Signature must be 64 or 65 bytes, got {}", len)));
+            }
+        }
+
+        match format {
+            "hex" => Ok(hex::encode(&signature)),
+            "base64" => Ok(STANDARD.encode(&signature)),
+            "compact64" => match signature.len() {
+                64 => Ok(hex::encode(&signature)),
+                _ if signature[0] == 0x02 => Ok(hex::encode(&signature[1..])),
+                _ => Err(FrostError::MalformedInput(
+                    "Signature R has odd y, so it has no 64-byte BIP340 form".to_string()
+                )),
+            },
+            other => Err(FrostError::MalformedInput(format!(
+                "Unsupported signature format {}; expected one of {}", other, SIGNATURE_FORMATS.join(", ")
+            ))),
+        }
+    })();
+
+    match result {
+        Ok(encoded) => {
+            serde_json::to_string(&FrostResult::ok(encoded)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
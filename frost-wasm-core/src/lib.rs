@@ -3,18 +3,64 @@
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicU16, AtomicUsize, Ordering};
+#[cfg(feature = "full")]
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "full")]
+use std::cell::RefCell;
+#[cfg(feature = "full")]
+use zeroize::Zeroize;
+#[cfg(feature = "full")]
+use zeroize::Zeroizing;
 
 // FROST imports
-use frost_secp256k1::{self as frost, Secp256K1Sha256, keys::KeyPackage};
+use frost_secp256k1::{self as frost, Secp256K1Sha256};
+use frost_core::keys::PublicKeyPackage;
+#[cfg(feature = "full")]
+use frost_secp256k1::keys::KeyPackage;
+#[cfg(feature = "full")]
 use frost_core::{
-    keys::{dkg, PublicKeyPackage, IdentifierList},
+    keys::{dkg, IdentifierList},
     round1, round2,
     Identifier,
 };
-use frost_secp256k1::rand_core::OsRng;
+#[cfg(feature = "full")]
+use frost_secp256k1::rand_core::{CryptoRng, OsRng, RngCore};
+#[cfg(feature = "full")]
+use rand::{rngs::StdRng, SeedableRng};
+#[cfg(feature = "full")]
+use k256::elliptic_curve::{sec1::ToEncodedPoint, PrimeField};
+#[cfg(feature = "full")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "full")]
+use aes_gcm::{aead::{Aead, KeyInit, Payload}, Aes256Gcm, Nonce};
+#[cfg(feature = "full")]
+use chacha20::{cipher::{KeyIvInit, StreamCipher}, ChaCha20};
+#[cfg(feature = "full")]
+use hkdf::Hkdf;
+
+use encoding::hex_to_bytes;
+
+#[cfg(feature = "debug-tools")]
+mod debug;
+#[cfg(feature = "full")]
+mod ecdh;
+mod encoding;
+mod nostr;
+#[cfg(feature = "full")]
+mod nip44;
+#[cfg(feature = "full")]
+mod seal;
+#[cfg(feature = "full")]
+mod subkeys;
+#[cfg(feature = "full")]
+mod taproot;
+#[cfg(feature = "full")]
+mod wire;
 
 // Type aliases for clarity
+#[cfg(feature = "full")]
 type FrostIdentifier = Identifier<Secp256K1Sha256>;
 
 // Set up panic hook for better debugging
@@ -23,7 +69,10 @@ pub fn main() {
     console_error_panic_hook::set_once();
 }
 
-// Error types for the WASM interface
+// Error types for the WASM interface. `MalformedInput` is for caller data that
+// doesn't have the expected shape or encoding, `SerializationError` for
+// failing to serialize our own output, and `KeygenError`/`SigningError` only
+// for failures of the cryptography itself.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum FrostError {
     InvalidParticipant(String),
@@ -32,28 +81,208 @@ pub enum FrostError {
     SigningError(String),
     SerializationError(String),
     InvalidStateTransition(String),
+    RngUnavailable(String),
+    IntegrityError(String),
+    InputTooLarge { field: String, limit: usize, actual: usize },
+    MalformedInput(String),
+    CorruptedShare(String),
+    TooManyCeremonies { limit: usize },
+}
+
+/// Stable numeric codes for `FrostError` variants, reported as `error_code`
+///
+/// Codes are append-only: a new variant gets the next unused number, and an
+/// existing code is never renumbered or reused, so JS can switch on them.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrostErrorCode {
+    InvalidParticipant = 1,
+    InsufficientParticipants = 2,
+    KeygenError = 3,
+    SigningError = 4,
+    SerializationError = 5,
+    InvalidStateTransition = 6,
+    RngUnavailable = 7,
+    IntegrityError = 8,
+    InputTooLarge = 9,
+    MalformedInput = 10,
+    CorruptedShare = 11,
+    TooManyCeremonies = 12,
+}
+
+impl FrostError {
+    fn code(&self) -> FrostErrorCode {
+        match self {
+            FrostError::InvalidParticipant(_) => FrostErrorCode::InvalidParticipant,
+            FrostError::InsufficientParticipants { .. } => FrostErrorCode::InsufficientParticipants,
+            FrostError::KeygenError(_) => FrostErrorCode::KeygenError,
+            FrostError::SigningError(_) => FrostErrorCode::SigningError,
+            FrostError::SerializationError(_) => FrostErrorCode::SerializationError,
+            FrostError::InvalidStateTransition(_) => FrostErrorCode::InvalidStateTransition,
+            FrostError::RngUnavailable(_) => FrostErrorCode::RngUnavailable,
+            FrostError::IntegrityError(_) => FrostErrorCode::IntegrityError,
+            FrostError::InputTooLarge { .. } => FrostErrorCode::InputTooLarge,
+            FrostError::MalformedInput(_) => FrostErrorCode::MalformedInput,
+            FrostError::CorruptedShare(_) => FrostErrorCode::CorruptedShare,
+            FrostError::TooManyCeremonies { .. } => FrostErrorCode::TooManyCeremonies,
+        }
+    }
+}
+
+// Human readable text for each error code
+const ERROR_MESSAGES: [(FrostErrorCode, &str); 12] = [
+    (FrostErrorCode::InvalidParticipant, "The participant is unknown or not allowed in this ceremony"),
+    (FrostErrorCode::InsufficientParticipants, "Not enough participants to meet the threshold"),
+    (FrostErrorCode::KeygenError, "Key generation failed"),
+    (FrostErrorCode::SigningError, "Signing failed"),
+    (FrostErrorCode::SerializationError, "A result could not be serialized"),
+    (FrostErrorCode::InvalidStateTransition, "The ceremony is not in the right round for this step"),
+    (FrostErrorCode::RngUnavailable, "No secure random number source is available"),
+    (FrostErrorCode::IntegrityError, "The ceremony state failed its integrity check"),
+    (FrostErrorCode::InputTooLarge, "An input is larger than this server accepts"),
+    (FrostErrorCode::MalformedInput, "An input is not well-formed JSON or has the wrong structure"),
+    (FrostErrorCode::CorruptedShare, "A stored share failed its checksum and may be corrupted"),
+    (FrostErrorCode::TooManyCeremonies, "Too many ceremonies are open on this server"),
+];
+
+/// Describe an error code in plain English, for UIs that don't show raw errors
+#[wasm_bindgen]
+pub fn error_message(code: u32) -> String {
+    ERROR_MESSAGES.iter()
+        .find(|(known, _)| *known as u32 == code)
+        .map_or("Unknown error", |(_, message)| *message)
+        .to_string()
+}
+
+// Which ceremony a state belongs to, serialized as the state's `kind` so a
+// state handed to the other ceremony's functions is caught up front
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CeremonyKind {
+    Keygen,
+    Signing,
+}
+
+impl CeremonyKind {
+    // States written before `kind` existed are told apart by their fields
+    #[cfg(feature = "full")]
+    fn of_state(state: &serde_json::Value) -> Option<Self> {
+        match state.get("kind") {
+            Some(kind) => serde_json::from_value(kind.clone()).ok(),
+            None if state.get("max_participants").is_some() => Some(CeremonyKind::Keygen),
+            None if state.get("signers").is_some() => Some(CeremonyKind::Signing),
+            None => None,
+        }
+    }
+    
+    #[cfg(feature = "full")]
+    fn name(self) -> &'static str {
+        match self {
+            CeremonyKind::Keygen => "keygen",
+            CeremonyKind::Signing => "signing",
+        }
+    }
+    
+    fn keygen() -> Self {
+        CeremonyKind::Keygen
+    }
+    
+    fn signing() -> Self {
+        CeremonyKind::Signing
+    }
 }
 
 // State for key generation ceremony
 #[derive(Serialize, Deserialize, Clone)]
 pub struct KeygenState {
+    #[serde(default = "CeremonyKind::keygen")]
+    pub kind: CeremonyKind,
     pub threshold: u16,
     pub max_participants: u16,
     pub current_round: u8,
     pub round1_packages: BTreeMap<String, String>,
     pub key_packages: BTreeMap<String, String>,
     pub group_public_key: Option<String>,
+    #[serde(default)]
+    pub participant_identifiers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub ceremony_id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    pub commit_reveal: bool,
+    #[serde(default)]
+    pub round1_commitments: BTreeMap<String, String>,
+    #[serde(default)]
+    pub removed_participants: BTreeSet<String>,
+}
+
+// Tag of the object form keygen round 1 stores each participant's entry in
+#[cfg(feature = "full")]
+const ROUND1_ENTRY_FORMAT: &str = "frost-dkg-round1/v2";
+
+// Reported in `deprecations` when an entry in the `[secret, package]` array
+// form written by earlier versions is read
+#[cfg(feature = "full")]
+const LEGACY_ROUND1_MARKER: &str = "legacy_round1_array";
+
+// A participant's keygen round 1 secret and public package as stored in
+// `KeygenState::round1_packages` and handed to keygen_round2
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+struct Round1Entry {
+    format: String,
+    secret: dkg::round1::SecretPackage<Secp256K1Sha256>,
+    package: dkg::round1::Package<Secp256K1Sha256>,
 }
 
-// State for signing ceremony  
+// State for signing ceremony. Everything round 2 needs (nonces, commitments,
+// identifiers, shares) lives here, and the module keeps nothing between calls,
+// so a ceremony can resume in a fresh instance from its last serialized state.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct SigningState {
+    #[serde(default = "CeremonyKind::signing")]
+    pub kind: CeremonyKind,
     pub message: Vec<u8>,
     pub current_round: u8,
     pub signers: Vec<String>,
     pub round1_packages: BTreeMap<String, String>,
     pub signature_shares: BTreeMap<String, String>,
     pub final_signature: Option<String>,
+    #[serde(default)]
+    pub signer_identifiers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub threshold: Option<u16>,
+    #[serde(default)]
+    pub ceremony_id: Option<String>,
+    #[serde(default)]
+    pub validated_identifiers: BTreeSet<String>,
+    #[serde(default)]
+    pub imported_nonce_ids: BTreeSet<String>,
+    #[serde(default)]
+    pub taproot: Option<TaprootSpend>,
+    #[serde(default)]
+    pub group_verifying_key: Option<String>,
+    // Round 1 commitments by identifier, for a coordinator resumed from a transcript
+    #[serde(default)]
+    pub transcript_commitments: BTreeMap<String, String>,
+    // Verifying share implied by each committed signer's signing share, by identifier
+    #[serde(default)]
+    pub signer_verifying_shares: BTreeMap<String, String>,
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+// The taproot output a Bitcoin signing ceremony's signature must be valid
+// for: the group key tweaked with `merkle_root` (hex), or with no merkle root
+// for a key-only (BIP86) output
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaprootSpend {
+    pub merkle_root: Option<String>,
 }
 
 // Result type for WASM functions
@@ -62,6 +291,12 @@ pub struct FrostResult<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<BTreeMap<String, u64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deprecations: Vec<String>,
 }
 
 impl<T> FrostResult<T> {
@@ -70,6 +305,9 @@ impl<T> FrostResult<T> {
             success: true,
             data: Some(data),
             error: None,
+            error_code: None,
+            timings: None,
+            deprecations: Vec::new(),
         }
     }
     
@@ -78,29 +316,595 @@ impl<T> FrostResult<T> {
             success: false,
             data: None,
             error: Some(format!("{:?}", error)),
+            error_code: Some(error.code() as u32),
+            timings: None,
+            deprecations: Vec::new(),
+        }
+    }
+    
+    #[cfg(feature = "full")]
+    fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings.0;
+        self
+    }
+    
+    #[cfg(feature = "full")]
+    fn with_deprecations(mut self, deprecations: Vec<String>) -> Self {
+        self.deprecations = deprecations;
+        self
+    }
+}
+
+// === TIMING FUNCTIONS ===
+
+// Whether round functions should report per-step timings
+#[cfg(feature = "full")]
+static COLLECT_TIMINGS: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable per-step timing collection in returned results
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn set_collect_timings(enabled: bool) {
+    COLLECT_TIMINGS.store(enabled, Ordering::Relaxed);
+}
+
+// Nanoseconds spent per ceremony step, present only when collection is enabled
+#[cfg(feature = "full")]
+struct Timings(Option<BTreeMap<String, u64>>);
+
+#[cfg(feature = "full")]
+impl Timings {
+    fn new() -> Self {
+        Self(COLLECT_TIMINGS.load(Ordering::Relaxed).then(BTreeMap::new))
+    }
+    
+    fn measure<T>(&mut self, step: &str, f: impl FnOnce() -> T) -> T {
+        let Some(timings) = self.0.as_mut() else {
+            return f();
+        };
+        
+        let start = now_nanos();
+        let output = f();
+        if let (Some(start), Some(end)) = (start, now_nanos()) {
+            *timings.entry(step.to_string()).or_insert(0) += end.saturating_sub(start);
+        }
+        output
+    }
+}
+
+// Read the host's high resolution clock (std Instant is unavailable in WASM).
+// Note that Workers only advance `performance.now()` across I/O, so timings
+// there are coarse; browsers and Node report real elapsed time.
+#[cfg(all(feature = "full", target_arch = "wasm32"))]
+fn now_nanos() -> Option<u64> {
+    use wasm_bindgen::JsCast;
+    
+    let performance = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance"))
+        .ok()?
+        .dyn_into::<web_sys::Performance>()
+        .ok()?;
+    Some((performance.now() * 1_000_000.0) as u64)
+}
+
+#[cfg(all(feature = "full", not(target_arch = "wasm32")))]
+fn now_nanos() -> Option<u64> {
+    None
+}
+
+// === PARALLEL SHARE VERIFICATION ===
+
+// Share sets smaller than this are verified serially; below it the work is
+// too small to pay for handing out to the thread pool
+#[cfg(feature = "parallel")]
+const PARALLEL_VERIFY_MIN_SHARES: usize = 8;
+
+// Whether share verification may use the thread pool, for benchmarking the
+// serial path against the parallel one in the same build
+#[cfg(feature = "full")]
+static PARALLEL_VERIFICATION: AtomicBool = AtomicBool::new(true);
+
+// wasm-bindgen-rayon's `initThreadPool(threads)`, which the host awaits once
+// before signing to start web workers for the rayon pool
+#[cfg(all(feature = "parallel", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+/// Allow or prevent share verification from using the rayon thread pool
+///
+/// Has an effect only in builds with the `parallel` feature, and there only
+/// once the host has started the pool with `initThreadPool`. Verification
+/// results are the same either way.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn set_parallel_verification(enabled: bool) {
+    PARALLEL_VERIFICATION.store(enabled, Ordering::Relaxed);
+}
+
+// Check each signature share against its signer's verifying share, failing on
+// the first invalid share in identifier order. Large sets are split across
+// the rayon pool when one is running; find_map_first keeps the reported share
+// the one a serial pass would report.
+#[cfg(feature = "full")]
+fn verify_signature_shares(
+    signature_shares: &BTreeMap<FrostIdentifier, round2::SignatureShare<Secp256K1Sha256>>,
+    signing_package: &frost::SigningPackage,
+    group_public_key: &PublicKeyPackage<Secp256K1Sha256>,
+    signer_name: impl Fn(&FrostIdentifier) -> String + Sync,
+) -> Result<(), FrostError> {
+    let verify = |(identifier, share): (&FrostIdentifier, &round2::SignatureShare<Secp256K1Sha256>)| -> Result<(), FrostError> {
+        let verifying_share = group_public_key.verifying_shares().get(identifier)
+            .ok_or_else(|| FrostError::InvalidParticipant(format!("No verifying share for {}", signer_name(identifier))))?;
+        frost_core::verify_signature_share(
+            *identifier,
+            verifying_share,
+            share,
+            signing_package,
+            group_public_key.verifying_key(),
+        ).map_err(|e| FrostError::SigningError(format!("Invalid signature share from {}: {}", signer_name(identifier), e)))
+    };
+    
+    #[cfg(feature = "parallel")]
+    if signature_shares.len() >= PARALLEL_VERIFY_MIN_SHARES
+        && PARALLEL_VERIFICATION.load(Ordering::Relaxed)
+        && rayon::current_num_threads() > 1
+    {
+        use rayon::prelude::*;
+        
+        return match signature_shares.par_iter().find_map_first(|entry| verify(entry).err()) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        };
+    }
+    
+    signature_shares.iter().try_for_each(verify)
+}
+
+// === SERIALIZATION BUFFER POOL ===
+
+// A pooled buffer that grew past this is dropped after use instead of kept,
+// so one oversized result doesn't pin its memory for the life of the isolate
+#[cfg(feature = "full")]
+const MAX_POOLED_BUFFER_BYTES: usize = 1 << 20;
+
+// Whether round functions serialize their results through the pooled buffer
+#[cfg(feature = "full")]
+static BUFFER_POOLING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "full")]
+thread_local! {
+    static SERIALIZATION_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Serialize round function results through one reusable buffer
+///
+/// Each round function returns the whole ceremony state as JSON. Without
+/// pooling that JSON is built in a fresh buffer grown by repeated
+/// reallocation, and the freed steps fragment the heap of a long-running
+/// isolate. With pooling, each result is written into a buffer already grown
+/// by earlier calls and handed back without a copy, and an empty buffer of
+/// the same capacity takes its place, so none of a result's nonces or key
+/// packages stay behind in the pool. Turning pooling off frees it.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn set_buffer_pooling(enabled: bool) {
+    BUFFER_POOLING.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        SERIALIZATION_BUFFER.with(|buffer| *buffer.borrow_mut() = Vec::new());
+    }
+}
+
+/// Size of this module's linear memory in bytes
+///
+/// WASM memory never shrinks, so this is the most the isolate has needed so
+/// far. Zero outside WASM.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn wasm_memory_bytes() -> usize {
+    #[cfg(target_arch = "wasm32")]
+    {
+        core::arch::wasm32::memory_size(0) * 65536
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0
+    }
+}
+
+// Serialize an export's result, through the pooled buffer when pooling is on
+#[cfg(feature = "full")]
+fn result_json<T: Serialize>(result: &FrostResult<T>) -> String {
+    if !BUFFER_POOLING.load(Ordering::Relaxed) {
+        return serde_json::to_string(result).unwrap();
+    }
+    
+    SERIALIZATION_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        serde_json::to_writer(&mut *buffer, result).unwrap();
+        let capacity = buffer.capacity();
+        let json = std::mem::take(&mut *buffer);
+        if capacity <= MAX_POOLED_BUFFER_BYTES {
+            *buffer = Vec::with_capacity(capacity);
+        }
+        // serde_json only writes UTF-8
+        String::from_utf8(json).unwrap()
+    })
+}
+
+// === LOGGING ===
+
+// Host function receiving ceremony events; nothing is logged until one is set
+#[cfg(feature = "full")]
+thread_local! {
+    static LOG_CALLBACK: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+}
+
+/// Register a function to receive ceremony events, or pass nothing to stop logging
+///
+/// The function is called with one JSON string per event, tagged by `event`:
+/// `round_started`, `package_accepted`, or `aggregation_attempted`. Events
+/// carry only ceremony kinds, rounds, participant labels, counts, and ceremony
+/// ids; keys, shares, nonces, and messages are never included. Errors thrown
+/// by the function are ignored so logging can't fail a ceremony.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn set_log_callback(callback: Option<js_sys::Function>) {
+    LOG_CALLBACK.with(|registered| *registered.borrow_mut() = callback);
+}
+
+
+// Everything that can be logged. Only public, non-secret fields belong here.
+#[cfg(feature = "full")]
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum LogEvent<'a> {
+    RoundStarted {
+        ceremony: CeremonyKind,
+        round: u8,
+        ceremony_id: Option<&'a str>,
+    },
+    PackageAccepted {
+        ceremony: CeremonyKind,
+        round: u8,
+        participant: &'a str,
+        ceremony_id: Option<&'a str>,
+    },
+    AggregationAttempted {
+        shares: usize,
+        success: bool,
+        ceremony_id: Option<&'a str>,
+    },
+}
+
+// Hand an event to the registered callback, if any. The callback is cloned out
+// first so it may itself call set_log_callback.
+#[cfg(feature = "full")]
+fn log_event(event: LogEvent) {
+    let Some(callback) = LOG_CALLBACK.with(|registered| registered.borrow().clone()) else {
+        return;
+    };
+    
+    if let Ok(event_json) = serde_json::to_string(&event) {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&event_json));
+    }
+}
+
+// === KEY PACKAGE HANDLES ===
+
+// Key packages loaded once for a ceremony, boxed so checking one out and back
+// in moves a pointer instead of copying the signing share
+#[cfg(feature = "full")]
+struct LoadedKeyPackages {
+    next_handle: u32,
+    loaded: BTreeMap<u32, Box<Zeroizing<KeyPackage>>>,
+}
+
+#[cfg(feature = "full")]
+thread_local! {
+    static KEY_PACKAGES: RefCell<LoadedKeyPackages> = const {
+        RefCell::new(LoadedKeyPackages { next_handle: 1, loaded: BTreeMap::new() })
+    };
+}
+
+// Reference to a loaded key package, passed to the round functions in place
+// of the key package JSON
+#[derive(Serialize, Deserialize)]
+pub struct KeyPackageHandle {
+    pub key_package_handle: u32,
+}
+
+/// Deserialize a key package once and keep it in module memory for a ceremony
+///
+/// Returns `{"key_package_handle": n}`; pass that JSON wherever
+/// `signing_round1`, `signing_round2`, or `fulfill_signing_request` take a key
+/// package, so the secret share isn't parsed again for every round. The key
+/// package is zeroized once `signing_round2` or `fulfill_signing_request` has
+/// produced the signer's share with it, which ends the handle. Call
+/// `release_key_package` for ceremonies that end before that.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn load_key_package(key_package_json: &str) -> String {
+    let result = (|| -> Result<KeyPackageHandle, FrostError> {
+        check_input_sizes(&[("key_package_json", key_package_json)])?;
+        check_present(&[("key_package_json", key_package_json)])?;
+        
+        let key_package = Box::new(Zeroizing::new(parse_key_package(key_package_json)?));
+        let key_package_handle = KEY_PACKAGES.with(|table| {
+            let mut table = table.borrow_mut();
+            let handle = table.next_handle;
+            table.next_handle = handle.wrapping_add(1).max(1);
+            table.loaded.insert(handle, key_package);
+            handle
+        });
+        
+        Ok(KeyPackageHandle { key_package_handle })
+    })();
+    
+    match result {
+        Ok(handle) => {
+            serde_json::to_string(&FrostResult::ok(handle)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<KeyPackageHandle>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Zeroize a loaded key package and end its handle
+///
+/// Returns whether the handle was still loaded.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn release_key_package(key_package_handle: u32) -> String {
+    let released = KEY_PACKAGES.with(|table| table.borrow_mut().loaded.remove(&key_package_handle)).is_some();
+    serde_json::to_string(&FrostResult::ok(released)).unwrap()
+}
+
+// A key package for the duration of one round function call. Loaded key
+// packages are checked out of the table and go back into it on drop unless
+// released; parsed ones are zeroized on drop.
+#[cfg(feature = "full")]
+struct SignerKeyPackage {
+    handle: Option<u32>,
+    key_package: Option<Box<Zeroizing<KeyPackage>>>,
+}
+
+#[cfg(feature = "full")]
+impl SignerKeyPackage {
+    // Either a `KeyPackageHandle` or key package JSON
+    fn checkout(key_package_json: &str) -> Result<Self, FrostError> {
+        let Ok(KeyPackageHandle { key_package_handle }) = serde_json::from_str(key_package_json) else {
+            return Ok(SignerKeyPackage {
+                handle: None,
+                key_package: Some(Box::new(Zeroizing::new(parse_key_package(key_package_json)?))),
+            });
+        };
+        
+        let key_package = KEY_PACKAGES.with(|table| table.borrow_mut().loaded.remove(&key_package_handle))
+            .ok_or(FrostError::MalformedInput(
+                format!("Key package handle {} is not loaded", key_package_handle)
+            ))?;
+        Ok(SignerKeyPackage { handle: Some(key_package_handle), key_package: Some(key_package) })
+    }
+    
+    // The signer is done with this ceremony, so a loaded key package is
+    // zeroized instead of going back into the table
+    fn release(mut self) {
+        self.handle = None;
+    }
+}
+
+#[cfg(feature = "full")]
+impl std::ops::Deref for SignerKeyPackage {
+    type Target = KeyPackage;
+    
+    fn deref(&self) -> &KeyPackage {
+        self.key_package.as_ref().expect("key package is present until drop")
+    }
+}
+
+#[cfg(feature = "full")]
+impl Drop for SignerKeyPackage {
+    fn drop(&mut self) {
+        if let (Some(handle), Some(key_package)) = (self.handle, self.key_package.take()) {
+            KEY_PACKAGES.with(|table| table.borrow_mut().loaded.insert(handle, key_package));
+        }
+    }
+}
+
+// === CEREMONY REGISTRY ===
+
+// Ceremonies open in this isolate, bounded so a flood of requests can't grow
+// per-ceremony memory without limit
+#[cfg(feature = "full")]
+static MAX_CEREMONIES: AtomicUsize = AtomicUsize::new(1024);
+
+#[cfg(feature = "full")]
+thread_local! {
+    static OPEN_CEREMONIES: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Override the number of ceremonies that may be open at once (default 1024)
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn set_max_ceremonies(max_ceremonies: usize) {
+    MAX_CEREMONIES.store(max_ceremonies, Ordering::Relaxed);
+}
+
+/// Register a ceremony as open in this isolate
+///
+/// Call before creating the ceremony's state and `close_ceremony` once it
+/// finishes or is abandoned. Fails with `TooManyCeremonies` when the cap set
+/// by `set_max_ceremonies` is reached, and with `InvalidStateTransition` if
+/// `ceremony_id` is already open. Returns the number of open ceremonies.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn open_ceremony(ceremony_id: &str) -> String {
+    let result = (|| -> Result<usize, FrostError> {
+        check_input_sizes(&[("ceremony_id", ceremony_id)])?;
+        if ceremony_id.is_empty() {
+            return Err(FrostError::MalformedInput("ceremony_id is empty".to_string()));
+        }
+        
+        OPEN_CEREMONIES.with(|open| {
+            let mut open = open.borrow_mut();
+            if open.contains(ceremony_id) {
+                return Err(FrostError::InvalidStateTransition(
+                    format!("Ceremony {} is already open", ceremony_id)
+                ));
+            }
+            
+            let limit = MAX_CEREMONIES.load(Ordering::Relaxed);
+            if open.len() >= limit {
+                return Err(FrostError::TooManyCeremonies { limit });
+            }
+            
+            open.insert(ceremony_id.to_string());
+            Ok(open.len())
+        })
+    })();
+    
+    match result {
+        Ok(count) => {
+            serde_json::to_string(&FrostResult::ok(count)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<usize>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Mark a ceremony as finished, freeing its slot
+///
+/// Returns whether it was open.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn close_ceremony(ceremony_id: &str) -> String {
+    let closed = OPEN_CEREMONIES.with(|open| open.borrow_mut().remove(ceremony_id));
+    serde_json::to_string(&FrostResult::ok(closed)).unwrap()
+}
+
+/// The number of ceremonies currently open in this isolate
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn active_ceremony_count() -> usize {
+    OPEN_CEREMONIES.with(|open| open.borrow().len())
+}
+
+// === INPUT LIMITS ===
+
+// Caller-supplied JSON, messages, and participant counts are bounded so a
+// hostile request can't exhaust WASM memory or CPU before parsing even starts
+static MAX_JSON_BYTES: AtomicUsize = AtomicUsize::new(1024 * 1024);
+static MAX_MESSAGE_BYTES: AtomicUsize = AtomicUsize::new(64 * 1024);
+static MAX_PARTICIPANTS: AtomicU16 = AtomicU16::new(255);
+
+/// Override the input size limits (defaults: 1 MiB of JSON per argument,
+/// 64 KiB messages, 255 participants)
+#[wasm_bindgen]
+pub fn set_input_limits(max_json_bytes: usize, max_message_bytes: usize, max_participants: u16) {
+    MAX_JSON_BYTES.store(max_json_bytes, Ordering::Relaxed);
+    MAX_MESSAGE_BYTES.store(max_message_bytes, Ordering::Relaxed);
+    MAX_PARTICIPANTS.store(max_participants, Ordering::Relaxed);
+}
+
+// Reject any string argument over the JSON limit, naming the argument
+fn check_input_sizes(inputs: &[(&str, &str)]) -> Result<(), FrostError> {
+    let limit = MAX_JSON_BYTES.load(Ordering::Relaxed);
+    for (field, input) in inputs {
+        if input.len() > limit {
+            return Err(FrostError::InputTooLarge {
+                field: field.to_string(),
+                limit,
+                actual: input.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Reject a blank or literal "null" key package, signing package, or group key
+// by name, before serde turns it into an unhelpful EOF or type error
+fn check_present(inputs: &[(&str, &str)]) -> Result<(), FrostError> {
+    for (field, input) in inputs {
+        let input = input.trim();
+        if input.is_empty() || input == "null" {
+            return Err(FrostError::MalformedInput(format!("{} is empty", field)));
         }
     }
+    Ok(())
+}
+
+fn check_message_size(message: &[u8]) -> Result<(), FrostError> {
+    let limit = MAX_MESSAGE_BYTES.load(Ordering::Relaxed);
+    if message.len() > limit {
+        return Err(FrostError::InputTooLarge {
+            field: "message".to_string(),
+            limit,
+            actual: message.len(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(feature = "full")]
+fn check_participant_count(field: &str, count: usize) -> Result<(), FrostError> {
+    let limit = MAX_PARTICIPANTS.load(Ordering::Relaxed) as usize;
+    if count > limit {
+        return Err(FrostError::InputTooLarge {
+            field: field.to_string(),
+            limit,
+            actual: count,
+        });
+    }
+    Ok(())
 }
 
 // === KEYGEN FUNCTIONS ===
 
 /// Initialize a new key generation ceremony
+///
+/// `ceremony_id` is carried in the state so it can be sealed with `seal_state`.
+/// `created_at` and `expires_at` are unix seconds from the caller's clock; see
+/// `check_expired`.
+#[cfg(feature = "full")]
 #[wasm_bindgen]
-pub fn create_keygen_state(threshold: u16, max_participants: u16) -> String {
+pub fn create_keygen_state(
+    threshold: u16,
+    max_participants: u16,
+    ceremony_id: Option<String>,
+    created_at: Option<u64>,
+    expires_at: Option<u64>
+) -> String {
     let result = if threshold == 0 || threshold > max_participants {
         FrostResult::err(FrostError::InsufficientParticipants {
             required: threshold,
             actual: max_participants,
         })
+    } else if let Err(e) = check_participant_count("max_participants", max_participants as usize) {
+        FrostResult::err(e)
+    } else if let Err(e) = check_expiry_window(created_at, expires_at) {
+        FrostResult::err(e)
     } else {
         let state = KeygenState {
+            kind: CeremonyKind::Keygen,
             threshold,
             max_participants,
             current_round: 1,
             round1_packages: BTreeMap::new(),
             key_packages: BTreeMap::new(),
             group_public_key: None,
+            participant_identifiers: BTreeMap::new(),
+            ceremony_id,
+            created_at,
+            expires_at,
+            commit_reveal: false,
+            round1_commitments: BTreeMap::new(),
+            removed_participants: BTreeSet::new(),
         };
+        log_event(LogEvent::RoundStarted {
+            ceremony: CeremonyKind::Keygen,
+            round: 1,
+            ceremony_id: state.ceremony_id.as_deref(),
+        });
         FrostResult::ok(state)
     };
     
@@ -111,178 +915,117 @@ pub fn create_keygen_state(threshold: u16, max_participants: u16) -> String {
     })
 }
 
-/// Handle participant data for keygen round 1
-#[wasm_bindgen] 
-pub fn keygen_round1(state_json: &str, participant_id: &str) -> String {
-    let result = (|| -> Result<(KeygenState, String), FrostError> {
-        // Parse current state
-        let state_result: FrostResult<KeygenState> = serde_json::from_str(state_json)
-            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
-            
-        let mut state = state_result.data.ok_or(FrostError::InvalidStateTransition(
-            "Invalid state provided".to_string()
-        ))?;
+/// Require every participant to commit to its round 1 package before any is revealed
+///
+/// Without this a participant could wait for the others' round 1 packages and
+/// choose its own with them in view. Once enabled, each participant generates
+/// its round 1 entry privately (with `keygen_round1` on its own copy of the
+/// ceremony state, using `pubkey_hex` identifiers so they agree), submits
+/// `round1_package_hash` of it with `keygen_commit`, and only after every
+/// participant has committed reveals the entry to `keygen_round1`. Must be
+/// enabled before any round 1 package or commitment is recorded.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn enable_commit_reveal(state_json: &str) -> String {
+    let result = (|| -> Result<KeygenState, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
         
-        // Validate we're in round 1
-        if state.current_round != 1 {
+        let mut state = parse_keygen_state(state_json)?;
+        
+        if state.current_round != 1 || !state.round1_packages.is_empty() {
             return Err(FrostError::InvalidStateTransition(
-                format!("Expected round 1, got round {}", state.current_round)
+                "Commit-reveal must be enabled before round 1 packages are recorded".to_string()
             ));
         }
-        
-        // Validate participant limit
-        if state.round1_packages.len() >= state.max_participants as usize {
-            return Err(FrostError::InsufficientParticipants {
-                required: state.threshold,
-                actual: state.max_participants,
-            });
+        if state.max_participants == 1 {
+            return Err(FrostError::InvalidStateTransition(
+                "1-of-1 groups have no other participants to commit before".to_string()
+            ));
         }
         
-        // Generate real FROST DKG round 1 package
-        let identifier = FrostIdentifier::try_from(
-            (state.round1_packages.len() + 1) as u16
-        ).map_err(|e| FrostError::KeygenError(format!("Invalid identifier: {}", e)))?;
-        
-        let (round1_secret, round1_package) = dkg::part1(
-            identifier,
-            state.max_participants,
-            state.threshold,
-            &mut OsRng,
-        ).map_err(|e| FrostError::KeygenError(format!("DKG round 1 failed: {}", e)))?;
-        
-        // Serialize the round1 package for storage
-        let package_serialized = serde_json::to_string(&(round1_secret, round1_package))
-            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
-        
-        state.round1_packages.insert(participant_id.to_string(), package_serialized.clone());
-        
-        // Check if we have enough participants to advance
-        if state.round1_packages.len() >= state.threshold as usize {
-            state.current_round = 2;
+        state.commit_reveal = true;
+        Ok(state)
+    })();
+    
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<KeygenState>::err(e)).unwrap()
         }
+    }
+}
+
+/// Hash a round 1 package for `keygen_commit`
+///
+/// Accepts an entry as `keygen_round1` returns it or a bare round 1 package.
+/// Only the public package is hashed: SHA-256 over a domain tag and its
+/// canonical JSON, as hex.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn round1_package_hash(round1_json: &str) -> String {
+    let mut deprecations = Vec::new();
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("round1_json", round1_json)])?;
         
-        Ok((state, package_serialized))
+        let package = public_round1_package(round1_json, "round1_json", &mut deprecations)?;
+        round1_package_digest(&package)
     })();
     
     match result {
-        Ok((state, package)) => {
-            serde_json::to_string(&FrostResult::ok((state, package))).unwrap()
+        Ok(hash) => {
+            serde_json::to_string(&FrostResult::ok(hash).with_deprecations(deprecations)).unwrap()
         }
         Err(e) => {
-            serde_json::to_string(&FrostResult::<(KeygenState, String)>::err(e)).unwrap()
+            serde_json::to_string(&FrostResult::<String>::err(e).with_deprecations(deprecations)).unwrap()
         }
     }
 }
 
-/// Handle participant data for keygen round 2
+/// Record a participant's commitment to its round 1 package
+///
+/// `package_hash` is the hex `round1_package_hash` of the package the
+/// participant will later reveal to `keygen_round1`. A commitment can't be
+/// replaced once recorded.
+#[cfg(feature = "full")]
 #[wasm_bindgen]
-pub fn keygen_round2(
-    state_json: &str, 
-    participant_id: &str, 
-    round1_packages_json: &str
-) -> String {
-    let result = (|| -> Result<(KeygenState, String), FrostError> {
-        // Parse current state
-        let state_result: FrostResult<KeygenState> = serde_json::from_str(state_json)
-            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
-            
-        let mut state = state_result.data.ok_or(FrostError::InvalidStateTransition(
-            "Invalid state provided".to_string()
-        ))?;
+pub fn keygen_commit(state_json: &str, participant_id: &str, package_hash: &str) -> String {
+    let result = (|| -> Result<KeygenState, FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("package_hash", package_hash),
+        ])?;
         
-        // Validate we're in round 2
-        if state.current_round != 2 {
+        let mut state = parse_keygen_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        if !state.commit_reveal {
             return Err(FrostError::InvalidStateTransition(
-                format!("Expected round 2, got round {}", state.current_round)
+                "Commit-reveal is not enabled for this ceremony".to_string()
             ));
         }
-        
-        // Parse round 1 packages to get all participant data
-        let all_round1_packages: BTreeMap<String, String> = serde_json::from_str(round1_packages_json)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to parse round1 packages: {}", e)))?;
-        
-        // Get this participant's round 1 secret and package
-        let participant_round1_data = state.round1_packages.get(participant_id)
-            .ok_or(FrostError::InvalidParticipant(format!("Participant {} not found in round 1", participant_id)))?;
-        
-        // Deserialize the participant's round 1 secret and package
-        let (round1_secret, _round1_package): (dkg::round1::SecretPackage<Secp256K1Sha256>, dkg::round1::Package<Secp256K1Sha256>) = 
-            serde_json::from_str(participant_round1_data)
-                .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize round1 secret: {}", e)))?;
-        
-        // Collect all round 1 packages from other participants
-        let mut received_round1_packages = BTreeMap::new();
-        for (other_participant, package_data) in &all_round1_packages {
-            if other_participant != participant_id {
-                let (_secret, package): (dkg::round1::SecretPackage<Secp256K1Sha256>, dkg::round1::Package<Secp256K1Sha256>) = 
-                    serde_json::from_str(package_data)
-                        .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize package for {}: {}", other_participant, e)))?;
-                
-                // Map participant name to identifier
-                let identifier = FrostIdentifier::try_from(
-                    (received_round1_packages.len() + 2) as u16  // +1 for this participant, +1 for 1-based indexing
-                ).map_err(|e| FrostError::KeygenError(format!("Invalid identifier for {}: {}", other_participant, e)))?;
-                
-                received_round1_packages.insert(identifier, package);
-            }
-        }
-        
-        // Perform DKG round 2
-        let (key_package, group_public_key) = dkg::part2(round1_secret, &received_round1_packages)
-            .map_err(|e| FrostError::KeygenError(format!("DKG round 2 failed: {}", e)))?;
-        
-        // Serialize the key package for storage
-        let key_package_serialized = serde_json::to_string(&key_package)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))?;
-        
-        // Store the key package
-        state.key_packages.insert(participant_id.to_string(), key_package_serialized.clone());
-        
-        // If all participants have completed, store the group public key
-        if state.key_packages.len() >= state.threshold as usize {
-            let group_public_key_serialized = serde_json::to_string(&group_public_key)
-                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?;
-            state.group_public_key = Some(group_public_key_serialized);
-        }
-        
-        Ok((state, key_package_serialized))
-    })();
-    
-    match result {
-        Ok((state, key_package)) => {
-            serde_json::to_string(&FrostResult::ok((state, key_package))).unwrap()
+        if state.current_round != 1 {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Expected round 1, got round {}", state.current_round)
+            ));
         }
-        Err(e) => {
-            serde_json::to_string(&FrostResult::<(KeygenState, String)>::err(e)).unwrap()
+        ensure_not_removed(&state, participant_id)?;
+        if state.round1_commitments.contains_key(participant_id) {
+            return Err(FrostError::InvalidParticipant(
+                format!("{} has already committed to a round 1 package", participant_id)
+            ));
         }
-    }
-}
-
-// === SIGNING FUNCTIONS ===
-
-/// Initialize a new signing ceremony
-#[wasm_bindgen]
-pub fn create_signing_state(message: &[u8], signers_json: &str) -> String {
-    let result = (|| -> Result<SigningState, FrostError> {
-        let signers: Vec<String> = serde_json::from_str(signers_json)
-            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
-            
-        if signers.is_empty() {
+        if state.round1_commitments.len() >= state.max_participants as usize {
             return Err(FrostError::InsufficientParticipants {
-                required: 1,
-                actual: 0,
+                required: state.threshold,
+                actual: state.max_participants,
             });
         }
         
-        let state = SigningState {
-            message: message.to_vec(),
-            current_round: 1,
-            signers,
-            round1_packages: BTreeMap::new(),
-            signature_shares: BTreeMap::new(),
-            final_signature: None,
-        };
-        
+        let hash = nostr::decode_fixed::<32>(package_hash, "package_hash")?;
+        state.round1_commitments.insert(participant_id.to_string(), hex::encode(hash));
         Ok(state)
     })();
     
@@ -291,241 +1034,593 @@ pub fn create_signing_state(message: &[u8], signers_json: &str) -> String {
             serde_json::to_string(&FrostResult::ok(state)).unwrap()
         }
         Err(e) => {
-            serde_json::to_string(&FrostResult::<SigningState>::err(e)).unwrap()
+            serde_json::to_string(&FrostResult::<KeygenState>::err(e)).unwrap()
         }
     }
 }
 
-/// Handle participant data for signing round 1 (nonce generation)
-#[wasm_bindgen]
-pub fn signing_round1(state_json: &str, participant_id: &str, key_package_json: &str) -> String {
-    let result = (|| -> Result<(SigningState, String), FrostError> {
-        let state_result: FrostResult<SigningState> = serde_json::from_str(state_json)
-            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
-            
-        let mut state = state_result.data.ok_or(FrostError::InvalidStateTransition(
-            "Invalid state provided".to_string()
-        ))?;
+/// Handle participant data for keygen round 1
+///
+/// When `pubkey_hex` is given the participant's identifier is derived from its
+/// Nostr pubkey (see `identifier_from_pubkey`), otherwise identifiers are
+/// assigned in order of arrival.
+///
+/// `extra_entropy` is mixed into the seed for this participant's secrets
+/// alongside the host's random source, never in place of it. Round 2 draws no
+/// randomness, so round 1 is the only place it applies.
+///
+/// In commit-reveal mode (see `enable_commit_reveal`) nothing is generated:
+/// `revealed_round1_json` must be the entry the participant committed to, and
+/// is only accepted once every participant has committed.
+#[cfg(feature = "full")]
+#[wasm_bindgen] 
+pub fn keygen_round1(
+    state_json: &str,
+    participant_id: &str,
+    pubkey_hex: Option<String>,
+    extra_entropy: Option<Vec<u8>>,
+    revealed_round1_json: Option<String>
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(KeygenState, String), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("pubkey_hex", pubkey_hex.as_deref().unwrap_or_default()),
+            ("revealed_round1_json", revealed_round1_json.as_deref().unwrap_or_default()),
+        ])?;
         
+        // Parse current state
+        let mut state = parse_keygen_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        // Validate we're in round 1
         if state.current_round != 1 {
             return Err(FrostError::InvalidStateTransition(
                 format!("Expected round 1, got round {}", state.current_round)
             ));
         }
+        ensure_not_removed(&state, participant_id)?;
         
-        // Deserialize the key package for this participant
-        let key_package: KeyPackage = serde_json::from_str(key_package_json)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize key package: {}", e)))?;
+        // Validate participant limit
+        if state.round1_packages.len() >= state.max_participants as usize {
+            return Err(FrostError::InsufficientParticipants {
+                required: state.threshold,
+                actual: state.max_participants,
+            });
+        }
         
-        // Generate nonces for signing round 1
-        let (nonces, commitments) = round1::commit(key_package.signing_share(), &mut OsRng);
+        let revealed = match revealed_round1_json.as_deref() {
+            Some(revealed_json) if state.commit_reveal => Some(read_round1_reveal(&state, participant_id, revealed_json)?),
+            None if !state.commit_reveal => None,
+            Some(_) => return Err(FrostError::MalformedInput(
+                "A round 1 package can only be revealed in commit-reveal mode".to_string()
+            )),
+            None => return Err(FrostError::MalformedInput(
+                format!("Commit-reveal mode requires {} to reveal its committed round 1 package", participant_id)
+            )),
+        };
         
-        // Serialize the nonces and commitments for storage
-        let round1_data = serde_json::to_string(&(nonces, commitments))
-            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize round1 data: {}", e)))?;
+        // Generate real FROST DKG round 1 package
+        let identifier = match (pubkey_hex.as_deref(), &revealed) {
+            (Some(pubkey_hex), _) => derive_identifier_from_pubkey(pubkey_hex)?,
+            (None, Some(entry)) => *entry.secret.identifier(),
+            (None, None) => FrostIdentifier::try_from(
+                participant_count(state.round1_packages.len().saturating_add(1))?
+            ).map_err(|e| FrostError::InvalidParticipant(format!("Invalid identifier: {}", e)))?,
+        };
+        if revealed.as_ref().is_some_and(|entry| *entry.secret.identifier() != identifier) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Revealed round 1 package for {} was generated for another identifier", participant_id)
+            ));
+        }
         
-        state.round1_packages.insert(participant_id.to_string(), round1_data.clone());
+        let identifier_hex = identifier_to_hex(&identifier);
+        if state.participant_identifiers.values().any(|existing| *existing == identifier_hex) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Identifier for {} is already taken", participant_id)
+            ));
+        }
         
-        // Check if we have enough participants to advance
-        if state.round1_packages.len() >= state.signers.len() {
+        let mut rng = seeded_rng(&mut OsRng, extra_entropy.as_deref())?;
+        
+        // frost needs at least two participants for DKG, so a 1-of-1 participant
+        // just samples its signing key here and builds its key package in round 2
+        if state.max_participants == 1 {
+            let signing_key = frost::SigningKey::new(&mut rng);
+            let verifying_key = serde_json::to_string(&frost::VerifyingKey::from(&signing_key))
+                .map_err(|e| FrostError::SerializationError(e.to_string()))?;
+            
+            state.round1_packages.insert(participant_id.to_string(), hex::encode(signing_key.serialize()));
+            state.participant_identifiers.insert(participant_id.to_string(), identifier_hex);
             state.current_round = 2;
+            log_round1_accepted(CeremonyKind::Keygen, participant_id, true, state.ceremony_id.as_deref());
+            
+            return Ok((state, verifying_key));
         }
         
-        // Return the commitments (public part) for coordination
-        let commitments_serialized = serde_json::to_string(&commitments)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitments: {}", e)))?;
+        let entry = match revealed {
+            Some(entry) => entry,
+            None => {
+                let (round1_secret, round1_package) = timings.measure("dkg_part1", || dkg::part1(
+                    identifier,
+                    state.max_participants,
+                    state.threshold,
+                    &mut rng,
+                )).map_err(|e| FrostError::KeygenError(format!("DKG round 1 failed: {}", e)))?;
+                
+                Round1Entry {
+                    format: ROUND1_ENTRY_FORMAT.to_string(),
+                    secret: round1_secret,
+                    package: round1_package,
+                }
+            }
+        };
+        
+        // Serialize the round1 package for storage
+        let package_serialized = serde_json::to_string(&entry)
+            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
+        
+        state.round1_packages.insert(participant_id.to_string(), package_serialized.clone());
+        state.participant_identifiers.insert(participant_id.to_string(), identifier_hex);
+        
+        // DKG round 2 needs a round 1 package from every participant
+        if state.round1_packages.len() >= state.max_participants as usize {
+            state.current_round = 2;
+        }
+        log_round1_accepted(CeremonyKind::Keygen, participant_id, state.current_round == 2, state.ceremony_id.as_deref());
         
-        Ok((state, commitments_serialized))
+        Ok((state, package_serialized))
     })();
     
     match result {
-        Ok((state, nonces)) => {
-            serde_json::to_string(&FrostResult::ok((state, nonces))).unwrap()
+        Ok((state, package)) => {
+            result_json(&FrostResult::ok((state, package)).with_timings(timings))
         }
         Err(e) => {
-            serde_json::to_string(&FrostResult::<(SigningState, String)>::err(e)).unwrap()
+            result_json(&FrostResult::<(KeygenState, String)>::err(e).with_timings(timings))
         }
     }
 }
 
-/// Handle participant data for signing round 2 (signature share generation)
+/// Handle participant data for keygen round 2
+#[cfg(feature = "full")]
 #[wasm_bindgen]
-pub fn signing_round2(
-    state_json: &str,
-    participant_id: &str,
-    key_package_json: &str,
-    signing_package_json: &str,
-    group_public_key_json: &str
+pub fn keygen_round2(
+    state_json: &str, 
+    participant_id: &str, 
+    round1_packages_json: &str
 ) -> String {
-    let result = (|| -> Result<(SigningState, Option<String>), FrostError> {
-        let state_result: FrostResult<SigningState> = serde_json::from_str(state_json)
-            .map_err(|e| FrostError::SerializationError(e.to_string()))?;
-            
-        let mut state = state_result.data.ok_or(FrostError::InvalidStateTransition(
-            "Invalid state provided".to_string()
-        ))?;
+    let mut timings = Timings::new();
+    let mut deprecations = Vec::new();
+    let result = (|| -> Result<(KeygenState, String), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("round1_packages_json", round1_packages_json),
+        ])?;
         
+        // Parse current state
+        let mut state = parse_keygen_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        // Validate we're in round 2
         if state.current_round != 2 {
             return Err(FrostError::InvalidStateTransition(
                 format!("Expected round 2, got round {}", state.current_round)
             ));
         }
         
-        // Deserialize the key package for this participant
-        let key_package: KeyPackage = serde_json::from_str(key_package_json)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize key package: {}", e)))?;
-        
-        // Deserialize the signing package (contains message and all commitments)  
-        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize signing package: {}", e)))?;
-        
-        // Get this participant's nonces from round 1
-        let participant_round1_data = state.round1_packages.get(participant_id)
-            .ok_or(FrostError::InvalidParticipant(format!("Participant {} not found in round 1", participant_id)))?;
+        let all_round1_packages = parse_round1_package_map(&state, round1_packages_json)?;
+        let key_package_serialized = keygen_round2_participant(
+            &mut state,
+            participant_id,
+            &all_round1_packages,
+            &mut timings,
+            &mut deprecations,
+        )?;
         
-        let (nonces, _commitments): (frost::round1::SigningNonces, frost::round1::SigningCommitments) = 
-            serde_json::from_str(participant_round1_data)
-                .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize nonces: {}", e)))?;
+        Ok((state, key_package_serialized))
+    })();
+    
+    match result {
+        Ok((state, key_package)) => {
+            result_json(&FrostResult::ok((state, key_package))
+                .with_timings(timings)
+                .with_deprecations(deprecations))
+        }
+        Err(e) => {
+            result_json(&FrostResult::<(KeygenState, String)>::err(e)
+                .with_timings(timings)
+                .with_deprecations(deprecations))
+        }
+    }
+}
+
+/// Run keygen round 2 for several participants held by this caller in one call
+///
+/// `participant_ids_json` is a JSON array of participant labels; each is
+/// processed exactly as `keygen_round2` would, with its own round 1 secret and
+/// identifier, against the state left by the one before. Returns the updated
+/// state and a map of participant label to round 2 package. Fails as a whole
+/// if any participant fails, so no partial state is returned.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn keygen_round2_batch(
+    state_json: &str,
+    participant_ids_json: &str,
+    round1_packages_json: &str
+) -> String {
+    let mut timings = Timings::new();
+    let mut deprecations = Vec::new();
+    let result = (|| -> Result<(KeygenState, BTreeMap<String, String>), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_ids_json", participant_ids_json),
+            ("round1_packages_json", round1_packages_json),
+        ])?;
         
-        // Generate signature share
-        let signature_share = round2::sign(&signing_package, &nonces, &key_package)
-            .map_err(|e| FrostError::SigningError(format!("Failed to generate signature share: {}", e)))?;
+        let mut state = parse_keygen_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
         
-        // Serialize and store the signature share
-        let signature_share_serialized = serde_json::to_string(&signature_share)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signature share: {}", e)))?;
+        if state.current_round != 2 {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Expected round 2, got round {}", state.current_round)
+            ));
+        }
         
-        state.signature_shares.insert(participant_id.to_string(), signature_share_serialized.clone());
+        let participant_ids: Vec<String> = serde_json::from_str(participant_ids_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse participant ids: {}", e)))?;
+        check_participant_count("participant_ids", participant_ids.len())?;
         
-        // If all participants have signed, aggregate the signature
-        let final_signature = if state.signature_shares.len() >= state.signers.len() {
-            // Deserialize the group public key package from keygen
-            let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
-                .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize group public key: {}", e)))?;
-            
-            // Collect all signature shares with proper identifier mapping
-            let mut signature_shares = BTreeMap::new();
-            for (idx, (participant, share_data)) in state.signature_shares.iter().enumerate() {
-                let share: round2::SignatureShare<Secp256K1Sha256> = serde_json::from_str(share_data)
-                    .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize share for {}: {}", participant, e)))?;
-                
-                // Map participant to identifier based on order (consistent with keygen)
-                let identifier = FrostIdentifier::try_from((idx + 1) as u16)
-                    .map_err(|e| FrostError::SigningError(format!("Invalid identifier for {}: {}", participant, e)))?;
-                
-                signature_shares.insert(identifier, share);
+        let all_round1_packages = parse_round1_package_map(&state, round1_packages_json)?;
+        let mut packages = BTreeMap::new();
+        for participant_id in &participant_ids {
+            if packages.contains_key(participant_id) {
+                return Err(FrostError::MalformedInput(
+                    format!("Participant {} is listed more than once", participant_id)
+                ));
             }
             
-            // Aggregate the signature using real FROST
-            let group_signature = frost::aggregate(&signing_package, &signature_shares, &group_public_key)
-                .map_err(|e| FrostError::SigningError(format!("Failed to aggregate signature: {}", e)))?;
-            
-            let final_sig_serialized = serde_json::to_string(&group_signature)
-                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize final signature: {}", e)))?;
-            
-            state.final_signature = Some(final_sig_serialized.clone());
-            Some(final_sig_serialized)
-        } else {
-            None
-        };
+            let package = keygen_round2_participant(
+                &mut state,
+                participant_id,
+                &all_round1_packages,
+                &mut timings,
+                &mut deprecations,
+            )?;
+            packages.insert(participant_id.clone(), package);
+        }
         
-        Ok((state, final_signature))
+        Ok((state, packages))
     })();
     
     match result {
-        Ok((state, signature)) => {
-            serde_json::to_string(&FrostResult::ok((state, signature))).unwrap()
+        Ok((state, packages)) => {
+            result_json(&FrostResult::ok((state, packages))
+                .with_timings(timings)
+                .with_deprecations(deprecations))
         }
         Err(e) => {
-            serde_json::to_string(&FrostResult::<(SigningState, Option<String>)>::err(e)).unwrap()
+            result_json(&FrostResult::<(KeygenState, BTreeMap<String, String>)>::err(e)
+                .with_timings(timings)
+                .with_deprecations(deprecations))
         }
     }
 }
 
-// === UTILITY FUNCTIONS ===
+// Keygen round 2 for one participant of a parsed round 2 state, storing and
+// returning its package. The participant's own round 1 secret carries its
+// identifier, and every other entry is keyed by the identifier recorded for it.
+#[cfg(feature = "full")]
+fn keygen_round2_participant(
+    state: &mut KeygenState,
+    participant_id: &str,
+    all_round1_packages: &BTreeMap<String, String>,
+    timings: &mut Timings,
+    deprecations: &mut Vec<String>,
+) -> Result<String, FrostError> {
+    if state.max_participants == 1 {
+        let signing_key_hex = state.round1_packages.get(participant_id)
+            .ok_or(FrostError::InvalidParticipant(format!("Participant {} not found in round 1", participant_id)))?;
+        let identifier_hex = state.participant_identifiers.get(participant_id)
+            .ok_or(FrostError::InvalidParticipant(format!("No identifier recorded for {}", participant_id)))?;
+        
+        let (key_package, public_key_package) = single_signer_keys(
+            identifier_from_hex(identifier_hex)?,
+            signing_key_hex,
+        )?;
+        
+        let key_package_serialized = serde_json::to_string(&key_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))?;
+        let group_public_key_serialized = serde_json::to_string(&public_key_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?;
+        
+        state.key_packages.insert(participant_id.to_string(), key_package_serialized.clone());
+        state.group_public_key = Some(group_public_key_serialized);
+        log_event(LogEvent::PackageAccepted {
+            ceremony: CeremonyKind::Keygen,
+            round: 2,
+            participant: participant_id,
+            ceremony_id: state.ceremony_id.as_deref(),
+        });
+        
+        return Ok(key_package_serialized);
+    }
+    
+    // Shares computed from only a subset of round 1 packages are invalid
+    let received = state.round1_packages.len().min(all_round1_packages.len());
+    if received != state.max_participants as usize {
+        return Err(FrostError::InsufficientParticipants {
+            required: state.max_participants,
+            actual: participant_count(received)?,
+        });
+    }
+    
+    // Get this participant's round 1 secret and package
+    let participant_round1_data = state.round1_packages.get(participant_id)
+        .ok_or(FrostError::InvalidParticipant(format!("Participant {} not found in round 1", participant_id)))?;
+    
+    // Deserialize the participant's round 1 secret and package
+    let round1_secret = read_round1_entry(participant_round1_data, participant_id, deprecations)?.secret;
+    
+    // Collect all round 1 packages from other participants
+    let mut received_round1_packages = BTreeMap::new();
+    for (other_participant, package_data) in all_round1_packages {
+        if other_participant != participant_id {
+            let package = read_round1_entry(package_data, other_participant, deprecations)?.package;
+            
+            // Map participant name to the identifier it used in round 1
+            let identifier_hex = state.participant_identifiers.get(other_participant)
+                .ok_or(FrostError::InvalidParticipant(format!("No identifier recorded for {}", other_participant)))?;
+            let identifier = identifier_from_hex(identifier_hex)?;
+            
+            // Check each proof here so a bad one names its sender, which
+            // part2's error does not
+            dkg::verify_proof_of_knowledge(identifier, package.commitment(), package.proof_of_knowledge())
+                .map_err(|e| FrostError::KeygenError(
+                    format!("Invalid proof of knowledge from {}: {}", other_participant, e)
+                ))?;
+            
+            received_round1_packages.insert(identifier, package);
+        }
+    }
+    
+    // Perform DKG round 2
+    let (key_package, group_public_key) = timings.measure("dkg_part2", || dkg::part2(round1_secret, &received_round1_packages))
+        .map_err(|e| FrostError::KeygenError(format!("DKG round 2 failed: {}", e)))?;
+    
+    // Serialize the key package for storage
+    let key_package_serialized = serde_json::to_string(&key_package)
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))?;
+    
+    // Store the key package
+    state.key_packages.insert(participant_id.to_string(), key_package_serialized.clone());
+    
+    // If all participants have completed, store the group public key
+    if state.key_packages.len() >= state.threshold as usize {
+        let group_public_key_serialized = serde_json::to_string(&group_public_key)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?;
+        state.group_public_key = Some(group_public_key_serialized);
+    }
+    log_event(LogEvent::PackageAccepted {
+        ceremony: CeremonyKind::Keygen,
+        round: 2,
+        participant: participant_id,
+        ceremony_id: state.ceremony_id.as_deref(),
+    });
+    
+    Ok(key_package_serialized)
+}
 
-/// Generate FROST key shares from a private key (Trusted Dealer mode)
+// The round 1 packages keygen round 2 is handed. A single-participant
+// ceremony has none to read.
+#[cfg(feature = "full")]
+fn parse_round1_package_map(state: &KeygenState, round1_packages_json: &str) -> Result<BTreeMap<String, String>, FrostError> {
+    if state.max_participants == 1 {
+        return Ok(BTreeMap::new());
+    }
+    
+    serde_json::from_str(round1_packages_json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to parse round1 packages: {}", e)))
+}
+
+// A participant removed with remove_participant can't rejoin the ceremony
+#[cfg(feature = "full")]
+fn ensure_not_removed(state: &KeygenState, participant_id: &str) -> Result<(), FrostError> {
+    if state.removed_participants.contains(participant_id) {
+        return Err(FrostError::InvalidParticipant(
+            format!("{} was removed from this ceremony", participant_id)
+        ));
+    }
+    Ok(())
+}
+
+/// Remove a participant that dropped out of a keygen ceremony
+///
+/// Every round 1 secret is bound to the participant count it was created for,
+/// so removal shrinks `max_participants` by one, zeroizes all stored round 1
+/// packages, and restarts round 1: the remaining participants must submit
+/// fresh round 1 packages (and, in commit-reveal mode, fresh commitments).
+/// Only a participant that submitted a round 1 package or commitment can be
+/// removed, and its name is kept in `removed_participants` so it can't rejoin.
+///
+/// A full restart with a new ceremony is required when removal would leave
+/// fewer participants than the threshold, or once any key package has been
+/// produced, since those shares already depend on the removed participant.
+#[cfg(feature = "full")]
 #[wasm_bindgen]
-pub fn generate_frost_shares(
-    private_key_hex: &str,
-    threshold: u16,
-    max_participants: u16
-) -> String {
-    let result = (|| -> Result<(String, BTreeMap<String, String>), FrostError> {
-        if threshold == 0 || threshold > max_participants {
-            return Err(FrostError::InsufficientParticipants {
-                required: threshold,
-                actual: max_participants,
-            });
-        }
+pub fn remove_participant(state_json: &str, participant_id: &str) -> String {
+    let result = (|| -> Result<KeygenState, FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+        ])?;
         
-        // For now, we'll use the default trusted dealer which generates its own secret
-        // In future, we could use the provided private_key_hex but that requires additional implementation
-        let _ = private_key_hex; // Acknowledge the parameter
+        let mut state = parse_keygen_state(state_json)?;
         
-        // Create identifiers for all participants
-        let mut identifiers = Vec::new();
-        for i in 1..=max_participants {
-            let identifier = FrostIdentifier::try_from(i)
-                .map_err(|e| FrostError::KeygenError(format!("Invalid identifier {}: {}", i, e)))?;
-            identifiers.push(identifier);
+        if !state.key_packages.is_empty() || state.group_public_key.is_some() {
+            return Err(FrostError::InvalidStateTransition(
+                "Key packages were already produced; start a new ceremony without this participant".to_string()
+            ));
         }
         
-        // Generate key shares using trusted dealer
-        let (shares, group_public_key) = frost::keys::generate_with_dealer(
-            max_participants,
-            threshold,
-            IdentifierList::Custom(&identifiers),
-            &mut OsRng,
-        ).map_err(|e| FrostError::KeygenError(format!("Trusted dealer failed: {}", e)))?;
+        if !state.round1_packages.contains_key(participant_id) && !state.round1_commitments.contains_key(participant_id) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Participant {} not found in round 1", participant_id)
+            ));
+        }
         
-        // Serialize shares
-        let mut serialized_shares = BTreeMap::new();
-        for (identifier, key_package) in shares {
-            let share_data = serde_json::to_string(&key_package)
-                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize share: {}", e)))?;
-            // Use the identifier as-is in string format for now
-            let participant_key = format!("participant_{:?}", identifier);
-            serialized_shares.insert(participant_key, share_data);
+        let remaining = state.max_participants - 1;
+        if remaining < state.threshold {
+            return Err(FrostError::InsufficientParticipants {
+                required: state.threshold,
+                actual: remaining,
+            });
         }
         
-        // Serialize group public key
-        let group_public_key_serialized = serde_json::to_string(&group_public_key)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?;
+        for (_, mut package) in std::mem::take(&mut state.round1_packages) {
+            package.zeroize();
+        }
+        state.participant_identifiers.clear();
+        state.round1_commitments.clear();
+        state.removed_participants.insert(participant_id.to_string());
+        state.max_participants = remaining;
+        state.current_round = 1;
         
-        Ok((group_public_key_serialized, serialized_shares))
+        Ok(state)
     })();
     
     match result {
-        Ok((pubkey, shares)) => {
-            serde_json::to_string(&FrostResult::ok((pubkey, shares))).unwrap()
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
         }
         Err(e) => {
-            serde_json::to_string(&FrostResult::<(String, BTreeMap<String, String>)>::err(e)).unwrap()
+            serde_json::to_string(&FrostResult::<KeygenState>::err(e)).unwrap()
         }
     }
 }
 
-/// Verify a FROST signature
+// The public half of a DKG: every participant's round 1 commitment and proof
+// of knowledge, keyed by identifier hex, with the labels they used
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct DkgTranscript {
+    pub ceremony_id: Option<String>,
+    pub threshold: u16,
+    pub max_participants: u16,
+    pub participants: BTreeMap<String, String>,
+    pub packages: BTreeMap<String, dkg::round1::Package<Secp256K1Sha256>>,
+}
+
+/// Extract the public round 1 packages of a keygen state for publication
+///
+/// Only the commitments and proofs of knowledge are copied; the round 1 secret
+/// stored alongside each package is dropped. 1-of-1 groups have no DKG and so
+/// no transcript.
+#[cfg(feature = "full")]
 #[wasm_bindgen]
-pub fn verify_signature(
-    message: &[u8],
-    signature_json: &str,
-    group_public_key_json: &str
-) -> String {
-    let result = (|| -> Result<bool, FrostError> {
-        // Deserialize the signature
-        let signature: frost::Signature = serde_json::from_str(signature_json)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize signature: {}", e)))?;
+pub fn dkg_public_transcript(state_json: &str) -> String {
+    let mut deprecations = Vec::new();
+    let result = (|| -> Result<DkgTranscript, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
         
-        // Deserialize the group public key  
-        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
-            .map_err(|e| FrostError::SerializationError(format!("Failed to deserialize group public key: {}", e)))?;
+        let state = parse_keygen_state(state_json)?;
         
-        // Verify the signature using FROST
-        let verification_result = group_public_key.verifying_key().verify(message, &signature);
+        if state.max_participants == 1 {
+            return Err(FrostError::InvalidStateTransition(
+                "1-of-1 groups are not generated with a DKG and have no transcript".to_string()
+            ));
+        }
         
-        Ok(verification_result.is_ok())
+        if state.round1_packages.len() != state.max_participants as usize {
+            return Err(FrostError::InsufficientParticipants {
+                required: state.max_participants,
+                actual: participant_count(state.round1_packages.len())?,
+            });
+        }
+        
+        let mut packages = BTreeMap::new();
+        for (participant, round1_data) in &state.round1_packages {
+            let package = read_round1_entry(round1_data, participant, &mut deprecations)?.package;
+            let identifier_hex = state.participant_identifiers.get(participant)
+                .ok_or(FrostError::InvalidParticipant(format!("No identifier recorded for {}", participant)))?;
+            
+            packages.insert(identifier_hex.clone(), package);
+        }
+        
+        Ok(DkgTranscript {
+            ceremony_id: state.ceremony_id,
+            threshold: state.threshold,
+            max_participants: state.max_participants,
+            participants: state.participant_identifiers,
+            packages,
+        })
+    })();
+    
+    match result {
+        Ok(transcript) => {
+            serde_json::to_string(&FrostResult::ok(transcript).with_deprecations(deprecations)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<DkgTranscript>::err(e).with_deprecations(deprecations)).unwrap()
+        }
+    }
+}
+
+/// Check that a group key was honestly generated from a published DKG transcript
+///
+/// Checks the transcript holds exactly `max_participants` participants, each
+/// with a commitment of `threshold` coefficients and a valid proof of
+/// knowledge, then recomputes the group key and verifying shares from the
+/// commitments and compares them with `group_public_key_json`, which must have
+/// a verifying share for every participant and no others. Returns false on any
+/// mismatch.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn verify_dkg_transcript(transcript_json: &str, group_public_key_json: &str) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[
+            ("transcript_json", transcript_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let transcript: DkgTranscript = serde_json::from_str(transcript_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize transcript: {}", e)))?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        if transcript.packages.len() != transcript.max_participants as usize {
+            return Ok(false);
+        }
+        let participant_identifiers: BTreeSet<&String> = transcript.participants.values().collect();
+        if transcript.participants.len() != transcript.max_participants as usize
+            || !participant_identifiers.iter().copied().eq(transcript.packages.keys())
+        {
+            return Ok(false);
+        }
+        
+        let mut commitments = BTreeMap::new();
+        for (identifier_hex, package) in &transcript.packages {
+            let identifier = identifier_from_hex(identifier_hex)?;
+            let coefficients = package.commitment().serialize()
+                .map_err(|e| FrostError::MalformedInput(format!("Invalid commitment from {}: {}", identifier_hex, e)))?;
+            if coefficients.len() != transcript.threshold as usize {
+                return Ok(false);
+            }
+            if dkg::verify_proof_of_knowledge(identifier, package.commitment(), package.proof_of_knowledge()).is_err() {
+                return Ok(false);
+            }
+            commitments.insert(identifier, package.commitment());
+        }
+        
+        let Ok(recomputed) = PublicKeyPackage::from_dkg_commitments(&commitments) else {
+            return Ok(false);
+        };
+        
+        Ok(recomputed.verifying_key() == group_public_key.verifying_key()
+            && recomputed.verifying_shares() == group_public_key.verifying_shares())
     })();
     
     match result {
@@ -538,8 +1633,5346 @@ pub fn verify_signature(
     }
 }
 
-// === WASM MEMORY OPTIMIZATION ===
+// The group key a DKG will produce, compressed SEC1 and x-only (Nostr) hex
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct GroupKeyPreview {
+    pub group_public_key: String,
+    pub xonly_pubkey: String,
+}
 
-// Use wee_alloc as the global allocator for smaller WASM binary size
-#[global_allocator]
-static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
\ No newline at end of file
+/// Compute the group key a DKG will produce from its round 1 packages alone
+///
+/// `round1_packages_json` maps labels to the packages returned by
+/// `keygen_round1` (bare round 1 packages are accepted too) and must hold
+/// every participant's. The group key is the sum of the constant terms of
+/// their commitments, so it is known before round 2 starts and the final key
+/// can be checked against it. Proofs of knowledge are not checked here.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn compute_group_key_preview(round1_packages_json: &str) -> String {
+    let mut deprecations = Vec::new();
+    let result = (|| -> Result<GroupKeyPreview, FrostError> {
+        check_input_sizes(&[("round1_packages_json", round1_packages_json)])?;
+        
+        let round1_packages: BTreeMap<String, String> = serde_json::from_str(round1_packages_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse round1 packages: {}", e)))?;
+        
+        if round1_packages.len() < 2 {
+            return Err(FrostError::InsufficientParticipants {
+                required: 2,
+                actual: participant_count(round1_packages.len())?,
+            });
+        }
+        
+        let mut group_key = k256::ProjectivePoint::IDENTITY;
+        for (participant, round1_data) in &round1_packages {
+            let package = public_round1_package(round1_data, participant, &mut deprecations)?;
+            let coefficients = package.commitment().serialize()
+                .map_err(|e| FrostError::MalformedInput(format!("Invalid commitment from {}: {}", participant, e)))?;
+            let constant_term = coefficients.first().ok_or(FrostError::MalformedInput(
+                format!("Empty commitment from {}", participant)
+            ))?;
+            group_key += sec1_point(constant_term)?;
+        }
+        
+        let group_key = k256::PublicKey::from_affine(group_key.to_affine())
+            .map_err(|_| FrostError::KeygenError("Round 1 commitments sum to the identity".to_string()))?;
+        let compressed = group_key.to_encoded_point(true);
+        
+        Ok(GroupKeyPreview {
+            group_public_key: hex::encode(compressed.as_bytes()),
+            xonly_pubkey: hex::encode(&compressed.as_bytes()[1..]),
+        })
+    })();
+    
+    match result {
+        Ok(preview) => {
+            serde_json::to_string(&FrostResult::ok(preview).with_deprecations(deprecations)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<GroupKeyPreview>::err(e).with_deprecations(deprecations)).unwrap()
+        }
+    }
+}
+
+/// Derive a participant's FROST identifier from its 32-byte x-only Nostr pubkey
+///
+/// The identifier is `Identifier::derive(pubkey)`, so every server maps the
+/// same pubkey to the same identifier without coordination.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn identifier_from_pubkey(pubkey_hex: &str) -> String {
+    let result = check_input_sizes(&[("pubkey_hex", pubkey_hex)])
+        .and_then(|_| derive_identifier_from_pubkey(pubkey_hex))
+        .map(|identifier| identifier_to_hex(&identifier));
+    
+    match result {
+        Ok(identifier) => {
+            serde_json::to_string(&FrostResult::ok(identifier)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// Names the share encryption in a packaged keygen result
+#[cfg(feature = "full")]
+const SHARE_ENCRYPTION: &str = "aes-256-gcm";
+
+// AES-GCM nonce length
+#[cfg(feature = "full")]
+const SHARE_NONCE_LEN: usize = 12;
+
+// Everything stored per account once keygen completes
+#[derive(Serialize, Deserialize)]
+pub struct KeygenResult {
+    pub group_public_key: String,
+    pub threshold: u16,
+    pub max_participants: u16,
+    pub participant_identifiers: BTreeMap<String, String>,
+    pub shares: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub share_encryption: Option<String>,
+}
+
+/// Package a completed keygen ceremony as the document to persist for the group
+///
+/// Returns `{ group_public_key, threshold, max_participants,
+/// participant_identifiers, shares }`, with `shares` mapping each participant
+/// label to its key package. When `share_keys_json` maps every label to a
+/// 32-byte hex key, each share is instead encrypted with AES-256-GCM under its
+/// participant's key, with the label as associated data, stored as hex of the
+/// 12-byte nonce followed by the ciphertext and tag, and `share_encryption` is
+/// set to `"aes-256-gcm"`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn package_keygen_result(state_json: &str, share_keys_json: Option<String>) -> String {
+    let result = (|| -> Result<KeygenResult, FrostError> {
+        let share_keys_json = share_keys_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("share_keys_json", share_keys_json.unwrap_or_default()),
+        ])?;
+        
+        let state = parse_keygen_state(state_json)?;
+        let group_public_key = state.group_public_key.ok_or(FrostError::InvalidStateTransition(
+            "Keygen has not produced a group public key yet".to_string()
+        ))?;
+        
+        let (shares, share_encryption) = match share_keys_json {
+            Some(share_keys_json) => {
+                let mut share_keys: BTreeMap<String, String> = serde_json::from_str(share_keys_json)
+                    .map_err(|e| FrostError::MalformedInput(format!("Failed to parse share keys: {}", e)))?;
+                let encrypted = encrypt_shares(&state.key_packages, &share_keys);
+                share_keys.values_mut().for_each(|key_hex| key_hex.zeroize());
+                (encrypted?, Some(SHARE_ENCRYPTION.to_string()))
+            }
+            None => (state.key_packages, None),
+        };
+        
+        Ok(KeygenResult {
+            group_public_key,
+            threshold: state.threshold,
+            max_participants: state.max_participants,
+            participant_identifiers: state.participant_identifiers,
+            shares,
+            share_encryption,
+        })
+    })();
+    
+    match result {
+        Ok(keygen_result) => {
+            serde_json::to_string(&FrostResult::ok(keygen_result)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<KeygenResult>::err(e)).unwrap()
+        }
+    }
+}
+
+// Encrypt each share under its participant's key; a share without a key is
+// an error rather than being stored in the clear
+#[cfg(feature = "full")]
+fn encrypt_shares(
+    shares: &BTreeMap<String, String>,
+    share_keys: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, FrostError> {
+    let mut rng = secure_rng()?;
+    let mut encrypted = BTreeMap::new();
+    for (participant, share) in shares {
+        let key_hex = share_keys.get(participant).ok_or(FrostError::MalformedInput(
+            format!("No share key for {}", participant)
+        ))?;
+        encrypted.insert(participant.clone(), seal_share(share.as_bytes(), participant, key_hex, &mut rng)?);
+    }
+    Ok(encrypted)
+}
+
+// Encrypt one share under a 32-byte hex key with the participant label as
+// associated data, as hex of the nonce followed by the ciphertext and tag
+#[cfg(feature = "full")]
+fn seal_share(
+    share: &[u8],
+    participant: &str,
+    key_hex: &str,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<String, FrostError> {
+    let key = Zeroizing::new(nostr::decode_fixed::<32>(key_hex, "share key")?);
+    let cipher = Aes256Gcm::new_from_slice(&*key)
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid share key: {}", e)))?;
+    
+    let mut nonce = [0u8; SHARE_NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+    let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce), Payload {
+        msg: share,
+        aad: participant.as_bytes(),
+    }).map_err(|_| FrostError::SerializationError(format!("Failed to encrypt share for {}", participant)))?;
+    
+    Ok(hex::encode([&nonce[..], &ciphertext].concat()))
+}
+
+// Decrypt a share sealed by seal_share. A wrong key and a share sealed for
+// another participant both fail authentication.
+#[cfg(feature = "full")]
+fn open_share(sealed_hex: &str, participant: &str, key_hex: &str) -> Result<Zeroizing<Vec<u8>>, FrostError> {
+    let sealed = hex_to_bytes(sealed_hex, "encrypted share")?;
+    if sealed.len() < SHARE_NONCE_LEN {
+        return Err(FrostError::MalformedInput("Encrypted share is shorter than its nonce".to_string()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(SHARE_NONCE_LEN);
+    
+    let key = Zeroizing::new(nostr::decode_fixed::<32>(key_hex, "share key")?);
+    let cipher = Aes256Gcm::new_from_slice(&*key)
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid share key: {}", e)))?;
+    cipher.decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: participant.as_bytes() })
+        .map(Zeroizing::new)
+        .map_err(|_| FrostError::IntegrityError(format!(
+            "Encrypted share for {} did not authenticate: wrong key, or sealed for another participant", participant
+        )))
+}
+
+/// Re-encrypt a stored share under a new share key
+///
+/// `ciphertext` is a share as stored by `package_keygen_result` with share
+/// keys, and `participant` the label it was stored under. The share is
+/// decrypted with `old_key_hex` and encrypted with `new_key_hex` under a
+/// fresh nonce, the label authenticated by both, so a share sealed for one
+/// participant can't be rewrapped as another's. The plaintext never leaves
+/// the call and is zeroized before it returns. A wrong old key is an
+/// `IntegrityError`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn rewrap_share(ciphertext: &str, old_key_hex: &str, new_key_hex: &str, participant: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("ciphertext", ciphertext), ("participant", participant)])?;
+        check_present(&[("ciphertext", ciphertext), ("participant", participant)])?;
+        
+        let share = open_share(ciphertext, participant, old_key_hex)?;
+        seal_share(&share, participant, new_key_hex, &mut secure_rng()?)
+    })();
+    
+    match result {
+        Ok(rewrapped) => {
+            serde_json::to_string(&FrostResult::ok(rewrapped)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// === SIGNING FUNCTIONS ===
+
+/// Initialize a new signing ceremony
+///
+/// `signers_json` is a JSON array of signer labels, or an object mapping each
+/// label to its identifier hex, such as the `identifiers` table from the
+/// trusted dealer. With a table, round 1 only accepts the key package with
+/// that identifier for each signer. Pass a `ceremony_id` if the state will be
+/// sealed between calls.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn create_signing_state(
+    message: &[u8],
+    signers_json: &str,
+    ceremony_id: Option<String>,
+    created_at: Option<u64>,
+    expires_at: Option<u64>
+) -> String {
+    let result = new_signing_state(message, signers_json, ceremony_id, created_at, expires_at, None);
+    
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SigningState>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Initialize a signing ceremony for a known group, resolving every signer's
+/// identifier up front
+///
+/// Takes the same `signers_json` as `create_signing_state`. Each signer is
+/// resolved against `group_public_key_json`: through the table when one is
+/// given, otherwise by treating each label as a 32-byte x-only Nostr pubkey
+/// (see `identifier_from_pubkey`). Every resolved identifier must belong to
+/// the group. The returned state's `signer_identifiers` holds the full
+/// mapping, so round 1 only accepts each signer's own key package and later
+/// calls read identifiers from the state rather than re-deriving them. The
+/// state is also pinned to the group, as if the first signer had committed.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn create_signing_state_for_group(
+    message: &[u8],
+    signers_json: &str,
+    group_public_key_json: &str,
+    ceremony_id: Option<String>,
+    created_at: Option<u64>,
+    expires_at: Option<u64>
+) -> String {
+    let result = (|| -> Result<SigningState, FrostError> {
+        check_input_sizes(&[("group_public_key_json", group_public_key_json)])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        let mut state = new_signing_state(message, signers_json, ceremony_id, created_at, expires_at, None)?;
+        resolve_signer_identifiers(&mut state, &group_public_key)?;
+        
+        Ok(state)
+    })();
+    
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SigningState>::err(e)).unwrap()
+        }
+    }
+}
+
+// The identifier each signer's key package resolved to, in the table form
+// `create_signing_state` takes
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SigningPreflight {
+    pub signers: BTreeMap<String, String>,
+}
+
+/// Check a signing request before any ceremony state is created
+///
+/// `signers_json` maps each chosen signer's label to its key package (JSON, a
+/// checksummed envelope, or a `KeyPackageHandle`, which stays loaded). The
+/// checks run in this order and the first failure is returned:
+///
+/// - `message` is a 32-byte Nostr event id (`MalformedInput`)
+/// - there are at least `threshold` signers (`InsufficientParticipants`)
+/// - each key package loads (`MalformedInput` or `CorruptedShare`)
+/// - each key package is a member of the group, under its own identifier
+///   (`InvalidParticipant`)
+///
+/// On success the data is `{ signers }`, mapping each label to its
+/// identifier, ready to pass to `create_signing_state`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn preflight_signing(
+    message: &[u8],
+    signers_json: &str,
+    group_public_key_json: &str,
+    threshold: u16
+) -> String {
+    let result = (|| -> Result<SigningPreflight, FrostError> {
+        check_input_sizes(&[
+            ("signers_json", signers_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("signers_json", signers_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        if message.len() != 32 {
+            return Err(FrostError::MalformedInput(
+                format!("Expected a 32-byte event id, got {} bytes", message.len())
+            ));
+        }
+        
+        let signers: BTreeMap<String, String> = serde_json::from_str(signers_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signers: {}", e)))?;
+        check_participant_count("signers", signers.len())?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        if threshold == 0 {
+            return Err(FrostError::MalformedInput("Threshold must be at least 1".to_string()));
+        }
+        ensure_threshold_met(threshold, signers.len())?;
+        
+        let mut identifiers = BTreeMap::new();
+        let mut seen = BTreeSet::new();
+        for (signer, key_package_json) in &signers {
+            let key_package = SignerKeyPackage::checkout(key_package_json)?;
+            let identifier_hex = identifier_to_hex(key_package.identifier());
+            
+            let member = key_package.verifying_key() == group_public_key.verifying_key()
+                && group_public_key.verifying_shares().get(key_package.identifier()) == Some(key_package.verifying_share());
+            if !member {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Signer {}'s key package {} is not a member of the group", signer, identifier_hex)
+                ));
+            }
+            if !seen.insert(identifier_hex.clone()) {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Identifier {} is assigned to more than one signer", identifier_hex)
+                ));
+            }
+            identifiers.insert(signer.clone(), identifier_hex);
+        }
+        
+        Ok(SigningPreflight { signers: identifiers })
+    })();
+    
+    match result {
+        Ok(preflight) => {
+            serde_json::to_string(&FrostResult::ok(preflight)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SigningPreflight>::err(e)).unwrap()
+        }
+    }
+}
+
+// Fill in the identifier of every signer the table left out by deriving it
+// from the label as a Nostr pubkey, check they all belong to the group, and
+// pin the state to it
+#[cfg(feature = "full")]
+fn resolve_signer_identifiers(
+    state: &mut SigningState,
+    group_public_key: &PublicKeyPackage<Secp256K1Sha256>,
+) -> Result<(), FrostError> {
+    let mut seen = BTreeSet::new();
+    for signer in &state.signers {
+        let identifier = match state.signer_identifiers.get(signer) {
+            Some(identifier_hex) => identifier_from_hex(identifier_hex)?,
+            None => derive_identifier_from_pubkey(signer).map_err(|_| FrostError::InvalidParticipant(format!(
+                "Cannot resolve signer {} to an identifier: pass a table of identifiers or label signers by their Nostr pubkey", signer
+            )))?,
+        };
+        let identifier_hex = identifier_to_hex(&identifier);
+        
+        if !group_public_key.verifying_shares().contains_key(&identifier) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Signer {} has identifier {}, which is not in the group", signer, identifier_hex)
+            ));
+        }
+        if !seen.insert(identifier_hex.clone()) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Identifier {} is assigned to more than one signer", identifier_hex)
+            ));
+        }
+        state.signer_identifiers.insert(signer.clone(), identifier_hex);
+    }
+    
+    let group_key = group_public_key.verifying_key().serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?;
+    state.group_verifying_key = Some(hex::encode(group_key));
+    
+    Ok(())
+}
+
+// Signers as a list of labels, or as a table of labels to identifier hex
+// such as a trusted dealer's `identifiers`
+#[cfg(feature = "full")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SignerList {
+    Labels(Vec<String>),
+    Identifiers(BTreeMap<String, String>),
+}
+
+// A round 1 signing state over `message` for the signers in `signers_json`
+#[cfg(feature = "full")]
+fn new_signing_state(
+    message: &[u8],
+    signers_json: &str,
+    ceremony_id: Option<String>,
+    created_at: Option<u64>,
+    expires_at: Option<u64>,
+    taproot: Option<TaprootSpend>,
+) -> Result<SigningState, FrostError> {
+    check_input_sizes(&[("signers_json", signers_json)])?;
+    check_message_size(message)?;
+    check_expiry_window(created_at, expires_at)?;
+    
+    let (signers, signer_identifiers) = match serde_json::from_str(signers_json)
+        .map_err(|e| FrostError::MalformedInput(e.to_string()))?
+    {
+        SignerList::Labels(signers) => (signers, BTreeMap::new()),
+        SignerList::Identifiers(table) => {
+            let mut signer_identifiers = BTreeMap::new();
+            let mut seen = BTreeSet::new();
+            for (signer, identifier_hex) in table {
+                let identifier_hex = identifier_to_hex(&identifier_from_hex(&identifier_hex)?);
+                if !seen.insert(identifier_hex.clone()) {
+                    return Err(FrostError::InvalidParticipant(
+                        format!("Identifier {} is assigned to more than one signer", identifier_hex)
+                    ));
+                }
+                signer_identifiers.insert(signer, identifier_hex);
+            }
+            (signer_identifiers.keys().cloned().collect(), signer_identifiers)
+        }
+    };
+    check_participant_count("signers", signers.len())?;
+        
+    if signers.is_empty() {
+        return Err(FrostError::InsufficientParticipants {
+            required: 1,
+            actual: 0,
+        });
+    }
+    
+    let state = SigningState {
+        kind: CeremonyKind::Signing,
+        message: message.to_vec(),
+        current_round: 1,
+        signers,
+        round1_packages: BTreeMap::new(),
+        signature_shares: BTreeMap::new(),
+        final_signature: None,
+        signer_identifiers,
+        threshold: None,
+        ceremony_id,
+        validated_identifiers: BTreeSet::new(),
+        imported_nonce_ids: BTreeSet::new(),
+        taproot,
+        group_verifying_key: None,
+        transcript_commitments: BTreeMap::new(),
+        signer_verifying_shares: BTreeMap::new(),
+        created_at,
+        expires_at,
+    };
+    log_event(LogEvent::RoundStarted {
+        ceremony: CeremonyKind::Signing,
+        round: 1,
+        ceremony_id: state.ceremony_id.as_deref(),
+    });
+    
+    Ok(state)
+}
+
+// A signer's round 1 commitment labeled with its FROST identifier, so the
+// coordinator can build the signing package without a participant table.
+// `ceremony_binding` ties it to the ceremony id and message it was made for.
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SignerCommitment {
+    pub identifier: String,
+    pub commitment: frost::round1::SigningCommitments,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ceremony_binding: Option<String>,
+}
+
+// Separates commitment bindings from other hashes of the same commitments
+#[cfg(feature = "full")]
+const COMMITMENT_BINDING_DOMAIN: &[u8] = b"frost-wasm-core/commitment-binding/v1";
+
+// SHA-256 over the ceremony id and message (each length-prefixed), the
+// signer's identifier, and its commitment
+#[cfg(feature = "full")]
+fn commitment_binding(
+    ceremony_id: Option<&str>,
+    message: &[u8],
+    identifier: &FrostIdentifier,
+    commitment: &frost::round1::SigningCommitments,
+) -> Result<String, FrostError> {
+    let ceremony_id = ceremony_id.unwrap_or_default();
+    let commitment_bytes = commitment.serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitments: {}", e)))?;
+    let digest = Sha256::new()
+        .chain_update(COMMITMENT_BINDING_DOMAIN)
+        .chain_update((ceremony_id.len() as u64).to_be_bytes())
+        .chain_update(ceremony_id)
+        .chain_update((message.len() as u64).to_be_bytes())
+        .chain_update(message)
+        .chain_update(identifier.serialize())
+        .chain_update(commitment_bytes)
+        .finalize();
+    Ok(hex::encode(digest))
+}
+
+// Reject commitments bound to another ceremony or message, such as one a
+// signer replayed from an earlier ceremony. Unbound commitments pass.
+#[cfg(feature = "full")]
+fn check_commitment_bindings(state: &SigningState, commitments_json: &str) -> Result<(), FrostError> {
+    let signer_commitments: Vec<SignerCommitment> = serde_json::from_str(commitments_json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to parse commitments: {}", e)))?;
+    
+    for signer_commitment in &signer_commitments {
+        let Some(binding) = &signer_commitment.ceremony_binding else {
+            continue;
+        };
+        let identifier = identifier_from_hex(&signer_commitment.identifier)?;
+        let expected = commitment_binding(state.ceremony_id.as_deref(), &state.message, &identifier, &signer_commitment.commitment)?;
+        if *binding != expected {
+            return Err(FrostError::SigningError(format!(
+                "stale commitment: {}'s commitment was made for another ceremony or message", signer_commitment.identifier
+            )));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Handle participant data for signing round 1 (nonce generation)
+///
+/// Returns the updated state and the signer's `{ identifier, commitment }`.
+///
+/// The first key package pins the ceremony to its group key, and key packages
+/// from any other group are rejected with `InvalidParticipant`. When
+/// `group_public_key_json` is given, the key package's verifying share must
+/// also be the group's share for its identifier.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn signing_round1(
+    state_json: &str,
+    participant_id: &str,
+    key_package_json: &str,
+    group_public_key_json: Option<String>
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, SignerCommitment), FrostError> {
+        let group_public_key_json = group_public_key_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("key_package_json", key_package_json),
+            ("group_public_key_json", group_public_key_json.unwrap_or_default()),
+        ])?;
+        check_present(&[("key_package_json", key_package_json)])?;
+        if let Some(json) = group_public_key_json {
+            check_present(&[("group_public_key_json", json)])?;
+        }
+        
+        let mut state = parse_signing_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        if state.current_round != 1 {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Expected round 1, got round {}", state.current_round)
+            ));
+        }
+        
+        let group_public_key = group_public_key_json
+            .map(|json| serde_json::from_str::<PublicKeyPackage<Secp256K1Sha256>>(json)
+                .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e))))
+            .transpose()?;
+        
+        // Deserialize or check out the key package for this participant
+        let key_package = SignerKeyPackage::checkout(key_package_json)?;
+        let signer_commitment = commit_signer(&mut state, participant_id, &key_package, group_public_key.as_ref(), &mut timings)?;
+        
+        Ok((state, signer_commitment))
+    })();
+    
+    match result {
+        Ok((state, commitment)) => {
+            result_json(&FrostResult::ok((state, commitment)).with_timings(timings))
+        }
+        Err(e) => {
+            result_json(&FrostResult::<(SigningState, SignerCommitment)>::err(e).with_timings(timings))
+        }
+    }
+}
+
+/// Handle participant data for signing round 2 (signature share generation)
+///
+/// The group public key is only needed by the call that completes the
+/// ceremony and aggregates; other signers may omit it or pass an empty string.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn signing_round2(
+    state_json: &str,
+    participant_id: &str,
+    key_package_json: &str,
+    signing_package_json: &str,
+    group_public_key_json: Option<String>
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, Option<String>), FrostError> {
+        let group_public_key_json = group_public_key_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("key_package_json", key_package_json),
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json.unwrap_or_default()),
+        ])?;
+        check_present(&[
+            ("key_package_json", key_package_json),
+            ("signing_package_json", signing_package_json),
+        ])?;
+        if let Some(json) = group_public_key_json {
+            check_present(&[("group_public_key_json", json)])?;
+        }
+        
+        let mut state = parse_signing_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        // Deserialize or check out the key package for this participant
+        let key_package = SignerKeyPackage::checkout(key_package_json)?;
+        
+        // Deserialize the signing package (contains message and all commitments)  
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        
+        sign_share(&mut state, participant_id, &key_package, &signing_package, &mut timings)?;
+        let threshold = state.threshold.unwrap_or(*key_package.min_signers());
+        key_package.release();
+        
+        // If all participants have signed, aggregate the signature
+        let final_signature = aggregate_if_complete(&mut state, threshold, &signing_package, group_public_key_json, &mut timings)?;
+        
+        Ok((state, final_signature))
+    })();
+    
+    match result {
+        Ok((state, signature)) => {
+            result_json(&FrostResult::ok((state, signature)).with_timings(timings))
+        }
+        Err(e) => {
+            result_json(&FrostResult::<(SigningState, Option<String>)>::err(e).with_timings(timings))
+        }
+    }
+}
+
+/// Expand participant weights into virtual participant labels
+///
+/// `weights_json` maps each logical participant to its weight, e.g.
+/// `{"server": 3, "alice": 1}`. A participant of weight `w` is given `w`
+/// virtual labels, `server#1` through `server#3`, each of which holds its own
+/// share. Pass the returned labels to `dealer_keygen` (or a DKG) in place of
+/// the participant names, and the threshold then counts shares rather than
+/// parties, so a weight-3 server meets a threshold of 4 with any one other
+/// participant.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn virtual_participants(weights_json: &str) -> String {
+    let result = (|| -> Result<Vec<String>, FrostError> {
+        check_input_sizes(&[("weights_json", weights_json)])?;
+        
+        let weights: BTreeMap<String, u16> = serde_json::from_str(weights_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse participant weights: {}", e)))?;
+        if weights.is_empty() {
+            return Err(FrostError::MalformedInput("At least one participant weight is required".to_string()));
+        }
+        
+        let mut labels = Vec::new();
+        for (participant, weight) in &weights {
+            if participant.is_empty() || participant.contains('#') {
+                return Err(FrostError::MalformedInput(
+                    format!("Participant name {:?} must be non-empty and must not contain '#'", participant)
+                ));
+            }
+            if *weight == 0 {
+                return Err(FrostError::MalformedInput(
+                    format!("Participant {} must have a weight of at least 1", participant)
+                ));
+            }
+            
+            check_participant_count("weights_json", labels.len() + *weight as usize)?;
+            labels.extend((1..=*weight).map(|index| format!("{}#{}", participant, index)));
+        }
+        
+        Ok(labels)
+    })();
+    
+    match result {
+        Ok(labels) => serde_json::to_string(&FrostResult::ok(labels)).unwrap(),
+        Err(e) => serde_json::to_string(&FrostResult::<Vec<String>>::err(e)).unwrap(),
+    }
+}
+
+/// Run signing round 1 for every share held by a weighted participant
+///
+/// `key_packages_json` maps each of the participant's virtual labels (as
+/// returned by `virtual_participants`) to its key package, and every label
+/// must belong to `participant_id`. Each share commits exactly as
+/// `signing_round1` would. Returns the updated state and the commitments, one
+/// per share. Fails as a whole if any share fails.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn signing_round1_weighted(
+    state_json: &str,
+    participant_id: &str,
+    key_packages_json: &str,
+    group_public_key_json: Option<String>
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, Vec<SignerCommitment>), FrostError> {
+        let group_public_key_json = group_public_key_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("key_packages_json", key_packages_json),
+            ("group_public_key_json", group_public_key_json.unwrap_or_default()),
+        ])?;
+        if let Some(json) = group_public_key_json {
+            check_present(&[("group_public_key_json", json)])?;
+        }
+        
+        let mut state = parse_signing_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        if state.current_round != 1 {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Expected round 1, got round {}", state.current_round)
+            ));
+        }
+        
+        let group_public_key = group_public_key_json
+            .map(|json| serde_json::from_str::<PublicKeyPackage<Secp256K1Sha256>>(json)
+                .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e))))
+            .transpose()?;
+        
+        let key_packages = parse_weighted_key_packages(participant_id, key_packages_json)?;
+        let mut commitments = Vec::with_capacity(key_packages.len());
+        for (label, key_package_json) in &key_packages {
+            // A 1-of-N group's round 1 ends with its first commitment
+            if state.current_round != 1 {
+                break;
+            }
+            let key_package = SignerKeyPackage::checkout(key_package_json)?;
+            commitments.push(commit_signer(&mut state, label, &key_package, group_public_key.as_ref(), &mut timings)?);
+        }
+        
+        Ok((state, commitments))
+    })();
+    
+    match result {
+        Ok((state, commitments)) => {
+            result_json(&FrostResult::ok((state, commitments)).with_timings(timings))
+        }
+        Err(e) => {
+            result_json(&FrostResult::<(SigningState, Vec<SignerCommitment>)>::err(e).with_timings(timings))
+        }
+    }
+}
+
+/// Run signing round 2 for every share held by a weighted participant
+///
+/// Takes the same `key_packages_json` map as `signing_round1_weighted` and
+/// signs with each share against the nonces it committed. As with
+/// `signing_round2`, the group public key is only needed by the call that
+/// completes the ceremony and aggregates.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn signing_round2_weighted(
+    state_json: &str,
+    participant_id: &str,
+    key_packages_json: &str,
+    signing_package_json: &str,
+    group_public_key_json: Option<String>
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, Option<String>), FrostError> {
+        let group_public_key_json = group_public_key_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+            ("key_packages_json", key_packages_json),
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json.unwrap_or_default()),
+        ])?;
+        check_present(&[("signing_package_json", signing_package_json)])?;
+        if let Some(json) = group_public_key_json {
+            check_present(&[("group_public_key_json", json)])?;
+        }
+        
+        let mut state = parse_signing_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        
+        let key_packages = parse_weighted_key_packages(participant_id, key_packages_json)?;
+        for (label, key_package_json) in &key_packages {
+            let key_package = SignerKeyPackage::checkout(key_package_json)?;
+            sign_share(&mut state, label, &key_package, &signing_package, &mut timings)?;
+            key_package.release();
+        }
+        
+        // If all participants have signed, aggregate the signature
+        let threshold = recorded_threshold(&state)?;
+        let final_signature = aggregate_if_complete(&mut state, threshold, &signing_package, group_public_key_json, &mut timings)?;
+        
+        Ok((state, final_signature))
+    })();
+    
+    match result {
+        Ok((state, signature)) => {
+            result_json(&FrostResult::ok((state, signature)).with_timings(timings))
+        }
+        Err(e) => {
+            result_json(&FrostResult::<(SigningState, Option<String>)>::err(e).with_timings(timings))
+        }
+    }
+}
+
+/// Rebuild a coordinator's round 2 signing state from the public transcript
+///
+/// For disaster recovery when the coordinator's state is lost but `message`
+/// and the signers' `{ identifier, commitment }` array (as for
+/// `build_signing_package`) survived. Returns a round 2 state with one signer
+/// per commitment, labeled by its identifier hex, and the signing package,
+/// which is the one the signers originally committed to. The transcript
+/// doesn't carry the group's threshold, so the state records the number of
+/// commitments as its threshold, since every signer in the package must sign
+/// anyway. Surviving signers re-sign that package from their own states and
+/// the coordinator collects the shares with `add_signature_share`.
+///
+/// Nonces are never part of the transcript. A signer that lost its nonces
+/// must commit again in a new ceremony, since its new commitment changes the
+/// signing package; a share it made over any other package fails
+/// `add_signature_share`'s verification against this one, so reused or
+/// replaced nonces are caught rather than aggregated. The state keeps the
+/// transcript's commitments, so a package built from any others is refused
+/// outright.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn resume_signing_from_transcript(message: &[u8], commitments_json: &str) -> String {
+    let result = (|| -> Result<(SigningState, String), FrostError> {
+        check_input_sizes(&[("commitments_json", commitments_json)])?;
+        check_present(&[("commitments_json", commitments_json)])?;
+        check_message_size(message)?;
+        
+        let signing_package = build_signing_package_from_commitments(message, commitments_json)?;
+        let identifiers: Vec<String> = signing_package.signing_commitments().keys()
+            .map(identifier_to_hex)
+            .collect();
+        check_participant_count("commitments_json", identifiers.len())?;
+        if identifiers.is_empty() {
+            return Err(FrostError::InsufficientParticipants {
+                required: 1,
+                actual: 0,
+            });
+        }
+        
+        let transcript_commitments = signing_package.signing_commitments().iter()
+            .map(|(identifier, commitment)| serde_json::to_string(commitment)
+                .map(|commitment| (identifier_to_hex(identifier), commitment))
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitment: {}", e))))
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        
+        let state = SigningState {
+            kind: CeremonyKind::Signing,
+            message: message.to_vec(),
+            current_round: 2,
+            signers: identifiers.clone(),
+            round1_packages: BTreeMap::new(),
+            signature_shares: BTreeMap::new(),
+            final_signature: None,
+            signer_identifiers: identifiers.iter().map(|identifier| (identifier.clone(), identifier.clone())).collect(),
+            threshold: Some(participant_count(identifiers.len())?),
+            ceremony_id: None,
+            validated_identifiers: BTreeSet::new(),
+            imported_nonce_ids: BTreeSet::new(),
+            taproot: None,
+            group_verifying_key: None,
+            transcript_commitments,
+            signer_verifying_shares: BTreeMap::new(),
+            created_at: None,
+            expires_at: None,
+        };
+        log_event(LogEvent::RoundStarted {
+            ceremony: CeremonyKind::Signing,
+            round: 2,
+            ceremony_id: None,
+        });
+        
+        let signing_package = serde_json::to_string(&signing_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signing package: {}", e)))?;
+        Ok((state, signing_package))
+    })();
+    
+    match result {
+        Ok((state, signing_package)) => {
+            serde_json::to_string(&FrostResult::ok((state, signing_package))).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(SigningState, String)>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Verify a signature share from another signer and store it in the state
+///
+/// The share is checked against the signer's verifying share before it is
+/// accepted, so a bad share is rejected on arrival and named, instead of
+/// failing the final aggregation. Once every signer's share is in, the
+/// signature is aggregated and returned.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn add_signature_share(
+    state_json: &str,
+    identifier_hex: &str,
+    share_json: &str,
+    signing_package_json: &str,
+    group_public_key_json: &str
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, Option<String>), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("identifier_hex", identifier_hex),
+            ("share_json", share_json),
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        let mut state = parse_signing_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        if state.current_round != 2 {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Expected round 2, got round {}", state.current_round)
+            ));
+        }
+        
+        // Share verification here has no taproot tweak, so it would reject
+        // every honest share of a Bitcoin ceremony
+        if state.taproot.is_some() {
+            return Err(FrostError::InvalidStateTransition(
+                "Taproot ceremonies must collect signature shares with signing_round2".to_string()
+            ));
+        }
+        
+        let identifier = identifier_from_hex(identifier_hex)?;
+        let identifier_hex = identifier_to_hex(&identifier);
+        let participant_id = state.signer_identifiers.iter()
+            .find(|(_, recorded)| **recorded == identifier_hex)
+            .map(|(participant, _)| participant.clone())
+            .ok_or(FrostError::InvalidParticipant(format!("No signer committed with identifier {}", identifier_hex)))?;
+        
+        if state.signature_shares.contains_key(&participant_id) {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Participant {} already submitted a signature share", participant_id)
+            ));
+        }
+        
+        let signature_share: round2::SignatureShare<Secp256K1Sha256> = serde_json::from_str(share_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signature share: {}", e)))?;
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        check_group_matches_key_packages(&state, &group_public_key, [&identifier])?;
+        
+        let verifying_share = group_public_key.verifying_shares().get(&identifier)
+            .ok_or(FrostError::InvalidParticipant(format!("No verifying share for {}", participant_id)))?;
+        
+        timings.measure("share_verification", || frost_core::verify_signature_share(
+            identifier,
+            verifying_share,
+            &signature_share,
+            &signing_package,
+            group_public_key.verifying_key(),
+        )).map_err(|e| FrostError::SigningError(format!("Invalid signature share from {}: {}", participant_id, e)))?;
+        
+        log_event(LogEvent::PackageAccepted {
+            ceremony: CeremonyKind::Signing,
+            round: 2,
+            participant: &participant_id,
+            ceremony_id: state.ceremony_id.as_deref(),
+        });
+        state.signature_shares.insert(participant_id, share_json.to_string());
+        state.validated_identifiers.insert(identifier_hex);
+        
+        let threshold = recorded_threshold(&state)?;
+        let final_signature = aggregate_if_complete(&mut state, threshold, &signing_package, Some(group_public_key_json), &mut timings)?;
+        
+        Ok((state, final_signature))
+    })();
+    
+    match result {
+        Ok((state, signature)) => {
+            serde_json::to_string(&FrostResult::ok((state, signature)).with_timings(timings)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(SigningState, Option<String>)>::err(e).with_timings(timings)).unwrap()
+        }
+    }
+}
+
+/// Re-run aggregation over the signature shares stored in the state
+///
+/// Aggregation is deterministic, so this reproduces the original signature
+/// byte for byte, e.g. after a crash between aggregating and persisting. If
+/// the state already records a final signature, a different result is
+/// reported as an `IntegrityError` rather than silently replacing it.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn aggregate_signature(
+    state_json: &str,
+    signing_package_json: &str,
+    group_public_key_json: &str
+) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, String), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        let mut state = parse_signing_state(state_json)?;
+        let recorded_signature = state.final_signature.take();
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        
+        let threshold = recorded_threshold(&state)?;
+        let signature = aggregate_if_complete(&mut state, threshold, &signing_package, Some(group_public_key_json), &mut timings)?
+            .ok_or(FrostError::InvalidStateTransition(format!(
+                "Waiting for {} more signature shares",
+                state.signers.len() - state.signature_shares.len()
+            )))?;
+        
+        if recorded_signature.is_some_and(|recorded| recorded != signature) {
+            return Err(FrostError::IntegrityError(
+                "Re-aggregation produced a different signature than the one recorded".to_string()
+            ));
+        }
+        
+        Ok((state, signature))
+    })();
+    
+    match result {
+        Ok((state, signature)) => {
+            serde_json::to_string(&FrostResult::ok((state, signature)).with_timings(timings)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(SigningState, String)>::err(e).with_timings(timings)).unwrap()
+        }
+    }
+}
+
+/// Aggregate signature shares and verify the result without touching any state
+///
+/// `shares_json` maps each signer's identifier (hex) to its signature share
+/// JSON, as collected for `signing_package_json`. Every share is checked
+/// against its signer's verifying share, then the aggregate is verified
+/// against the group key; the signature is returned only if both pass, so a
+/// coordinator can check it before persisting it as final. A bad share or
+/// failed verification is a `SigningError`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn try_aggregate_preview(
+    signing_package_json: &str,
+    shares_json: &str,
+    group_public_key_json: &str
+) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("signing_package_json", signing_package_json),
+            ("shares_json", shares_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("signing_package_json", signing_package_json),
+            ("shares_json", shares_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        let share_entries: BTreeMap<String, String> = serde_json::from_str(shares_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signature shares: {}", e)))?;
+        
+        let mut signature_shares = BTreeMap::new();
+        for (identifier_hex, share_data) in &share_entries {
+            let identifier = identifier_from_hex(identifier_hex)?;
+            let share: round2::SignatureShare<Secp256K1Sha256> = serde_json::from_str(share_data)
+                .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize share for {}: {}", identifier_hex, e)))?;
+            signature_shares.insert(identifier, share);
+        }
+        verify_signature_shares(&signature_shares, &signing_package, &group_public_key, identifier_to_hex)?;
+        
+        let signature = frost::aggregate(&signing_package, &signature_shares, &group_public_key)
+            .map_err(|e| FrostError::SigningError(format!("Failed to aggregate signature: {}", e)))?;
+        group_public_key.verifying_key().verify(signing_package.message(), &signature)
+            .map_err(|e| FrostError::SigningError(format!("Aggregated signature does not verify: {}", e)))?;
+        
+        serde_json::to_string(&signature)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signature: {}", e)))
+    })();
+    
+    match result {
+        Ok(signature) => {
+            serde_json::to_string(&FrostResult::ok(signature)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Build the signing package from the commitments collected in round 1
+///
+/// By default the commitments come from the state, which must be in round 2.
+/// A coordinator that collected the `{ identifier, commitment }` values
+/// returned by `signing_round1` can pass them as a JSON array instead; only
+/// the message is then taken from the state.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn build_signing_package(state_json: &str, commitments_json: Option<String>) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("commitments_json", commitments_json.as_deref().unwrap_or_default()),
+        ])?;
+        
+        let state = parse_signing_state(state_json)?;
+        
+        let signing_package = match commitments_json.as_deref() {
+            Some(commitments_json) => {
+                check_commitment_bindings(&state, commitments_json)?;
+                build_signing_package_from_commitments(&state.message, commitments_json)?
+            }
+            None => {
+                if state.current_round != 2 {
+                    return Err(FrostError::InvalidStateTransition(
+                        format!("Expected round 2, got round {}", state.current_round)
+                    ));
+                }
+                build_signing_package_from_state(&state)?
+            }
+        };
+        serde_json::to_string(&signing_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signing package: {}", e)))
+    })();
+    
+    match result {
+        Ok(signing_package) => {
+            serde_json::to_string(&FrostResult::ok(signing_package)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// Version tag of commitment bundles
+#[cfg(feature = "full")]
+const COMMITMENTS_BUNDLE_FORMAT: &str = "frost-commitments-bundle/v1";
+
+// Separates bundle checksums from other hashes
+#[cfg(feature = "full")]
+const COMMITMENTS_BUNDLE_DOMAIN: &[u8] = b"frost-wasm-core/commitments-bundle/v1";
+
+// Round 1 commitments carried to an air-gapped coordinator
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct CommitmentsBundle {
+    pub format: String,
+    pub message_digest: String,
+    pub commitments: Vec<SignerCommitment>,
+    pub checksum: String,
+}
+
+/// Export a round 2 state's commitments for an offline signing package build
+///
+/// The bundle holds only public values: the `{ identifier, commitment }` of
+/// each signer in identifier order, the SHA-256 of the message, and a
+/// SHA-256 checksum over both and the format tag. Carry it to the air-gapped
+/// coordinator with the message and pass both to
+/// `build_signing_package_from_bundle`. The checksum catches corruption in
+/// transit; it is not a MAC.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn export_commitments_bundle(state_json: &str) -> String {
+    let result = (|| -> Result<CommitmentsBundle, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
+        
+        let state = parse_signing_state(state_json)?;
+        if state.current_round != 2 {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Expected round 2, got round {}", state.current_round)
+            ));
+        }
+        
+        let commitments: Vec<SignerCommitment> = state_commitments(&state)?.into_iter()
+            .map(|(identifier, commitment)| SignerCommitment { identifier: identifier_to_hex(&identifier), commitment, ceremony_binding: None })
+            .collect();
+        let message_digest = hex::encode(Sha256::digest(&state.message));
+        let checksum = commitments_bundle_checksum(&message_digest, &commitments)?;
+        
+        Ok(CommitmentsBundle {
+            format: COMMITMENTS_BUNDLE_FORMAT.to_string(),
+            message_digest,
+            commitments,
+            checksum,
+        })
+    })();
+    
+    match result {
+        Ok(bundle) => {
+            serde_json::to_string(&FrostResult::ok(bundle)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<CommitmentsBundle>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Build the signing package from a bundle made by `export_commitments_bundle`
+///
+/// The bundle's checksum must match its contents and its message digest the
+/// SHA-256 of `message`; either mismatch is an `IntegrityError`. The package
+/// is the one `build_signing_package` gives for the exporting state.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn build_signing_package_from_bundle(message: &[u8], bundle_json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("bundle_json", bundle_json)])?;
+        check_message_size(message)?;
+        check_present(&[("bundle_json", bundle_json)])?;
+        
+        let bundle: CommitmentsBundle = serde_json::from_str(bundle_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize commitments bundle: {}", e)))?;
+        if bundle.format != COMMITMENTS_BUNDLE_FORMAT {
+            return Err(FrostError::MalformedInput(format!("Unknown commitments bundle format {}", bundle.format)));
+        }
+        if commitments_bundle_checksum(&bundle.message_digest, &bundle.commitments)? != bundle.checksum.to_lowercase() {
+            return Err(FrostError::IntegrityError("Commitments bundle does not match its checksum".to_string()));
+        }
+        if hex::encode(Sha256::digest(message)) != bundle.message_digest.to_lowercase() {
+            return Err(FrostError::IntegrityError("Commitments bundle was exported for a different message".to_string()));
+        }
+        
+        let commitments_json = serde_json::to_string(&bundle.commitments)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitments: {}", e)))?;
+        let signing_package = build_signing_package_from_commitments(message, &commitments_json)?;
+        serde_json::to_string(&signing_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signing package: {}", e)))
+    })();
+    
+    match result {
+        Ok(signing_package) => {
+            serde_json::to_string(&FrostResult::ok(signing_package)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// SHA-256 over the domain, format tag, message digest, and commitments JSON
+#[cfg(feature = "full")]
+fn commitments_bundle_checksum(message_digest: &str, commitments: &[SignerCommitment]) -> Result<String, FrostError> {
+    let commitments_json = serde_json::to_string(commitments)
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitments: {}", e)))?;
+    let digest = Sha256::new()
+        .chain_update(COMMITMENTS_BUNDLE_DOMAIN)
+        .chain_update(COMMITMENTS_BUNDLE_FORMAT)
+        .chain_update(message_digest.to_lowercase())
+        .chain_update(commitments_json)
+        .finalize();
+    Ok(hex::encode(digest))
+}
+
+/// Check a coordinator's signing package against one rebuilt from the signer's own view
+///
+/// `commitments_json` is the `{ identifier, commitment }` array the signer
+/// received, as for `build_signing_package`. Returns true only when the
+/// coordinator's package signs `message` with exactly these commitments. A
+/// signer should refuse to sign a package that doesn't match, since a
+/// coordinator could otherwise swap in a different message or commitment set.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn rebuild_and_compare_signing_package(
+    message: &[u8],
+    commitments_json: &str,
+    coordinator_package_json: &str
+) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[
+            ("commitments_json", commitments_json),
+            ("coordinator_package_json", coordinator_package_json),
+        ])?;
+        check_present(&[("coordinator_package_json", coordinator_package_json)])?;
+        check_message_size(message)?;
+        
+        let rebuilt = build_signing_package_from_commitments(message, commitments_json)?;
+        let coordinator_package: frost::SigningPackage = serde_json::from_str(coordinator_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse coordinator signing package: {}", e)))?;
+        
+        Ok(rebuilt == coordinator_package)
+    })();
+    
+    match result {
+        Ok(matches) => {
+            serde_json::to_string(&FrostResult::ok(matches)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+// Separates commitment fingerprints from other hashes of the same commitments
+#[cfg(feature = "full")]
+const COMMITMENT_FINGERPRINT_DOMAIN: &[u8] = b"frost-wasm-core/commitment-fingerprint/v1";
+
+/// Fingerprint each signer's round 1 nonce commitments for an audit log
+///
+/// Returns identifier hex to SHA-256 (hex) over a domain tag and the
+/// serialized hiding and binding commitments. Fingerprints are the same in
+/// every later state of the ceremony, so an auditor can confirm the same
+/// commitments were used throughout. The secret nonces stored beside each
+/// commitment are skipped without being deserialized.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn commitment_fingerprints(state_json: &str) -> String {
+    let result = (|| -> Result<BTreeMap<String, String>, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
+        
+        let state = parse_signing_state(state_json)?;
+        
+        let mut fingerprints = BTreeMap::new();
+        for (participant, round1_data) in &state.round1_packages {
+            let identifier_hex = state.signer_identifiers.get(participant)
+                .ok_or(FrostError::InvalidParticipant(format!("No identifier recorded for {}", participant)))?;
+            
+            let (_, commitments): (serde::de::IgnoredAny, frost::round1::SigningCommitments) =
+                serde_json::from_str(round1_data)
+                    .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize commitments for {}: {}", participant, e)))?;
+            let commitment_bytes = commitments.serialize()
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitments: {}", e)))?;
+            
+            let fingerprint = Sha256::new()
+                .chain_update(COMMITMENT_FINGERPRINT_DOMAIN)
+                .chain_update(&commitment_bytes)
+                .finalize();
+            fingerprints.insert(identifier_hex.clone(), hex::encode(fingerprint));
+        }
+        
+        Ok(fingerprints)
+    })();
+    
+    match result {
+        Ok(fingerprints) => {
+            serde_json::to_string(&FrostResult::ok(fingerprints)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<BTreeMap<String, String>>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Compute the group nonce commitment `R` a signing package commits to
+///
+/// `R` is the sum of `D_i + rho_i * E_i` over each signer's hiding and
+/// binding commitments, with the binding factors `rho_i` of RFC 9591 section
+/// 4.4. Binding factors hash in the group verifying key, so the group public
+/// key is needed alongside the package. Returns compressed SEC1 hex, equal to
+/// the `R` of the signature the package's shares aggregate to. Taproot
+/// ceremonies bind to the tweaked key and flip `R` to even y, so this value
+/// doesn't apply to them.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn aggregate_nonce(signing_package_json: &str, group_public_key_json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        let group_commitment = group_commitment(&signing_package, group_public_key.verifying_key())?;
+        Ok(hex::encode(group_commitment.to_affine().to_encoded_point(true).as_bytes()))
+    })();
+    
+    match result {
+        Ok(nonce) => {
+            serde_json::to_string(&FrostResult::ok(nonce)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// The group commitment R of a signing package (RFC 9591 section 4.5)
+#[cfg(feature = "full")]
+fn group_commitment(
+    signing_package: &frost::SigningPackage,
+    verifying_key: &frost::VerifyingKey,
+) -> Result<k256::ProjectivePoint, FrostError> {
+    if signing_package.signing_commitments().is_empty() {
+        return Err(FrostError::MalformedInput("Signing package has no commitments".to_string()));
+    }
+    
+    // encode_group_commitment_list: identifier || hiding || binding, in identifier order
+    let mut encoded_commitments = Vec::new();
+    let mut commitment_points = Vec::new();
+    for (identifier, commitments) in signing_package.signing_commitments() {
+        let hiding = commitments.hiding().serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid hiding commitment: {}", e)))?;
+        let binding = commitments.binding().serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid binding commitment: {}", e)))?;
+        encoded_commitments.extend_from_slice(&identifier.serialize());
+        encoded_commitments.extend_from_slice(&hiding);
+        encoded_commitments.extend_from_slice(&binding);
+        commitment_points.push((identifier, sec1_point(&hiding)?, sec1_point(&binding)?));
+    }
+    
+    let mut prefix = verifying_key.serialize()
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid group verifying key: {}", e)))?;
+    prefix.extend_from_slice(&<Secp256K1Sha256 as frost_core::Ciphersuite>::H4(signing_package.message()));
+    prefix.extend_from_slice(&<Secp256K1Sha256 as frost_core::Ciphersuite>::H5(&encoded_commitments));
+    
+    let mut group_commitment = k256::ProjectivePoint::IDENTITY;
+    for (identifier, hiding, binding) in commitment_points {
+        let binding_factor = <Secp256K1Sha256 as frost_core::Ciphersuite>::H1(
+            &[prefix.as_slice(), &identifier.serialize()].concat()
+        );
+        group_commitment += hiding + binding * binding_factor;
+    }
+    
+    Ok(group_commitment)
+}
+
+// Tag of a signer inclusion proof
+#[cfg(feature = "full")]
+const INCLUSION_PROOF_FORMAT: &str = "frost-inclusion-proof/v1";
+
+// Evidence that one signer's share was valid for a signing package: the
+// message (hex), every signer's commitment, the share, and the public keys
+// it verifies under. Signature shares are public once aggregated, so the
+// proof carries no secrets.
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SignerInclusionProof {
+    pub format: String,
+    pub identifier: String,
+    pub message: String,
+    pub commitments: Vec<SignerCommitment>,
+    pub signature_share: round2::SignatureShare<Secp256K1Sha256>,
+    pub verifying_share: frost::keys::VerifyingShare,
+    pub group_public_key: frost::VerifyingKey,
+}
+
+/// Record that a signer's share was valid and part of a signing package
+///
+/// `commitments_json` is the `{ identifier, commitment }` array the package
+/// was built from and must rebuild `signing_package_json` exactly. The share
+/// is verified against the signer's verifying share from
+/// `group_public_key_json`, which share verification needs, before the proof
+/// is returned; an invalid share is a `SigningError`. Auditors check the
+/// proof with `verify_signer_inclusion_proof`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn signer_inclusion_proof(
+    identifier_hex: &str,
+    signature_share_json: &str,
+    commitments_json: &str,
+    signing_package_json: &str,
+    group_public_key_json: &str
+) -> String {
+    let result = (|| -> Result<SignerInclusionProof, FrostError> {
+        check_input_sizes(&[
+            ("identifier_hex", identifier_hex),
+            ("signature_share_json", signature_share_json),
+            ("commitments_json", commitments_json),
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("signature_share_json", signature_share_json),
+            ("signing_package_json", signing_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        let identifier = identifier_from_hex(identifier_hex)?;
+        let signature_share: round2::SignatureShare<Secp256K1Sha256> = serde_json::from_str(signature_share_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signature share: {}", e)))?;
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        if build_signing_package_from_commitments(signing_package.message(), commitments_json)? != signing_package {
+            return Err(FrostError::MalformedInput(
+                "The commitments do not rebuild the signing package".to_string()
+            ));
+        }
+        let identifier_hex = identifier_to_hex(&identifier);
+        if !signing_package.signing_commitments().contains_key(&identifier) {
+            return Err(FrostError::InvalidParticipant(
+                format!("{} has no commitment in the signing package", identifier_hex)
+            ));
+        }
+        let verifying_share = group_public_key.verifying_shares().get(&identifier)
+            .ok_or(FrostError::InvalidParticipant(format!("No verifying share for {}", identifier_hex)))?;
+        
+        frost_core::verify_signature_share(
+            identifier,
+            verifying_share,
+            &signature_share,
+            &signing_package,
+            group_public_key.verifying_key(),
+        ).map_err(|e| FrostError::SigningError(format!("Invalid signature share from {}: {}", identifier_hex, e)))?;
+        
+        let mut commitments: Vec<SignerCommitment> = serde_json::from_str(commitments_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse commitments: {}", e)))?;
+        commitments.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        
+        Ok(SignerInclusionProof {
+            format: INCLUSION_PROOF_FORMAT.to_string(),
+            identifier: identifier_hex,
+            message: hex::encode(signing_package.message()),
+            commitments,
+            signature_share,
+            verifying_share: *verifying_share,
+            group_public_key: *group_public_key.verifying_key(),
+        })
+    })();
+    
+    match result {
+        Ok(proof) => {
+            serde_json::to_string(&FrostResult::ok(proof)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SignerInclusionProof>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Check a signer inclusion proof, optionally against the final signature
+///
+/// Returns true when the proof's share verifies for the signing package its
+/// message and commitments rebuild and its signer is one of the committers.
+/// With `signature_json`, the signature's `R` must also be that package's
+/// group commitment, which ties the share to the signature it was part of.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn verify_signer_inclusion_proof(proof_json: &str, signature_json: Option<String>) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        let signature_json = signature_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[
+            ("proof_json", proof_json),
+            ("signature_json", signature_json.unwrap_or_default()),
+        ])?;
+        check_present(&[("proof_json", proof_json)])?;
+        
+        let proof: SignerInclusionProof = serde_json::from_str(proof_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize inclusion proof: {}", e)))?;
+        if proof.format != INCLUSION_PROOF_FORMAT {
+            return Err(FrostError::MalformedInput(format!("Unknown inclusion proof format {}", proof.format)));
+        }
+        
+        let identifier = identifier_from_hex(&proof.identifier)?;
+        let message = hex_to_bytes(&proof.message, "message")?;
+        let commitments_json = serde_json::to_string(&proof.commitments)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize commitments: {}", e)))?;
+        let signing_package = build_signing_package_from_commitments(&message, &commitments_json)?;
+        if !signing_package.signing_commitments().contains_key(&identifier) {
+            return Ok(false);
+        }
+        
+        let share_valid = frost_core::verify_signature_share(
+            identifier,
+            &proof.verifying_share,
+            &proof.signature_share,
+            &signing_package,
+            &proof.group_public_key,
+        ).is_ok();
+        
+        let Some(signature_json) = signature_json else {
+            return Ok(share_valid);
+        };
+        let signature: frost::Signature = serde_json::from_str(signature_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signature: {}", e)))?;
+        let signature_bytes = signature.serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid signature: {}", e)))?;
+        let group_commitment = group_commitment(&signing_package, &proof.group_public_key)?;
+        
+        Ok(share_valid && signature_bytes[..33] == *group_commitment.to_affine().to_encoded_point(true).as_bytes())
+    })();
+    
+    match result {
+        Ok(valid) => {
+            serde_json::to_string(&FrostResult::ok(valid)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+// Separates approval digests from other hashes of the same signing package
+#[cfg(feature = "full")]
+const APPROVAL_DOMAIN: &[u8] = b"frost-wasm-core/signing-approval/v1";
+
+// What a human approver is shown before a signing ceremony proceeds
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct ApprovalSummary {
+    pub message: String,
+    pub event: Option<nostr::NostrEvent>,
+    pub signers: Vec<String>,
+    pub digest: String,
+}
+
+/// Summarize a signing package for a human approver
+///
+/// Returns the message (hex), the identifiers of the signers, and a SHA-256
+/// digest of the canonical signing package, so the package later signed can be
+/// checked against the one approved. If the message is a Nostr event JSON it is
+/// decoded; if it is an event id, pass the event as `event_json` and its kind
+/// and content are included once its id is confirmed to equal the message.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn signing_approval_summary(signing_package_json: &str, event_json: Option<String>) -> String {
+    let result = (|| -> Result<ApprovalSummary, FrostError> {
+        check_input_sizes(&[
+            ("signing_package_json", signing_package_json),
+            ("event_json", event_json.as_deref().unwrap_or_default()),
+        ])?;
+        check_present(&[("signing_package_json", signing_package_json)])?;
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signing package: {}", e)))?;
+        let message: &[u8] = signing_package.message();
+        
+        let event = match event_json.as_deref() {
+            Some(event_json) => {
+                let mut event: nostr::NostrEvent = serde_json::from_str(event_json)
+                    .map_err(|e| FrostError::MalformedInput(format!("Failed to parse event: {}", e)))?;
+                let id = nostr::event_id(&event)?;
+                if message != id.as_slice() {
+                    return Err(FrostError::IntegrityError(
+                        "Event id does not match the message being signed".to_string()
+                    ));
+                }
+                event.id = hex::encode(id);
+                Some(event)
+            }
+            None => match serde_json::from_slice::<nostr::NostrEvent>(message) {
+                Ok(mut event) => {
+                    event.id = hex::encode(nostr::event_id(&event)?);
+                    Some(event)
+                }
+                Err(_) => None,
+            },
+        };
+        
+        let signers = signing_package.signing_commitments()
+            .keys()
+            .map(identifier_to_hex)
+            .collect();
+        
+        let digest = Sha256::new()
+            .chain_update(APPROVAL_DOMAIN)
+            .chain_update(canonical_json(&signing_package)?.as_bytes())
+            .finalize();
+        
+        Ok(ApprovalSummary {
+            message: hex::encode(message),
+            event,
+            signers,
+            digest: hex::encode(digest),
+        })
+    })();
+    
+    match result {
+        Ok(summary) => {
+            serde_json::to_string(&FrostResult::ok(summary)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<ApprovalSummary>::err(e)).unwrap()
+        }
+    }
+}
+
+// What a coordinator sends one signer to ask for its signature share. The
+// signing package holds only the message and public commitments, so the
+// request carries no secrets, the signer's own or anyone else's.
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SigningRequest {
+    pub participant_id: String,
+    pub message: String,
+    pub signing_package: frost::SigningPackage,
+}
+
+// A signer's answer to a SigningRequest, ready for `add_signature_share`
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SigningResponse {
+    pub participant_id: String,
+    pub identifier: String,
+    pub share: round2::SignatureShare<Secp256K1Sha256>,
+}
+
+/// Bundle a signing package into a request for one signer
+///
+/// `participant_id` is the label the signer committed under in its own state;
+/// the signer answers with `fulfill_signing_request`. The message is repeated
+/// in hex so the signer can show it without decoding the package.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn build_signing_request(participant_id: &str, signing_package_json: &str) -> String {
+    let result = (|| -> Result<SigningRequest, FrostError> {
+        check_input_sizes(&[
+            ("participant_id", participant_id),
+            ("signing_package_json", signing_package_json),
+        ])?;
+        check_present(&[("signing_package_json", signing_package_json)])?;
+        
+        let signing_package: frost::SigningPackage = serde_json::from_str(signing_package_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing package: {}", e)))?;
+        
+        Ok(SigningRequest {
+            participant_id: participant_id.to_string(),
+            message: hex::encode(signing_package.message()),
+            signing_package,
+        })
+    })();
+    
+    match result {
+        Ok(request) => {
+            serde_json::to_string(&FrostResult::ok(request)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SigningRequest>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Answer a signing request with this signer's signature share
+///
+/// `state_json` is the signer's own signing state from round 1 and
+/// `key_package_json` its key package, which never leaves the signer. Returns
+/// the updated state and a response whose `identifier` and `share` the
+/// coordinator passes to `add_signature_share`. No aggregation happens here.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn fulfill_signing_request(state_json: &str, request_json: &str, key_package_json: &str) -> String {
+    let mut timings = Timings::new();
+    let result = (|| -> Result<(SigningState, SigningResponse), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("request_json", request_json),
+            ("key_package_json", key_package_json),
+        ])?;
+        check_present(&[("key_package_json", key_package_json)])?;
+        
+        let mut state = parse_signing_state(state_json)?;
+        ensure_not_expired(state.expires_at)?;
+        
+        let request: SigningRequest = serde_json::from_str(request_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signing request: {}", e)))?;
+        
+        if request.message != hex::encode(request.signing_package.message()) {
+            return Err(FrostError::MalformedInput(
+                "Signing request message does not match its signing package".to_string()
+            ));
+        }
+        
+        let key_package = SignerKeyPackage::checkout(key_package_json)?;
+        let share = sign_share(&mut state, &request.participant_id, &key_package, &request.signing_package, &mut timings)?;
+        let identifier = identifier_to_hex(key_package.identifier());
+        key_package.release();
+        
+        Ok((state, SigningResponse {
+            participant_id: request.participant_id,
+            identifier,
+            share,
+        }))
+    })();
+    
+    match result {
+        Ok((state, response)) => {
+            serde_json::to_string(&FrostResult::ok((state, response)).with_timings(timings)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(SigningState, SigningResponse)>::err(e).with_timings(timings)).unwrap()
+        }
+    }
+}
+
+/// Drop a signer that committed in round 1 but never produced a signature share
+///
+/// The remaining signers must still meet the threshold. If no shares have been
+/// produced yet, the signing package is rebuilt from the remaining commitments
+/// and returned. Shares already produced were computed over the old signing
+/// package and spent their nonces, so those signers are sent back to round 1
+/// to commit again and no signing package is returned.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn drop_signer(state_json: &str, participant_id: &str) -> String {
+    let result = (|| -> Result<(SigningState, Option<String>), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+        ])?;
+        
+        let mut state = parse_signing_state(state_json)?;
+        
+        if state.final_signature.is_some() {
+            return Err(FrostError::InvalidStateTransition(
+                "Signing ceremony is already complete".to_string()
+            ));
+        }
+        
+        if !state.signers.iter().any(|signer| signer == participant_id) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Participant {} is not a signer", participant_id)
+            ));
+        }
+        
+        if state.signature_shares.contains_key(participant_id) {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Participant {} already submitted a signature share", participant_id)
+            ));
+        }
+        
+        // Enough signers must remain to reach the threshold
+        let required = recorded_threshold(&state)?;
+        let remaining = state.signers.len() - 1;
+        if remaining < required as usize {
+            return Err(FrostError::InsufficientParticipants {
+                required,
+                actual: participant_count(remaining)?,
+            });
+        }
+        
+        state.signers.retain(|signer| signer != participant_id);
+        state.signer_identifiers.remove(participant_id);
+        if let Some(mut round1_data) = state.round1_packages.remove(participant_id) {
+            round1_data.zeroize();
+        }
+        
+        // Shares over the old signing package are unusable and their nonces are spent
+        let spent_signers: Vec<String> = state.signature_shares.keys().cloned().collect();
+        for signer in &spent_signers {
+            if let Some(mut round1_data) = state.round1_packages.remove(signer) {
+                round1_data.zeroize();
+            }
+        }
+        state.signature_shares.clear();
+        state.validated_identifiers.clear();
+        
+        if state.round1_packages.len() < state.signers.len() {
+            state.current_round = 1;
+            return Ok((state, None));
+        }
+        
+        // Every remaining signer still holds unused nonces, so rebuild the package
+        state.current_round = 2;
+        let signing_package = build_signing_package_from_state(&state)?;
+        let signing_package_serialized = serde_json::to_string(&signing_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signing package: {}", e)))?;
+        
+        Ok((state, Some(signing_package_serialized)))
+    })();
+    
+    match result {
+        Ok((state, signing_package)) => {
+            serde_json::to_string(&FrostResult::ok((state, signing_package))).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(SigningState, Option<String>)>::err(e)).unwrap()
+        }
+    }
+}
+
+// Signing nonces handed from the process that generated them to another one.
+// `nonce_id` is a random one-time marker: a state records every marker it has
+// imported and refuses to import the same nonces twice.
+#[derive(Serialize, Deserialize)]
+pub struct ExportedNonces {
+    pub ceremony_id: String,
+    pub participant_id: String,
+    pub nonce_id: String,
+    pub round1_data: String,
+}
+
+/// Move a signer's unused round 1 nonces out of the state for a hand-off
+///
+/// The nonces are removed from the returned state so this copy can no longer
+/// sign with them, and the ceremony goes back to round 1 until they are
+/// imported again with `import_signing_nonces`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn export_signing_nonces(state_json: &str, participant_id: &str) -> String {
+    let result = (|| -> Result<(SigningState, String), FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("participant_id", participant_id),
+        ])?;
+        
+        let mut state = parse_signing_state(state_json)?;
+        
+        let ceremony_id = state.ceremony_id.clone().ok_or(FrostError::InvalidStateTransition(
+            "Nonces can only be exported from a state with a ceremony id".to_string()
+        ))?;
+        
+        if state.signature_shares.contains_key(participant_id) {
+            return Err(FrostError::InvalidStateTransition(
+                format!("Nonces for {} were already consumed by a signature share", participant_id)
+            ));
+        }
+        
+        let round1_data = state.round1_packages.remove(participant_id)
+            .ok_or(FrostError::InvalidParticipant(format!("Participant {} not found in round 1", participant_id)))?;
+        
+        let mut nonce_id = [0u8; 16];
+        secure_rng()?.fill_bytes(&mut nonce_id);
+        
+        let exported = ExportedNonces {
+            ceremony_id,
+            participant_id: participant_id.to_string(),
+            nonce_id: hex::encode(nonce_id),
+            round1_data,
+        };
+        let exported_serialized = serde_json::to_string(&exported)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize nonces: {}", e)))?;
+        
+        state.current_round = 1;
+        
+        Ok((state, exported_serialized))
+    })();
+    
+    match result {
+        Ok((state, exported)) => {
+            serde_json::to_string(&FrostResult::ok((state, exported))).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(SigningState, String)>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Restore nonces produced by `export_signing_nonces` into a signing state
+///
+/// Fails if the nonces belong to another ceremony, if this state has already
+/// imported them, or if the signer already holds nonces in this state.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn import_signing_nonces(state_json: &str, exported_json: &str) -> String {
+    let result = (|| -> Result<SigningState, FrostError> {
+        check_input_sizes(&[
+            ("state_json", state_json),
+            ("exported_json", exported_json),
+        ])?;
+        
+        let mut state = parse_signing_state(state_json)?;
+        
+        let mut exported: ExportedNonces = serde_json::from_str(exported_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize nonces: {}", e)))?;
+        
+        // Don't leave the nonces lying in memory when they are refused
+        if let Err(e) = check_nonce_import(&state, &exported) {
+            exported.round1_data.zeroize();
+            return Err(e);
+        }
+        
+        state.imported_nonce_ids.insert(exported.nonce_id);
+        state.round1_packages.insert(exported.participant_id, exported.round1_data);
+        
+        if state.round1_packages.len() >= state.signers.len() {
+            state.current_round = 2;
+        }
+        
+        Ok(state)
+    })();
+    
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SigningState>::err(e)).unwrap()
+        }
+    }
+}
+
+// Separates replay cache entries from other hashes of the same message
+#[cfg(feature = "full")]
+const SIGNED_MESSAGE_DOMAIN: &[u8] = b"frost-wasm-core/signed-message/v1";
+
+// Hashes of the messages one group has signed, persisted by the caller
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SignedMessageCache {
+    pub group_id: String,
+    pub message_hashes: BTreeSet<String>,
+}
+
+// Outcome of a replay check, with the cache to persist afterwards
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct MessageCheck {
+    pub already_signed: bool,
+    pub cache: SignedMessageCache,
+}
+
+/// Check whether `group_id` has already signed `message`, and record it if not
+///
+/// The WASM module keeps nothing between calls, so the cache of message
+/// hashes is passed in as `cache_json` (omit it for a group's first message)
+/// and the updated cache is returned. Persist it only once the signature is
+/// actually produced. This is advisory: it only sees messages whose cache the
+/// caller supplies, and concurrent callers racing on one cache can both pass.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn check_message_not_signed(group_id: &str, message: &[u8], cache_json: Option<String>) -> String {
+    let result = (|| -> Result<MessageCheck, FrostError> {
+        check_input_sizes(&[
+            ("group_id", group_id),
+            ("cache_json", cache_json.as_deref().unwrap_or_default()),
+        ])?;
+        check_message_size(message)?;
+        
+        let mut cache = match cache_json.as_deref() {
+            Some(cache_json) => serde_json::from_str::<SignedMessageCache>(cache_json)
+                .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signed message cache: {}", e)))?,
+            None => SignedMessageCache {
+                group_id: group_id.to_string(),
+                message_hashes: BTreeSet::new(),
+            },
+        };
+        
+        if cache.group_id != group_id {
+            return Err(FrostError::MalformedInput(
+                format!("Signed message cache belongs to group {}, not {}", cache.group_id, group_id)
+            ));
+        }
+        
+        let message_hash = Sha256::new()
+            .chain_update(SIGNED_MESSAGE_DOMAIN)
+            .chain_update((group_id.len() as u64).to_be_bytes())
+            .chain_update(group_id.as_bytes())
+            .chain_update(message)
+            .finalize();
+        let already_signed = !cache.message_hashes.insert(hex::encode(message_hash));
+        
+        Ok(MessageCheck { already_signed, cache })
+    })();
+    
+    match result {
+        Ok(check) => {
+            serde_json::to_string(&FrostResult::ok(check)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<MessageCheck>::err(e)).unwrap()
+        }
+    }
+}
+
+// Who may sign an operation. `required` signers must all take part; when
+// `optional` is given, every other signer must be listed there, and when it
+// is omitted anyone else may join. At least `min_signers` must take part.
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct SignerPolicy {
+    #[serde(default)]
+    pub required: BTreeSet<String>,
+    #[serde(default)]
+    pub optional: Option<BTreeSet<String>>,
+    pub min_signers: u16,
+}
+
+// A policy requirement the signer set does not meet
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "requirement", rename_all = "snake_case")]
+pub enum PolicyFailure {
+    RequiredSigner { signer: String },
+    AllowedSigners { signer: String },
+    MinSigners { required: u16, actual: u16 },
+}
+
+// Whether a signer set satisfies a policy, and every requirement it misses
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct PolicyCheck {
+    pub satisfied: bool,
+    pub failures: Vec<PolicyFailure>,
+}
+
+/// Evaluate a signer policy against the signers of an operation
+///
+/// `signers_json` is a JSON array of the participant labels that will sign
+/// (repeats count once) and `policy_json` a `{ required, optional,
+/// min_signers }` policy. An unmet policy is reported in the result, with
+/// each failed requirement listed, rather than as an error.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn check_signer_policy(signers_json: &str, policy_json: &str) -> String {
+    let result = (|| -> Result<PolicyCheck, FrostError> {
+        check_input_sizes(&[
+            ("signers_json", signers_json),
+            ("policy_json", policy_json),
+        ])?;
+        
+        let signers: BTreeSet<String> = serde_json::from_str(signers_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signers: {}", e)))?;
+        check_participant_count("signers", signers.len())?;
+        
+        let policy: SignerPolicy = serde_json::from_str(policy_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse signer policy: {}", e)))?;
+        
+        let mut failures: Vec<PolicyFailure> = policy.required.difference(&signers)
+            .map(|signer| PolicyFailure::RequiredSigner { signer: signer.clone() })
+            .collect();
+        
+        if let Some(optional) = &policy.optional {
+            failures.extend(signers.iter()
+                .filter(|signer| !policy.required.contains(*signer) && !optional.contains(*signer))
+                .map(|signer| PolicyFailure::AllowedSigners { signer: signer.clone() }));
+        }
+        
+        let actual = participant_count(signers.len())?;
+        if actual < policy.min_signers {
+            failures.push(PolicyFailure::MinSigners { required: policy.min_signers, actual });
+        }
+        
+        Ok(PolicyCheck {
+            satisfied: failures.is_empty(),
+            failures,
+        })
+    })();
+    
+    match result {
+        Ok(check) => {
+            serde_json::to_string(&FrostResult::ok(check)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<PolicyCheck>::err(e)).unwrap()
+        }
+    }
+}
+
+// === UTILITY FUNCTIONS ===
+
+/// Generate FROST key shares from a private key (Trusted Dealer mode)
+///
+/// `labels_json` is an optional JSON array of one unique label per
+/// participant; the share for identifier `i` is keyed by the `i`th label.
+/// Without it, shares are keyed `participant_1..n` in identifier order.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn generate_frost_shares(
+    private_key_hex: &str,
+    threshold: u16,
+    max_participants: u16,
+    labels_json: Option<String>
+) -> String {
+    // For now, we'll use the default trusted dealer which generates its own secret
+    // In future, we could use the provided private_key_hex but that requires additional implementation
+    let _ = private_key_hex; // Acknowledge the parameter
+    
+    let result = (|| -> Result<(String, BTreeMap<String, String>), FrostError> {
+        let labels_json = labels_json.as_deref().filter(|json| !json.is_empty());
+        check_input_sizes(&[("labels_json", labels_json.unwrap_or_default())])?;
+        
+        let labels: Option<Vec<String>> = labels_json
+            .map(|json| serde_json::from_str(json)
+                .map_err(|e| FrostError::MalformedInput(format!("Failed to parse labels: {}", e))))
+            .transpose()?;
+        
+        let mut rng = secure_rng()?;
+        let output = deal_shares(threshold, max_participants, labels.as_deref(), &mut rng)?;
+        Ok((output.group_public_key, output.shares))
+    })();
+    
+    match result {
+        Ok((pubkey, shares)) => {
+            serde_json::to_string(&FrostResult::ok((pubkey, shares))).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<(String, BTreeMap<String, String>)>::err(e)).unwrap()
+        }
+    }
+}
+
+// Separates deterministic split seeds from every other use of the same key
+#[cfg(feature = "full")]
+const DETERMINISTIC_SPLIT_DOMAIN: &[u8] = b"frost-wasm-core/deterministic-split/v1";
+
+// A ChaCha20 keystream as the dealer's randomness, so a split is a pure
+// function of the seed
+#[cfg(feature = "full")]
+struct KeystreamRng(ChaCha20);
+
+#[cfg(feature = "full")]
+impl RngCore for KeystreamRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+    
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+    
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.fill(0);
+        self.0.apply_keystream(dest);
+    }
+    
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), frost_secp256k1::rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "full")]
+impl CryptoRng for KeystreamRng {}
+
+/// Split an existing private key into shares reproducibly (Trusted Dealer mode)
+///
+/// The polynomial coefficients come from HKDF-SHA256 over the key, keyed to
+/// `domain_sep` and the threshold parameters, instead of fresh randomness, so
+/// the same key, parameters, and `domain_sep` always give the same shares.
+/// That makes a migration repeatable and checkable, at a cost: anyone who
+/// learns the private key can recompute every share, and splitting the same
+/// key twice with one `domain_sep` hands out the same shares again. Use a
+/// distinct `domain_sep` per split, and prefer `generate_frost_shares` when
+/// reproducibility isn't needed. Shares are keyed `participant_1..n`. A key
+/// with odd y is negated before splitting, as for every dealer split, which
+/// keeps its x-only (Nostr) pubkey.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn split_deterministic(
+    private_key_hex: &str,
+    threshold: u16,
+    max_participants: u16,
+    domain_sep: &str
+) -> String {
+    let result = (|| -> Result<DealerOutput, FrostError> {
+        check_input_sizes(&[
+            ("private_key_hex", private_key_hex),
+            ("domain_sep", domain_sep),
+        ])?;
+        if domain_sep.is_empty() {
+            return Err(FrostError::MalformedInput("domain_sep is empty".to_string()));
+        }
+        
+        let private_key = Zeroizing::new(nostr::decode_fixed::<32>(private_key_hex, "private key")?);
+        let signing_key = frost::SigningKey::deserialize(private_key.as_slice())
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid private key: {}", e)))?;
+        
+        let info = [
+            &(domain_sep.len() as u64).to_be_bytes()[..],
+            domain_sep.as_bytes(),
+            &threshold.to_be_bytes(),
+            &max_participants.to_be_bytes(),
+        ].concat();
+        let mut seed = Zeroizing::new([0u8; 32]);
+        Hkdf::<Sha256>::new(Some(DETERMINISTIC_SPLIT_DOMAIN), private_key.as_slice())
+            .expand(&info, seed.as_mut_slice())
+            .map_err(|_| FrostError::KeygenError("Failed to derive the split seed".to_string()))?;
+        
+        let mut rng = KeystreamRng(ChaCha20::new(seed.as_slice().into(), (&[0u8; 12]).into()));
+        split_shares(threshold, max_participants, None, Some(&signing_key), &mut rng)
+    })();
+    
+    match result {
+        Ok(output) => {
+            serde_json::to_string(&FrostResult::ok(output)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<DealerOutput>::err(e)).unwrap()
+        }
+    }
+}
+
+// Tags the share envelope layout, so a later layout is told apart from this one
+#[cfg(feature = "full")]
+const SHARE_ENVELOPE_FORMAT: &str = "frost-share/v1";
+
+// Separates share checksums from every other hash of the same share
+#[cfg(feature = "full")]
+const SHARE_CHECKSUM_DOMAIN: &[u8] = b"frost-wasm-core/share-checksum/v1";
+
+// A key package or dealer share as stored, with a checksum over its exact
+// JSON so storage corruption is caught on load
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+struct ShareEnvelope {
+    format: String,
+    share: String,
+    checksum: String,
+}
+
+/// Wrap a key package or dealer share in an envelope with a checksum
+///
+/// The checksum is the first 8 bytes of SHA-256 over a domain tag and the
+/// share JSON, in hex. Every function that takes a key package accepts the
+/// envelope in its place and checks the checksum first, failing with
+/// `CorruptedShare` when stored bytes have changed. The checksum only catches
+/// accidental corruption; it is not a MAC and doesn't stop deliberate edits.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn checksum_share(share_json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("share_json", share_json)])?;
+        check_present(&[("share_json", share_json)])?;
+        
+        // Only a loadable share gets a checksum
+        parse_unwrapped_key_package(share_json)?;
+        
+        serde_json::to_string(&ShareEnvelope {
+            format: SHARE_ENVELOPE_FORMAT.to_string(),
+            share: share_json.to_string(),
+            checksum: share_checksum(share_json),
+        }).map_err(|e| FrostError::SerializationError(format!("Failed to serialize share envelope: {}", e)))
+    })();
+    
+    match result {
+        Ok(envelope) => {
+            serde_json::to_string(&FrostResult::ok(envelope)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+fn share_checksum(share_json: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(SHARE_CHECKSUM_DOMAIN)
+        .chain_update(share_json.as_bytes())
+        .finalize();
+    hex::encode(&digest[..8])
+}
+
+// Leads the bytes a share mnemonic encodes, so the layout can change later
+#[cfg(feature = "full")]
+const SHARE_MNEMONIC_VERSION: u8 = 1;
+
+// version || identifier || signing share || group verifying key || threshold
+#[cfg(feature = "full")]
+const SHARE_MNEMONIC_BYTES: usize = 1 + 32 + 32 + 33 + 2;
+
+/// Encode a key package as a checksummed mnemonic for a human-held backup
+///
+/// The words carry a version byte, the identifier, the signing share, the
+/// group verifying key, and the threshold: 100 bytes, encoded as in BIP39.
+/// The first bit of SHA-256 over the bytes is appended per 32 bits of data,
+/// and each 11 bits choose a word from the BIP39 English list, giving 75
+/// words. The verifying share is left out, since `share_from_mnemonic`
+/// derives it from the signing share. The mnemonic is the secret share and
+/// must be kept like one.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn share_to_mnemonic(key_package_json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("key_package_json", key_package_json)])?;
+        check_present(&[("key_package_json", key_package_json)])?;
+        
+        let key_package = parse_key_package(key_package_json)?;
+        let verifying_key = key_package.verifying_key().serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid group verifying key: {}", e)))?;
+        
+        let mut bytes = Zeroizing::new(Vec::with_capacity(SHARE_MNEMONIC_BYTES));
+        bytes.push(SHARE_MNEMONIC_VERSION);
+        bytes.extend_from_slice(&key_package.identifier().serialize());
+        bytes.extend_from_slice(&key_package.signing_share().serialize());
+        bytes.extend_from_slice(&verifying_key);
+        bytes.extend_from_slice(&key_package.min_signers().to_be_bytes());
+        
+        Ok(mnemonic_from_bytes(&bytes))
+    })();
+    
+    match result {
+        Ok(mnemonic) => {
+            serde_json::to_string(&FrostResult::ok(mnemonic)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Restore a key package from a mnemonic made by `share_to_mnemonic`
+///
+/// Words are separated by whitespace and matched case-insensitively. A word
+/// outside the BIP39 English list or a wrong word count is `MalformedInput`;
+/// a mistyped or swapped word that breaks the checksum is `CorruptedShare`.
+/// The mnemonic doesn't carry the group size, so a threshold of 0 or above
+/// the participant limit set by `set_input_limits` is `MalformedInput`.
+/// Returns the key package JSON, with the verifying share recomputed.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn share_from_mnemonic(words: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("words", words)])?;
+        check_present(&[("words", words)])?;
+        
+        let bytes = mnemonic_to_bytes(words, SHARE_MNEMONIC_BYTES)?;
+        if bytes[0] != SHARE_MNEMONIC_VERSION {
+            return Err(FrostError::MalformedInput(format!("Unsupported share mnemonic version {}", bytes[0])));
+        }
+        
+        let identifier = FrostIdentifier::deserialize(&bytes[1..33])
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid identifier in mnemonic: {}", e)))?;
+        let signing_share = frost::keys::SigningShare::deserialize(&bytes[33..65])
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid signing share in mnemonic: {}", e)))?;
+        let verifying_key = frost::VerifyingKey::deserialize(&bytes[65..98])
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid group verifying key in mnemonic: {}", e)))?;
+        let min_signers = u16::from_be_bytes([bytes[98], bytes[99]]);
+        let max_participants = MAX_PARTICIPANTS.load(Ordering::Relaxed);
+        if min_signers == 0 || min_signers > max_participants {
+            return Err(FrostError::MalformedInput(format!(
+                "Invalid threshold {} in mnemonic, expected 1 to {}", min_signers, max_participants
+            )));
+        }
+        
+        let verifying_share = frost::keys::VerifyingShare::from(signing_share);
+        let key_package = KeyPackage::new(identifier, signing_share, verifying_share, verifying_key, min_signers);
+        serde_json::to_string(&key_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))
+    })();
+    
+    match result {
+        Ok(key_package) => {
+            serde_json::to_string(&FrostResult::ok(key_package)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// BIP39 encoding of bytes whose length is a multiple of 4: the data, then
+// one checksum bit per 32 data bits from SHA-256, read 11 bits per word
+#[cfg(feature = "full")]
+fn mnemonic_from_bytes(data: &[u8]) -> String {
+    let checksum_bits = data.len() * 8 / 32;
+    let mut bits = Zeroizing::new(data.to_vec());
+    bits.extend_from_slice(&Sha256::digest(data)[..checksum_bits.div_ceil(8)]);
+    
+    let word_list = bip39::Language::English.word_list();
+    let bit = |i: usize| (bits[i / 8] >> (7 - i % 8)) as usize & 1;
+    (0..(data.len() * 8 + checksum_bits) / 11)
+        .map(|word| word_list[(0..11).fold(0, |index, offset| index << 1 | bit(word * 11 + offset))])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Decode a mnemonic from mnemonic_from_bytes back into its data bytes,
+// checking the word count, each word, and the checksum
+#[cfg(feature = "full")]
+fn mnemonic_to_bytes(words: &str, data_len: usize) -> Result<Zeroizing<Vec<u8>>, FrostError> {
+    let checksum_bits = data_len * 8 / 32;
+    let expected_words = (data_len * 8 + checksum_bits) / 11;
+    let words: Vec<&str> = words.split_whitespace().collect();
+    if words.len() != expected_words {
+        return Err(FrostError::MalformedInput(
+            format!("Expected {} words, got {}", expected_words, words.len())
+        ));
+    }
+    
+    let mut bits = Zeroizing::new(vec![0u8; data_len + checksum_bits.div_ceil(8)]);
+    for (position, word) in words.iter().enumerate() {
+        let index = bip39::Language::English.find_word(&word.to_lowercase()).ok_or(FrostError::MalformedInput(
+            format!("Word {} is not in the BIP39 English word list", position + 1)
+        ))? as usize;
+        for offset in 0..11 {
+            if index >> (10 - offset) & 1 == 1 {
+                let i = position * 11 + offset;
+                bits[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+    }
+    
+    let digest = Sha256::digest(&bits[..data_len]);
+    let checksum = &bits[data_len..];
+    if (0..checksum_bits).any(|i| (digest[i / 8] ^ checksum[i / 8]) & (0x80 >> (i % 8)) != 0) {
+        return Err(FrostError::CorruptedShare("Mnemonic checksum does not match; a word is wrong".to_string()));
+    }
+    
+    bits.truncate(data_len);
+    Ok(bits)
+}
+
+// Parameters for one group in a batch trusted dealer run
+#[derive(Serialize, Deserialize)]
+pub struct DealerSpec {
+    pub threshold: u16,
+    pub max_participants: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+}
+
+// Group public key and serialized shares produced by the trusted dealer
+#[derive(Serialize, Deserialize)]
+pub struct DealerOutput {
+    pub group_public_key: String,
+    pub shares: BTreeMap<String, String>,
+    pub identifiers: BTreeMap<String, String>,
+}
+
+/// Generate key shares for many independent groups in one call (Trusted Dealer mode)
+///
+/// Takes a JSON array of `{threshold, max_participants, labels?}` and returns
+/// an array of `{group_public_key, shares}` in the same order, with shares
+/// keyed as in `generate_frost_shares`. One seeded CSPRNG is
+/// shared by every group instead of seeding a new one per call.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn generate_frost_shares_batch(specs_json: &str) -> String {
+    let result = (|| -> Result<Vec<DealerOutput>, FrostError> {
+        check_input_sizes(&[("specs_json", specs_json)])?;
+        
+        let specs: Vec<DealerSpec> = serde_json::from_str(specs_json)
+            .map_err(|e| FrostError::MalformedInput(e.to_string()))?;
+        
+        let mut rng = secure_rng()?;
+        
+        specs.iter()
+            .map(|spec| deal_shares(spec.threshold, spec.max_participants, spec.labels.as_deref(), &mut rng))
+            .collect()
+    })();
+    
+    match result {
+        Ok(outputs) => {
+            serde_json::to_string(&FrostResult::ok(outputs)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<Vec<DealerOutput>>::err(e)).unwrap()
+        }
+    }
+}
+
+// Key packages relabeled in identifier order, with the group package trimmed
+// to the same participants
+#[derive(Serialize, Deserialize)]
+pub struct NormalizedGroup {
+    pub group_public_key: String,
+    pub key_packages: BTreeMap<String, String>,
+    pub identifiers: BTreeMap<String, String>,
+}
+
+/// Relabel a group's key packages as `participant_1..n` in identifier order
+///
+/// Takes the group public key package and a JSON map of label to key package
+/// (or trusted dealer share). Every package must belong to the group and
+/// agree with the group's verifying share for its identifier. Verifying
+/// shares without a key package are dropped from the group package.
+///
+/// The FROST identifiers themselves are the points the shares were evaluated
+/// at, so they can't be renumbered without a reshare. Only the labels change;
+/// the group verifying key and every share stay as they were.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn normalize_identifiers(group_public_key_json: &str, key_packages_json: &str) -> String {
+    let result = (|| -> Result<NormalizedGroup, FrostError> {
+        check_input_sizes(&[
+            ("group_public_key_json", group_public_key_json),
+            ("key_packages_json", key_packages_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        let labeled_packages: BTreeMap<String, String> = serde_json::from_str(key_packages_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse key packages: {}", e)))?;
+        
+        let mut key_packages = BTreeMap::new();
+        for (label, key_package_json) in &labeled_packages {
+            let key_package = parse_key_package(key_package_json)?;
+            
+            if key_package.verifying_key() != group_public_key.verifying_key() {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Key package for {} belongs to another group", label)
+                ));
+            }
+            
+            if group_public_key.verifying_shares().get(key_package.identifier()) != Some(key_package.verifying_share()) {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Key package for {} doesn't match the group's verifying share", label)
+                ));
+            }
+            
+            if key_packages.insert(*key_package.identifier(), key_package).is_some() {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Key package for {} repeats another participant's identifier", label)
+                ));
+            }
+        }
+        
+        let verifying_shares = key_packages.iter()
+            .map(|(identifier, key_package)| (*identifier, *key_package.verifying_share()))
+            .collect();
+        let normalized_group_key = PublicKeyPackage::new(verifying_shares, *group_public_key.verifying_key());
+        
+        let mut normalized = NormalizedGroup {
+            group_public_key: serde_json::to_string(&normalized_group_key)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?,
+            key_packages: BTreeMap::new(),
+            identifiers: BTreeMap::new(),
+        };
+        
+        // BTreeMap iterates identifiers in ascending order
+        for (index, (identifier, key_package)) in key_packages.iter().enumerate() {
+            let label = format!("participant_{}", index + 1);
+            let key_package_serialized = serde_json::to_string(key_package)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))?;
+            
+            normalized.key_packages.insert(label.clone(), key_package_serialized);
+            normalized.identifiers.insert(label, identifier_to_hex(identifier));
+        }
+        
+        Ok(normalized)
+    })();
+    
+    match result {
+        Ok(normalized) => {
+            serde_json::to_string(&FrostResult::ok(normalized)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<NormalizedGroup>::err(e)).unwrap()
+        }
+    }
+}
+
+// The threshold subsets of share labels that interpolated to the group key
+#[derive(Serialize, Deserialize)]
+pub struct ShareSetValidation {
+    pub subsets_checked: Vec<Vec<String>>,
+}
+
+/// Check that threshold subsets of a share set reconstruct the group key
+///
+/// `shares_json` maps labels to trusted dealer shares or key packages. Every
+/// share must carry the group's verifying key and verifying share, be issued
+/// for `threshold`, and have a signing share that produces its verifying
+/// share. The shares, in label order, are then split into runs of `threshold`
+/// (the last run wrapping around to the start) so every share is in at least
+/// one subset, and each subset's verifying shares are Lagrange-interpolated
+/// at zero, which must give the group verifying key. Fails with
+/// `IntegrityError` naming the first share or subset that doesn't.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn validate_share_set(shares_json: &str, group_public_key_json: &str, threshold: u16) -> String {
+    let result = (|| -> Result<ShareSetValidation, FrostError> {
+        check_input_sizes(&[
+            ("shares_json", shares_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        let labeled_shares: BTreeMap<String, String> = serde_json::from_str(shares_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse shares: {}", e)))?;
+        
+        let mut shares = Vec::new();
+        let mut identifiers = BTreeSet::new();
+        for (label, share_json) in &labeled_shares {
+            let key_package = parse_key_package(share_json)?;
+            
+            if key_package.verifying_key() != group_public_key.verifying_key() {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Share {} belongs to another group", label)
+                ));
+            }
+            if *key_package.min_signers() != threshold {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Share {} was not issued for a threshold of {}", label, threshold)
+                ));
+            }
+            if group_public_key.verifying_shares().get(key_package.identifier()) != Some(key_package.verifying_share()) {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Share {} doesn't match the group's verifying share", label)
+                ));
+            }
+            if !identifiers.insert(*key_package.identifier()) {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Share {} repeats another share's identifier", label)
+                ));
+            }
+            if frost::keys::VerifyingShare::from(*key_package.signing_share()) != *key_package.verifying_share() {
+                return Err(FrostError::IntegrityError(
+                    format!("Share {}'s signing share doesn't produce its verifying share", label)
+                ));
+            }
+            
+            shares.push((label.clone(), key_package));
+        }
+        
+        ensure_threshold_met(threshold, shares.len())?;
+        let group_key = sec1_point(&group_public_key.verifying_key().serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid group verifying key: {}", e)))?)?;
+        
+        let mut validation = ShareSetValidation { subsets_checked: Vec::new() };
+        for start in (0..shares.len()).step_by(threshold as usize) {
+            let subset: Vec<&(String, KeyPackage)> = (start..start + threshold as usize)
+                .map(|index| &shares[index % shares.len()])
+                .collect();
+            let labels: Vec<String> = subset.iter().map(|(label, _)| label.clone()).collect();
+            
+            let key_packages: Vec<&KeyPackage> = subset.iter().map(|(_, key_package)| key_package).collect();
+            if interpolate_verifying_shares(&key_packages)? != group_key {
+                return Err(FrostError::IntegrityError(
+                    format!("Shares {} do not interpolate to the group key", labels.join(", "))
+                ));
+            }
+            
+            validation.subsets_checked.push(labels);
+        }
+        
+        Ok(validation)
+    })();
+    
+    match result {
+        Ok(validation) => {
+            serde_json::to_string(&FrostResult::ok(validation)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<ShareSetValidation>::err(e)).unwrap()
+        }
+    }
+}
+
+// Why a key package can't take part in a signing ceremony
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipationIssue {
+    // The key package carries another group's verifying key
+    OtherGroup,
+    // The group has no verifying share for the package's identifier
+    NotAMember,
+    // The group's verifying share for the identifier is not the package's
+    VerifyingShareMismatch,
+    // The package's signing share doesn't produce its own verifying share
+    InconsistentKeyPackage,
+    // The package was issued for a different threshold
+    ThresholdMismatch,
+    // The group has fewer members than the threshold
+    ThresholdUnreachable,
+}
+
+// The result of a pre-flight participation check; `reasons` is empty exactly
+// when both `member` and `can_reach_threshold` hold
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct ParticipationCheck {
+    pub identifier: String,
+    pub member: bool,
+    pub can_reach_threshold: bool,
+    pub reasons: Vec<ParticipationIssue>,
+}
+
+/// Check whether a stored key package can sign for a group at a threshold
+///
+/// A pre-flight check for scheduling signers: the package must belong to the
+/// group, match the group's verifying share for its identifier, and be
+/// internally consistent, and the group must be able to reach `threshold`
+/// with shares issued for it. Nothing is checked out or modified. A package
+/// that fails any check is reported with every reason that applies, and only
+/// malformed input is an error.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn can_participate(key_package_json: &str, group_public_key_json: &str, threshold: u16) -> String {
+    let result = (|| -> Result<ParticipationCheck, FrostError> {
+        check_input_sizes(&[
+            ("key_package_json", key_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[
+            ("key_package_json", key_package_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        
+        let key_package = parse_key_package(key_package_json)?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        let mut reasons = Vec::new();
+        if key_package.verifying_key() != group_public_key.verifying_key() {
+            reasons.push(ParticipationIssue::OtherGroup);
+        }
+        match group_public_key.verifying_shares().get(key_package.identifier()) {
+            None => reasons.push(ParticipationIssue::NotAMember),
+            Some(verifying_share) if verifying_share != key_package.verifying_share() => {
+                reasons.push(ParticipationIssue::VerifyingShareMismatch);
+            }
+            Some(_) => {}
+        }
+        if frost::keys::VerifyingShare::from(*key_package.signing_share()) != *key_package.verifying_share() {
+            reasons.push(ParticipationIssue::InconsistentKeyPackage);
+        }
+        let member = reasons.is_empty();
+        
+        if *key_package.min_signers() != threshold {
+            reasons.push(ParticipationIssue::ThresholdMismatch);
+        }
+        if threshold == 0 || group_public_key.verifying_shares().len() < threshold as usize {
+            reasons.push(ParticipationIssue::ThresholdUnreachable);
+        }
+        
+        Ok(ParticipationCheck {
+            identifier: identifier_to_hex(key_package.identifier()),
+            member,
+            can_reach_threshold: member && reasons.is_empty(),
+            reasons,
+        })
+    })();
+    
+    match result {
+        Ok(check) => {
+            serde_json::to_string(&FrostResult::ok(check)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<ParticipationCheck>::err(e)).unwrap()
+        }
+    }
+}
+
+// A discrepancy between a group's stored policy and its shares
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "finding", rename_all = "snake_case")]
+pub enum AuditFinding {
+    // An expected member has no key package
+    MissingMember { member: String },
+    // A key package is held for a label the policy doesn't expect
+    ExtraMember { member: String },
+    // The key package doesn't parse or failed its checksum
+    UnreadableShare { member: String, reason: String },
+    // The key package carries another group's verifying key
+    OtherGroup { member: String },
+    // The group's verifying share for the identifier is absent or differs
+    WrongVerifyingShare { member: String, identifier: String },
+    // The package's signing share doesn't produce its own verifying share
+    InconsistentKeyPackage { member: String },
+    // Two key packages have the same identifier
+    DuplicateIdentifier { member: String, identifier: String },
+    // The package was issued for another threshold than the policy's
+    ThresholdMismatch { member: String, threshold: u16 },
+    // The group has fewer verifying shares than the policy's threshold
+    ThresholdUnreachable { threshold: u16, group_size: usize },
+}
+
+// Every discrepancy found by audit_group; `healthy` when there are none
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct GroupAudit {
+    pub healthy: bool,
+    pub findings: Vec<AuditFinding>,
+}
+
+/// Audit a group's key packages against its stored threshold policy
+///
+/// `key_packages_json` maps member labels to the key packages held for the
+/// group and `expected_members_json` is the JSON array of labels the policy
+/// says should hold one. Each package is checked against the group's
+/// verifying key and verifying shares and against `expected_threshold`, and
+/// the member sets are compared. Every discrepancy is listed as a finding,
+/// including unreadable or corrupted shares; only malformed top-level input
+/// is an error.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn audit_group(
+    group_public_key_json: &str,
+    key_packages_json: &str,
+    expected_threshold: u16,
+    expected_members_json: &str
+) -> String {
+    let result = (|| -> Result<GroupAudit, FrostError> {
+        check_input_sizes(&[
+            ("group_public_key_json", group_public_key_json),
+            ("key_packages_json", key_packages_json),
+            ("expected_members_json", expected_members_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        let key_packages: BTreeMap<String, String> = serde_json::from_str(key_packages_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse key packages: {}", e)))?;
+        let expected_members: BTreeSet<String> = serde_json::from_str(expected_members_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse expected members: {}", e)))?;
+        check_participant_count("key_packages_json", key_packages.len())?;
+        check_participant_count("expected_members_json", expected_members.len())?;
+        
+        let mut findings: Vec<AuditFinding> = expected_members.iter()
+            .filter(|member| !key_packages.contains_key(*member))
+            .map(|member| AuditFinding::MissingMember { member: member.clone() })
+            .collect();
+        
+        let mut identifiers = BTreeSet::new();
+        for (member, key_package_json) in &key_packages {
+            if !expected_members.contains(member) {
+                findings.push(AuditFinding::ExtraMember { member: member.clone() });
+            }
+            let key_package = match parse_key_package(key_package_json) {
+                Ok(key_package) => key_package,
+                Err(e) => {
+                    findings.push(AuditFinding::UnreadableShare { member: member.clone(), reason: format!("{:?}", e) });
+                    continue;
+                }
+            };
+            let identifier = identifier_to_hex(key_package.identifier());
+            
+            if key_package.verifying_key() != group_public_key.verifying_key() {
+                findings.push(AuditFinding::OtherGroup { member: member.clone() });
+            }
+            if group_public_key.verifying_shares().get(key_package.identifier()) != Some(key_package.verifying_share()) {
+                findings.push(AuditFinding::WrongVerifyingShare { member: member.clone(), identifier: identifier.clone() });
+            }
+            if frost::keys::VerifyingShare::from(*key_package.signing_share()) != *key_package.verifying_share() {
+                findings.push(AuditFinding::InconsistentKeyPackage { member: member.clone() });
+            }
+            if !identifiers.insert(*key_package.identifier()) {
+                findings.push(AuditFinding::DuplicateIdentifier { member: member.clone(), identifier });
+            }
+            if *key_package.min_signers() != expected_threshold {
+                findings.push(AuditFinding::ThresholdMismatch { member: member.clone(), threshold: *key_package.min_signers() });
+            }
+        }
+        
+        let group_size = group_public_key.verifying_shares().len();
+        if expected_threshold == 0 || group_size < expected_threshold as usize {
+            findings.push(AuditFinding::ThresholdUnreachable { threshold: expected_threshold, group_size });
+        }
+        
+        Ok(GroupAudit {
+            healthy: findings.is_empty(),
+            findings,
+        })
+    })();
+    
+    match result {
+        Ok(audit) => {
+            serde_json::to_string(&FrostResult::ok(audit)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<GroupAudit>::err(e)).unwrap()
+        }
+    }
+}
+
+// Separates rotation statements from every other message the group signs
+const KEY_ROTATION_DOMAIN: &[u8] = b"frost-wasm-core/key-rotation/v1";
+
+// An old group key's signature authorizing a new group key. Keys are
+// compressed SEC1 hex; the Nostr identity is the x-only tail of each.
+#[derive(Serialize, Deserialize)]
+pub struct KeyRotationRecord {
+    pub old_group_key: String,
+    pub new_group_key: String,
+    pub created_at: u64,
+    pub signature: frost::Signature,
+}
+
+/// Sign a statement with the old group key authorizing a rotation to the new one
+///
+/// `key_packages_json` maps labels to at least a threshold of the old group's
+/// key packages; they are used to run a complete signing ceremony in this call,
+/// so it is only for deployments that hold those shares in one place. The
+/// returned record can be checked by anyone with `verify_key_rotation`.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn rotate_group_key_attestation(
+    old_group_public_key_json: &str,
+    new_group_public_key_json: &str,
+    key_packages_json: &str,
+    created_at: u64
+) -> String {
+    let result = (|| -> Result<KeyRotationRecord, FrostError> {
+        check_input_sizes(&[
+            ("old_group_public_key_json", old_group_public_key_json),
+            ("new_group_public_key_json", new_group_public_key_json),
+            ("key_packages_json", key_packages_json),
+        ])?;
+        check_present(&[
+            ("old_group_public_key_json", old_group_public_key_json),
+            ("new_group_public_key_json", new_group_public_key_json),
+        ])?;
+        
+        let old_group_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(old_group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize old group public key: {}", e)))?;
+        let new_group_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(new_group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize new group public key: {}", e)))?;
+        
+        let labeled_packages: BTreeMap<String, String> = serde_json::from_str(key_packages_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse key packages: {}", e)))?;
+        
+        let mut key_packages = BTreeMap::new();
+        for (label, key_package_json) in &labeled_packages {
+            let key_package = parse_key_package(key_package_json)?;
+            if key_package.verifying_key() != old_group_key.verifying_key() {
+                return Err(FrostError::InvalidParticipant(
+                    format!("Key package for {} belongs to another group", label)
+                ));
+            }
+            key_packages.insert(*key_package.identifier(), key_package);
+        }
+        
+        let threshold = key_packages.values().next()
+            .map(|key_package| *key_package.min_signers())
+            .unwrap_or(1);
+        ensure_threshold_met(threshold, key_packages.len())?;
+        
+        let old_key_bytes = old_group_key.verifying_key().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize old group key: {}", e)))?;
+        let new_key_bytes = new_group_key.verifying_key().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize new group key: {}", e)))?;
+        let statement = key_rotation_statement(&old_key_bytes, &new_key_bytes, created_at);
+        
+        let mut rng = secure_rng()?;
+        let mut nonces = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        for (identifier, key_package) in &key_packages {
+            let (signer_nonces, signer_commitments) = round1::commit(key_package.signing_share(), &mut rng);
+            nonces.insert(*identifier, signer_nonces);
+            commitments.insert(*identifier, signer_commitments);
+        }
+        
+        let signing_package = frost::SigningPackage::new(commitments, &statement);
+        let mut signature_shares = BTreeMap::new();
+        for (identifier, key_package) in &key_packages {
+            let signature_share = round2::sign(&signing_package, &nonces[identifier], key_package)
+                .map_err(|e| FrostError::SigningError(format!("Failed to sign rotation statement: {}", e)))?;
+            signature_shares.insert(*identifier, signature_share);
+        }
+        
+        let signature = frost::aggregate(&signing_package, &signature_shares, &old_group_key)
+            .map_err(|e| FrostError::SigningError(format!("Failed to aggregate signature: {}", e)))?;
+        
+        Ok(KeyRotationRecord {
+            old_group_key: hex::encode(old_key_bytes),
+            new_group_key: hex::encode(new_key_bytes),
+            created_at,
+            signature,
+        })
+    })();
+    
+    match result {
+        Ok(record) => {
+            serde_json::to_string(&FrostResult::ok(record)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<KeyRotationRecord>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Check that a key rotation record is signed by its old group key
+#[wasm_bindgen]
+pub fn verify_key_rotation(record_json: &str) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[("record_json", record_json)])?;
+        
+        let record: KeyRotationRecord = serde_json::from_str(record_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize rotation record: {}", e)))?;
+        
+        let old_key_bytes = hex_to_bytes(&record.old_group_key, "old group key")?;
+        let new_key_bytes = hex_to_bytes(&record.new_group_key, "new group key")?;
+        
+        let verifying_key = frost::VerifyingKey::deserialize(&old_key_bytes)
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid old group key: {}", e)))?;
+        frost::VerifyingKey::deserialize(&new_key_bytes)
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid new group key: {}", e)))?;
+        
+        let statement = key_rotation_statement(&old_key_bytes, &new_key_bytes, record.created_at);
+        Ok(verifying_key.verify(&statement, &record.signature).is_ok())
+    })();
+    
+    match result {
+        Ok(valid) => {
+            serde_json::to_string(&FrostResult::ok(valid)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Verify a FROST signature
+#[wasm_bindgen]
+pub fn verify_signature(
+    message: &[u8],
+    signature_json: &str,
+    group_public_key_json: &str
+) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[
+            ("signature_json", signature_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        check_message_size(message)?;
+        
+        // Deserialize the signature
+        let signature: frost::Signature = serde_json::from_str(signature_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signature: {}", e)))?;
+        
+        // Deserialize the group public key  
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        // Verify the signature using FROST
+        let verification_result = group_public_key.verifying_key().verify(message, &signature);
+        
+        Ok(verification_result.is_ok())
+    })();
+    
+    match result {
+        Ok(valid) => {
+            serde_json::to_string(&FrostResult::ok(valid)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Re-serialize a state or package as canonical JSON for hashing
+///
+/// Object keys are sorted at every level and insignificant whitespace is
+/// removed, so two nodes holding the same logical value produce identical
+/// bytes. Packages embedded in a state as JSON strings are kept verbatim.
+#[wasm_bindgen]
+pub fn to_canonical_json(json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("json", json)])?;
+        
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| FrostError::MalformedInput(e.to_string()))?;
+        
+        canonical_json(&value)
+    })();
+    
+    match result {
+        Ok(canonical) => {
+            serde_json::to_string(&FrostResult::ok(canonical)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// Larger requests are almost certainly a bug, and the output is hex in a JS string
+#[cfg(feature = "full")]
+const MAX_RANDOM_BYTES: usize = 1024;
+
+/// Generate `len` cryptographically secure random bytes, hex encoded
+///
+/// Uses the same seeded CSPRNG as keygen and signing, so a missing host random
+/// source is reported as `RngUnavailable` instead of panicking.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn random_bytes(len: usize) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        if len > MAX_RANDOM_BYTES {
+            return Err(FrostError::InputTooLarge {
+                field: "len".to_string(),
+                limit: MAX_RANDOM_BYTES,
+                actual: len,
+            });
+        }
+        
+        let mut bytes = vec![0u8; len];
+        secure_rng()?.fill_bytes(&mut bytes);
+        let encoded = hex::encode(&bytes);
+        bytes.zeroize();
+        
+        Ok(encoded)
+    })();
+    
+    match result {
+        Ok(bytes) => {
+            serde_json::to_string(&FrostResult::ok(bytes)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Reconstruct the group's secret key from a threshold of key packages
+///
+/// **DANGER:** this recreates the single secret that threshold signing exists
+/// to avoid. Whoever sees the result can sign as the group without anyone
+/// else, forever. Only for documented, audited break-glass recovery, and only
+/// in builds made with the `dangerous-recovery` feature.
+///
+/// `key_packages_json` maps labels to key packages of one group. The secret is
+/// returned as hex; the buffers holding it here are zeroized, but the returned
+/// string is the caller's to wipe.
+#[cfg(feature = "dangerous-recovery")]
+#[wasm_bindgen]
+pub fn reconstruct_secret(key_packages_json: &str, threshold: u16) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("key_packages_json", key_packages_json)])?;
+        
+        let mut labeled_packages: BTreeMap<String, String> = serde_json::from_str(key_packages_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse key packages: {}", e)))?;
+        
+        let key_packages = labeled_packages.values()
+            .map(|key_package_json| parse_key_package(key_package_json))
+            .collect::<Result<Vec<_>, _>>();
+        for key_package_json in labeled_packages.values_mut() {
+            key_package_json.zeroize();
+        }
+        let key_packages = key_packages?;
+        if threshold == 0 || key_packages.is_empty() {
+            return Err(FrostError::MalformedInput(
+                "Reconstruction needs a threshold of at least 1 and at least one key package".to_string()
+            ));
+        }
+        
+        ensure_threshold_met(threshold, key_packages.len())?;
+        let group_verifying_key = *key_packages[0].verifying_key();
+        if key_packages.iter().any(|key_package| *key_package.verifying_key() != group_verifying_key) {
+            return Err(FrostError::InvalidParticipant(
+                "Key packages belong to different groups".to_string()
+            ));
+        }
+        if key_packages.iter().any(|key_package| *key_package.min_signers() != threshold) {
+            return Err(FrostError::InvalidParticipant(
+                format!("Key packages were not issued for a threshold of {}", threshold)
+            ));
+        }
+        
+        let signing_key = frost::keys::reconstruct(&key_packages)
+            .map_err(|e| FrostError::KeygenError(format!("Failed to reconstruct the secret: {}", e)))?;
+        if frost::VerifyingKey::from(&signing_key) != group_verifying_key {
+            return Err(FrostError::IntegrityError(
+                "Reconstructed secret does not match the group key".to_string()
+            ));
+        }
+        
+        let secret_bytes = Zeroizing::new(signing_key.serialize());
+        let secret_hex = Zeroizing::new(hex::encode(&*secret_bytes));
+        Ok(secret_hex.to_string())
+    })();
+    
+    match result {
+        Ok(secret) => {
+            let mut response = FrostResult::ok(secret);
+            let response_json = serde_json::to_string(&response).unwrap();
+            if let Some(secret) = response.data.as_mut() {
+                secret.zeroize();
+            }
+            response_json
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Check that the host provides what the module needs before a ceremony relies on it
+///
+/// Draws from the host's secure random source (`crypto.getRandomValues` in
+/// browsers and workers, `random_get` under WASI) and fails with
+/// `RngUnavailable` if it errors or returns repeated output.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn self_test() -> String {
+    let result = probe_rng(&mut OsRng).map(|_| true);
+    
+    match result {
+        Ok(passed) => {
+            serde_json::to_string(&FrostResult::ok(passed)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+// Approximate serialized sizes of states at completion, in bytes
+#[cfg(feature = "full")]
+#[derive(Serialize, Deserialize)]
+pub struct StateSizeEstimate {
+    pub keygen_bytes: usize,
+    pub signing_bytes: usize,
+}
+
+/// Estimate how large keygen and signing state JSON grows by completion
+///
+/// Builds states of the requested shape from freshly generated sample
+/// packages and measures their serialized length, so the figures follow the
+/// real encoding. Labels are assumed to look like `participant_12` and each
+/// extra label byte adds a few bytes per entry; signing assumes `threshold`
+/// signers and a 32-byte message.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn estimate_state_size(threshold: u16, max_participants: u16) -> String {
+    let result = (|| -> Result<StateSizeEstimate, FrostError> {
+        if threshold == 0 || threshold > max_participants {
+            return Err(FrostError::InsufficientParticipants {
+                required: threshold,
+                actual: max_participants,
+            });
+        }
+        check_participant_count("max_participants", max_participants as usize)?;
+        
+        let mut rng = secure_rng()?;
+        let identifiers = (1..=max_participants)
+            .map(|i| FrostIdentifier::try_from(i)
+                .map_err(|e| FrostError::InvalidParticipant(format!("Invalid identifier {}: {}", i, e))))
+            .collect::<Result<Vec<_>, _>>()?;
+        let labels: Vec<String> = (1..=max_participants).map(|i| format!("participant_{}", i)).collect();
+        
+        // One participant's worth of each stored value
+        let signing_key = frost::SigningKey::new(&mut rng);
+        let (key_package, single_group_key) = single_signer_keys(identifiers[0], &hex::encode(signing_key.serialize()))?;
+        let round1_entry = if max_participants == 1 {
+            hex::encode(signing_key.serialize())
+        } else {
+            let (secret, package) = dkg::part1(identifiers[0], max_participants, threshold.max(2), &mut rng)
+                .map_err(|e| FrostError::KeygenError(format!("DKG round 1 failed: {}", e)))?;
+            serde_json::to_string(&Round1Entry { format: ROUND1_ENTRY_FORMAT.to_string(), secret, package })
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize round1 package: {}", e)))?
+        };
+        
+        let (nonces, commitments) = round1::commit(key_package.signing_share(), &mut rng);
+        let signing_package = frost::SigningPackage::new(
+            BTreeMap::from([(identifiers[0], commitments)]),
+            &[0u8; 32],
+        );
+        let signature_share = round2::sign(&signing_package, &nonces, &key_package)
+            .map_err(|e| FrostError::SigningError(format!("Failed to generate signature share: {}", e)))?;
+        let round1_data = serde_json::to_string(&(nonces, commitments))
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize round1 data: {}", e)))?;
+        let signature_share_serialized = serde_json::to_string(&signature_share)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signature share: {}", e)))?;
+        let signature = frost::aggregate(&signing_package, &BTreeMap::from([(identifiers[0], signature_share)]), &single_group_key)
+            .map_err(|e| FrostError::SigningError(format!("Failed to aggregate signature: {}", e)))?;
+        
+        let group_public_key = PublicKeyPackage::new(
+            identifiers.iter().map(|identifier| (*identifier, *key_package.verifying_share())).collect(),
+            *key_package.verifying_key(),
+        );
+        let identifier_hexes: Vec<String> = identifiers.iter().map(identifier_to_hex).collect();
+        let every = |value: &String| -> BTreeMap<String, String> {
+            labels.iter().map(|label| (label.clone(), value.clone())).collect()
+        };
+        
+        let keygen_state = KeygenState {
+            kind: CeremonyKind::Keygen,
+            threshold,
+            max_participants,
+            current_round: 2,
+            round1_packages: every(&round1_entry),
+            key_packages: every(&serde_json::to_string(&key_package)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))?),
+            group_public_key: Some(serde_json::to_string(&group_public_key)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?),
+            participant_identifiers: labels.iter().cloned().zip(identifier_hexes.iter().cloned()).collect(),
+            ceremony_id: None,
+            created_at: None,
+            expires_at: None,
+            commit_reveal: false,
+            round1_commitments: BTreeMap::new(),
+            removed_participants: BTreeSet::new(),
+        };
+        
+        let signers = &labels[..threshold as usize];
+        let signer_entry = |value: &String| -> BTreeMap<String, String> {
+            signers.iter().map(|label| (label.clone(), value.clone())).collect()
+        };
+        let signing_state = SigningState {
+            kind: CeremonyKind::Signing,
+            message: vec![0u8; 32],
+            current_round: 2,
+            signers: signers.to_vec(),
+            round1_packages: signer_entry(&round1_data),
+            signature_shares: signer_entry(&signature_share_serialized),
+            final_signature: Some(serde_json::to_string(&signature)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize final signature: {}", e)))?),
+            signer_identifiers: signers.iter().cloned().zip(identifier_hexes.iter().cloned()).collect(),
+            threshold: Some(threshold),
+            ceremony_id: None,
+            validated_identifiers: identifier_hexes[..threshold as usize].iter().cloned().collect(),
+            imported_nonce_ids: BTreeSet::new(),
+            taproot: None,
+            group_verifying_key: None,
+            transcript_commitments: BTreeMap::new(),
+            signer_verifying_shares: BTreeMap::new(),
+            created_at: None,
+            expires_at: None,
+        };
+        
+        Ok(StateSizeEstimate {
+            keygen_bytes: serde_json::to_string(&FrostResult::ok(keygen_state))
+                .map_err(|e| FrostError::SerializationError(e.to_string()))?
+                .len(),
+            signing_bytes: serde_json::to_string(&FrostResult::ok(signing_state))
+                .map_err(|e| FrostError::SerializationError(e.to_string()))?
+                .len(),
+        })
+    })();
+    
+    match result {
+        Ok(estimate) => {
+            serde_json::to_string(&FrostResult::ok(estimate)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<StateSizeEstimate>::err(e)).unwrap()
+        }
+    }
+}
+
+// What this build of the module supports, fixed at compile time
+#[derive(Serialize, Deserialize)]
+pub struct Capabilities {
+    pub version: String,
+    pub ciphersuite: String,
+    pub keygen: bool,
+    pub signing: bool,
+    pub trusted_dealer: bool,
+    pub nostr_verification: bool,
+    pub repair: bool,
+    pub reshare: bool,
+    pub taproot: bool,
+    pub secret_recovery: bool,
+}
+
+/// Report the ciphersuite and the features compiled into this module
+#[wasm_bindgen]
+pub fn capabilities() -> String {
+    let full = cfg!(feature = "full");
+    let capabilities = Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        ciphersuite: <Secp256K1Sha256 as frost_core::Ciphersuite>::ID.to_string(),
+        keygen: full,
+        signing: full,
+        trusted_dealer: full,
+        nostr_verification: true,
+        repair: false,
+        reshare: false,
+        taproot: full,
+        secret_recovery: cfg!(feature = "dangerous-recovery"),
+    };
+    
+    serde_json::to_string(&FrostResult::ok(capabilities)).unwrap()
+}
+
+// Exact versions and build inputs of this module, for matching a failing
+// deserialization to the frost-core release that wrote the package
+#[derive(Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub frost_core: String,
+    pub frost_secp256k1: String,
+    pub frost_secp256k1_tr: String,
+    pub git_commit: String,
+    pub ciphersuite: String,
+    pub features: Vec<String>,
+}
+
+/// Report the FROST crate versions, git commit, and features of this build
+///
+/// Versions are read from Cargo.lock and the commit from git by the build
+/// script, so the call only copies compile-time constants. Either is
+/// `"unknown"` when the build couldn't determine it.
+#[wasm_bindgen]
+pub fn build_info() -> String {
+    let features = [
+        ("full", cfg!(feature = "full")),
+        ("dangerous-recovery", cfg!(feature = "dangerous-recovery")),
+        ("debug-tools", cfg!(feature = "debug-tools")),
+        ("parallel", cfg!(feature = "parallel")),
+    ];
+    let build_info = BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        frost_core: env!("FROST_CORE_VERSION").to_string(),
+        frost_secp256k1: env!("FROST_SECP256K1_VERSION").to_string(),
+        frost_secp256k1_tr: env!("FROST_SECP256K1_TR_VERSION").to_string(),
+        git_commit: env!("GIT_COMMIT").to_string(),
+        ciphersuite: <Secp256K1Sha256 as frost_core::Ciphersuite>::ID.to_string(),
+        features: features.iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+    };
+    
+    serde_json::to_string(&FrostResult::ok(build_info)).unwrap()
+}
+
+// Either kind of ceremony state, as read by parse_ceremony_state
+#[cfg(feature = "full")]
+enum CeremonyState {
+    Keygen(KeygenState),
+    Signing(SigningState),
+}
+
+// Parse a FrostResult-wrapped state of either kind. The `kind` tag decides
+// which; only states saved before the tag are told apart by their fields.
+#[cfg(feature = "full")]
+fn parse_ceremony_state(state_json: &str) -> Result<CeremonyState, FrostError> {
+    let value: serde_json::Value = serde_json::from_str(state_json)
+        .map_err(|e| FrostError::MalformedInput(e.to_string()))?;
+    let data = value.get("data").filter(|data| !data.is_null()).ok_or(
+        FrostError::InvalidStateTransition("Invalid state provided".to_string())
+    )?;
+    
+    match CeremonyKind::of_state(data) {
+        Some(CeremonyKind::Keygen) => Ok(CeremonyState::Keygen(parse_keygen_state(state_json)?)),
+        Some(CeremonyKind::Signing) => Ok(CeremonyState::Signing(parse_signing_state(state_json)?)),
+        None => Err(FrostError::MalformedInput(
+            "State has no known ceremony kind and is neither a keygen nor a signing state".to_string()
+        )),
+    }
+}
+
+// Whether a ceremony is finished, and its outcome if so
+#[derive(Serialize, Deserialize)]
+pub struct CompletionStatus {
+    pub ceremony: String,
+    pub complete: bool,
+    pub result: Option<String>,
+}
+
+/// Report whether a keygen or signing state is terminal
+///
+/// `ceremony` is `"keygen"` or `"signing"`. When complete, `result` holds the
+/// group public key or the final signature respectively.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn is_complete(state_json: &str) -> String {
+    let result = (|| -> Result<CompletionStatus, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
+        
+        let state = parse_ceremony_state(state_json)?;
+        
+        let (ceremony, result) = match state {
+            CeremonyState::Keygen(state) => ("keygen", state.group_public_key),
+            CeremonyState::Signing(state) => ("signing", state.final_signature),
+        };
+        
+        Ok(CompletionStatus {
+            ceremony: ceremony.to_string(),
+            complete: result.is_some(),
+            result,
+        })
+    })();
+    
+    match result {
+        Ok(status) => {
+            serde_json::to_string(&FrostResult::ok(status)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<CompletionStatus>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Report whether a keygen or signing state is past its `expires_at`
+///
+/// `now_unix` is the caller's current time in unix seconds. An expired
+/// ceremony can't be completed: round functions reject it, so its state should
+/// be discarded. States without `expires_at` never expire.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn check_expired(state_json: &str, now_unix: u64) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
+        
+        let state = parse_ceremony_state(state_json)?;
+        
+        let expires_at = match state {
+            CeremonyState::Keygen(state) => state.expires_at,
+            CeremonyState::Signing(state) => state.expires_at,
+        };
+        
+        Ok(is_expired(expires_at, now_unix))
+    })();
+    
+    match result {
+        Ok(expired) => {
+            serde_json::to_string(&FrostResult::ok(expired)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+// === HELPER FUNCTIONS ===
+
+// A ceremony expires at `expires_at` itself, not a second later
+#[cfg(feature = "full")]
+fn is_expired(expires_at: Option<u64>, now_unix: u64) -> bool {
+    expires_at.is_some_and(|expires_at| now_unix >= expires_at)
+}
+
+// A new ceremony's lifetime must not end before it starts
+#[cfg(feature = "full")]
+fn check_expiry_window(created_at: Option<u64>, expires_at: Option<u64>) -> Result<(), FrostError> {
+    match (created_at, expires_at) {
+        (Some(created_at), Some(expires_at)) if expires_at <= created_at => Err(FrostError::MalformedInput(
+            format!("expires_at {} is not after created_at {}", expires_at, created_at)
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Round functions have no caller-supplied time, so they check expiry against
+// the host clock: Date.now() in the browser or worker, the system clock natively
+#[cfg(feature = "full")]
+fn ensure_not_expired(expires_at: Option<u64>) -> Result<(), FrostError> {
+    if is_expired(expires_at, now_unix()) {
+        return Err(FrostError::InvalidStateTransition(
+            format!("Ceremony expired at {}", expires_at.unwrap_or_default())
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(all(feature = "full", target_arch = "wasm32"))]
+fn now_unix() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+#[cfg(all(feature = "full", not(target_arch = "wasm32")))]
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+// Serialize any value as JSON with sorted keys and no whitespace
+fn canonical_json<T: Serialize>(value: &T) -> Result<String, FrostError> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| FrostError::SerializationError(e.to_string()))?;
+    
+    serde_json::to_string(&sort_json_keys(value))
+        .map_err(|e| FrostError::SerializationError(e.to_string()))
+}
+
+// Rebuild objects in key order so the output doesn't depend on serde_json's map type
+fn sort_json_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let sorted: BTreeMap<String, serde_json::Value> = map.into_iter()
+                .map(|(key, value)| (key, sort_json_keys(value)))
+                .collect();
+            serde_json::Value::Object(sorted.into_iter().collect())
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sort_json_keys).collect())
+        }
+        other => other,
+    }
+}
+
+// The message an old group key signs to authorize a new one: the domain, both
+// 33-byte compressed keys, and the big-endian timestamp
+fn key_rotation_statement(old_group_key: &[u8], new_group_key: &[u8], created_at: u64) -> Vec<u8> {
+    [KEY_ROTATION_DOMAIN, old_group_key, new_group_key, &created_at.to_be_bytes()].concat()
+}
+
+// The group verifying key as the 32-byte x coordinate Nostr uses as a pubkey
+fn group_xonly_pubkey(group_public_key: &PublicKeyPackage<Secp256K1Sha256>) -> Result<[u8; 32], FrostError> {
+    let compressed = group_public_key.verifying_key().serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?;
+    
+    compressed.get(1..33)
+        .and_then(|xonly| xonly.try_into().ok())
+        .ok_or(FrostError::SerializationError(
+            format!("Expected a 33-byte compressed group key, got {} bytes", compressed.len())
+        ))
+}
+
+// Keep a signing ceremony to one group. A key package must belong to the
+// expected group when one is given, and always to the group the ceremony was
+// pinned to by the first signer, so a share from another group fails here
+// instead of at aggregation.
+#[cfg(feature = "full")]
+fn check_signer_group(
+    state: &mut SigningState,
+    key_package: &KeyPackage,
+    group_public_key: Option<&PublicKeyPackage<Secp256K1Sha256>>,
+) -> Result<(), FrostError> {
+    let identifier_hex = identifier_to_hex(key_package.identifier());
+    if let Some(group_public_key) = group_public_key {
+        let belongs = key_package.verifying_key() == group_public_key.verifying_key()
+            && group_public_key.verifying_shares().get(key_package.identifier()) == Some(key_package.verifying_share());
+        if !belongs {
+            return Err(FrostError::InvalidParticipant(
+                format!("Key package {} does not belong to the expected group", identifier_hex)
+            ));
+        }
+    }
+    
+    let implied_share = frost::keys::VerifyingShare::from(*key_package.signing_share()).serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize verifying share: {}", e)))?;
+    state.signer_verifying_shares.insert(identifier_hex.clone(), hex::encode(implied_share));
+    
+    let group_key_hex = hex::encode(key_package.verifying_key().serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?);
+    match &state.group_verifying_key {
+        Some(pinned) if *pinned != group_key_hex => Err(FrostError::InvalidParticipant(
+            format!("Key package {} is for group {}, but this ceremony signs for group {}", identifier_hex, group_key_hex, pinned)
+        )),
+        Some(_) => Ok(()),
+        None => {
+            state.group_verifying_key = Some(group_key_hex);
+            Ok(())
+        }
+    }
+}
+
+// Before aggregating, check the group public key against the key packages the
+// signers committed with: a group package from another group otherwise fails
+// as an invalid share from every signer
+#[cfg(feature = "full")]
+fn check_group_matches_key_packages<'a>(
+    state: &SigningState,
+    group_public_key: &PublicKeyPackage<Secp256K1Sha256>,
+    identifiers: impl IntoIterator<Item = &'a FrostIdentifier>,
+) -> Result<(), FrostError> {
+    let group_key_hex = hex::encode(group_public_key.verifying_key().serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?);
+    let pinned_group_matches = state.group_verifying_key.as_ref().is_none_or(|pinned| *pinned == group_key_hex);
+    
+    for identifier in identifiers {
+        let identifier_hex = identifier_to_hex(identifier);
+        let Some(implied_share) = state.signer_verifying_shares.get(&identifier_hex) else {
+            continue;
+        };
+        let group_share = match group_public_key.verifying_shares().get(identifier) {
+            Some(share) => hex::encode(share.serialize()
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize verifying share: {}", e)))?),
+            None => String::new(),
+        };
+        if *implied_share != group_share || !pinned_group_matches {
+            return Err(FrostError::SigningError(format!(
+                "key package / group key mismatch: the key package for {} is not part of the group public key {}", identifier_hex, group_key_hex
+            )));
+        }
+    }
+    
+    Ok(())
+}
+
+// Each identifier signs for one participant, and a participant given an
+// identifier when the ceremony was created must use that key package
+#[cfg(feature = "full")]
+fn check_signer_identifier(state: &SigningState, participant_id: &str, key_package: &KeyPackage) -> Result<(), FrostError> {
+    let identifier_hex = identifier_to_hex(key_package.identifier());
+    if let Some(expected) = state.signer_identifiers.get(participant_id) {
+        if *expected != identifier_hex {
+            return Err(FrostError::InvalidParticipant(
+                format!("Key package {} is not {}'s, which has identifier {}", identifier_hex, participant_id, expected)
+            ));
+        }
+    }
+    if let Some((other, _)) = state.signer_identifiers.iter()
+        .find(|(signer, recorded)| signer.as_str() != participant_id && **recorded == identifier_hex)
+    {
+        return Err(FrostError::InvalidParticipant(
+            format!("Key package {} belongs to {}", identifier_hex, other)
+        ));
+    }
+    
+    Ok(())
+}
+
+// Parse a keygen state wrapped in a FrostResult
+#[cfg(feature = "full")]
+fn parse_keygen_state(state_json: &str) -> Result<KeygenState, FrostError> {
+    let state_result: FrostResult<KeygenState> = parse_state_of_kind(state_json, CeremonyKind::Keygen)?;
+    
+    state_result.data.ok_or(FrostError::InvalidStateTransition(
+        "Invalid state provided".to_string()
+    ))
+}
+
+// Parse a signing state wrapped in a FrostResult
+#[cfg(feature = "full")]
+fn parse_signing_state(state_json: &str) -> Result<SigningState, FrostError> {
+    let state_result: FrostResult<SigningState> = parse_state_of_kind(state_json, CeremonyKind::Signing)?;
+    
+    state_result.data.ok_or(FrostError::InvalidStateTransition(
+        "Invalid state provided".to_string()
+    ))
+}
+
+// Deserialize a FrostResult-wrapped state after checking it is of the
+// expected kind, so a state from the other ceremony fails by name instead of
+// on whichever field it happens to lack
+#[cfg(feature = "full")]
+fn parse_state_of_kind<T: serde::de::DeserializeOwned>(
+    state_json: &str,
+    expected: CeremonyKind,
+) -> Result<FrostResult<T>, FrostError> {
+    let value: serde_json::Value = serde_json::from_str(state_json)
+        .map_err(|e| FrostError::MalformedInput(e.to_string()))?;
+    
+    if let Some(kind) = value.get("data").and_then(CeremonyKind::of_state) {
+        if kind != expected {
+            return Err(FrostError::InvalidStateTransition(
+                format!("wrong ceremony kind: expected a {} state, got a {} state", expected.name(), kind.name())
+            ));
+        }
+    }
+    
+    serde_json::from_value(value).map_err(|e| FrostError::MalformedInput(e.to_string()))
+}
+
+// Deserialize a key package, also accepting a share from the trusted dealer
+// and either of them wrapped in a checksummed share envelope
+#[cfg(feature = "full")]
+fn parse_key_package(key_package_json: &str) -> Result<KeyPackage, FrostError> {
+    if let Ok(envelope) = serde_json::from_str::<ShareEnvelope>(key_package_json) {
+        if envelope.format != SHARE_ENVELOPE_FORMAT {
+            return Err(FrostError::MalformedInput(
+                format!("Unsupported share envelope format {}", envelope.format)
+            ));
+        }
+        if share_checksum(&envelope.share) != envelope.checksum.to_lowercase() {
+            return Err(FrostError::CorruptedShare(
+                "Share does not match its checksum".to_string()
+            ));
+        }
+        return parse_unwrapped_key_package(&envelope.share);
+    }
+    
+    parse_unwrapped_key_package(key_package_json)
+}
+
+#[cfg(feature = "full")]
+fn parse_unwrapped_key_package(key_package_json: &str) -> Result<KeyPackage, FrostError> {
+    if let Ok(key_package) = serde_json::from_str::<KeyPackage>(key_package_json) {
+        return Ok(key_package);
+    }
+    
+    let secret_share: frost::keys::SecretShare = serde_json::from_str(key_package_json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize key package: {}", e)))?;
+    
+    KeyPackage::try_from(secret_share)
+        .map_err(|e| FrostError::SigningError(format!("Invalid secret share: {}", e)))
+}
+
+// Sum of lambda_i * Y_i over the key packages' verifying shares, where
+// lambda_i is the Lagrange coefficient for evaluating at zero. Works on the
+// public curve points with k256 because frost-core keeps its scalar and
+// element accessors internal.
+#[cfg(feature = "full")]
+fn interpolate_verifying_shares(key_packages: &[&KeyPackage]) -> Result<k256::ProjectivePoint, FrostError> {
+    let mut points = Vec::with_capacity(key_packages.len());
+    for key_package in key_packages {
+        let x = identifier_scalar(key_package.identifier())?;
+        let y = sec1_point(&key_package.verifying_share().serialize()
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid verifying share: {}", e)))?)?;
+        points.push((x, y));
+    }
+    
+    interpolate_at_zero(&points)
+}
+
+// Sum of lambda_i * P_i over `(x_i, P_i)` pairs with distinct x_i
+#[cfg(feature = "full")]
+fn interpolate_at_zero(points: &[(k256::Scalar, k256::ProjectivePoint)]) -> Result<k256::ProjectivePoint, FrostError> {
+    let mut sum = k256::ProjectivePoint::IDENTITY;
+    for (i, (x_i, y_i)) in points.iter().enumerate() {
+        let mut numerator = k256::Scalar::ONE;
+        let mut denominator = k256::Scalar::ONE;
+        for (j, (x_j, _)) in points.iter().enumerate() {
+            if i != j {
+                numerator *= x_j;
+                denominator *= *x_j - x_i;
+            }
+        }
+        let denominator_inverse = Option::<k256::Scalar>::from(denominator.invert()).ok_or(
+            FrostError::InvalidParticipant("Shares repeat an identifier".to_string())
+        )?;
+        sum += *y_i * (numerator * denominator_inverse);
+    }
+    
+    Ok(sum)
+}
+
+// An identifier as the curve scalar it stands for
+#[cfg(feature = "full")]
+fn identifier_scalar(identifier: &FrostIdentifier) -> Result<k256::Scalar, FrostError> {
+    let x = k256::Scalar::from_repr(k256::FieldBytes::clone_from_slice(&identifier.serialize()));
+    Option::<k256::Scalar>::from(x).ok_or(FrostError::InvalidParticipant(
+        format!("Invalid identifier {}", identifier_to_hex(identifier))
+    ))
+}
+
+// Read a keygen round 1 entry in either storage form: the tagged object
+// written now, or the `[secret, package]` array of earlier versions, which
+// adds the legacy marker to `deprecations`
+#[cfg(feature = "full")]
+fn read_round1_entry(round1_data: &str, participant: &str, deprecations: &mut Vec<String>) -> Result<Round1Entry, FrostError> {
+    if let Ok(entry) = serde_json::from_str::<Round1Entry>(round1_data) {
+        if entry.format != ROUND1_ENTRY_FORMAT {
+            return Err(FrostError::MalformedInput(
+                format!("Unsupported round 1 entry format {} for {}", entry.format, participant)
+            ));
+        }
+        return Ok(entry);
+    }
+    
+    let (secret, package): (dkg::round1::SecretPackage<Secp256K1Sha256>, dkg::round1::Package<Secp256K1Sha256>) =
+        serde_json::from_str(round1_data)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize round 1 entry for {}: {}", participant, e)))?;
+    
+    if !deprecations.iter().any(|marker| marker == LEGACY_ROUND1_MARKER) {
+        deprecations.push(LEGACY_ROUND1_MARKER.to_string());
+    }
+    Ok(Round1Entry {
+        format: ROUND1_ENTRY_FORMAT.to_string(),
+        secret,
+        package,
+    })
+}
+
+// The public package from a round 1 entry as keygen_round1 returns it, or
+// from a bare package
+#[cfg(feature = "full")]
+fn public_round1_package(
+    round1_data: &str,
+    participant: &str,
+    deprecations: &mut Vec<String>,
+) -> Result<dkg::round1::Package<Secp256K1Sha256>, FrostError> {
+    if let Ok(package) = serde_json::from_str(round1_data) {
+        return Ok(package);
+    }
+    
+    Ok(read_round1_entry(round1_data, participant, deprecations)?.package)
+}
+
+// Domain tag for round 1 package commitments
+#[cfg(feature = "full")]
+const ROUND1_COMMIT_DOMAIN: &[u8] = b"frost-wasm-core/round1-commitment/v1";
+
+#[cfg(feature = "full")]
+fn round1_package_digest(package: &dkg::round1::Package<Secp256K1Sha256>) -> Result<String, FrostError> {
+    let digest = Sha256::new()
+        .chain_update(ROUND1_COMMIT_DOMAIN)
+        .chain_update(canonical_json(package)?.as_bytes())
+        .finalize();
+    Ok(hex::encode(digest))
+}
+
+// A participant's revealed round 1 entry, checked against its commitment and
+// the ceremony's parameters. Nothing is revealed until every participant has
+// committed, so no package can be chosen with another in view.
+#[cfg(feature = "full")]
+fn read_round1_reveal(state: &KeygenState, participant_id: &str, revealed_json: &str) -> Result<Round1Entry, FrostError> {
+    if state.round1_commitments.len() < state.max_participants as usize {
+        return Err(FrostError::InvalidStateTransition(format!(
+            "Waiting for round 1 commitments: {} of {}",
+            state.round1_commitments.len(),
+            state.max_participants
+        )));
+    }
+    let commitment = state.round1_commitments.get(participant_id)
+        .ok_or(FrostError::InvalidParticipant(format!("{} has not committed to a round 1 package", participant_id)))?;
+    
+    let entry = read_round1_entry(revealed_json, participant_id, &mut Vec::new())?;
+    if round1_package_digest(&entry.package)? != *commitment {
+        return Err(FrostError::InvalidParticipant(
+            format!("Round 1 package from {} does not match its commitment", participant_id)
+        ));
+    }
+    if *entry.secret.min_signers() != state.threshold || *entry.secret.max_signers() != state.max_participants {
+        return Err(FrostError::MalformedInput(
+            format!("Revealed round 1 package for {} was generated for different group parameters", participant_id)
+        ));
+    }
+    
+    Ok(entry)
+}
+
+// A compressed SEC1 point as a k256 curve point
+#[cfg(feature = "full")]
+fn sec1_point(bytes: &[u8]) -> Result<k256::ProjectivePoint, FrostError> {
+    k256::PublicKey::from_sec1_bytes(bytes)
+        .map(|public_key| public_key.to_projective())
+        .map_err(|_| FrostError::MalformedInput("Invalid curve point".to_string()))
+}
+
+#[cfg(feature = "full")]
+fn identifier_to_hex(identifier: &FrostIdentifier) -> String {
+    hex::encode(identifier.serialize())
+}
+
+#[cfg(feature = "full")]
+fn identifier_from_hex(identifier_hex: &str) -> Result<FrostIdentifier, FrostError> {
+    let bytes = hex::decode(identifier_hex)
+        .map_err(|e| FrostError::InvalidParticipant(format!("Invalid identifier {}: {}", identifier_hex, e)))?;
+    
+    FrostIdentifier::deserialize(&bytes)
+        .map_err(|e| FrostError::InvalidParticipant(format!("Invalid identifier {}: {}", identifier_hex, e)))
+}
+
+// Fail with the counts involved when fewer than threshold signers took part
+#[cfg(feature = "full")]
+fn ensure_threshold_met(threshold: u16, actual: usize) -> Result<(), FrostError> {
+    if actual < threshold as usize {
+        return Err(FrostError::InsufficientParticipants {
+            required: threshold,
+            actual: participant_count(actual)?,
+        });
+    }
+    Ok(())
+}
+
+// Convert a participant count to u16, failing instead of wrapping when a
+// corrupted state holds more entries than FROST can number
+#[cfg(feature = "full")]
+fn participant_count(count: usize) -> Result<u16, FrostError> {
+    u16::try_from(count).map_err(|_| FrostError::InvalidParticipant(
+        format!("Participant count {} exceeds the maximum of {}", count, u16::MAX)
+    ))
+}
+
+// Hash a pubkey to an identifier. If the hash is the zero scalar (which is
+// not a valid identifier) retry with a one byte counter appended: attempt n
+// hashes `pubkey || n`, attempt 0 hashes the bare pubkey.
+#[cfg(feature = "full")]
+fn derive_identifier_from_pubkey(pubkey_hex: &str) -> Result<FrostIdentifier, FrostError> {
+    let pubkey = hex::decode(pubkey_hex)
+        .map_err(|e| FrostError::InvalidParticipant(format!("Invalid pubkey {}: {}", pubkey_hex, e)))?;
+    
+    if pubkey.len() != 32 {
+        return Err(FrostError::InvalidParticipant(
+            format!("Expected a 32-byte x-only pubkey, got {} bytes", pubkey.len())
+        ));
+    }
+    
+    for counter in 0..=u8::MAX {
+        let mut input = pubkey.clone();
+        if counter > 0 {
+            input.push(counter);
+        }
+        if let Ok(identifier) = FrostIdentifier::derive(&input) {
+            return Ok(identifier);
+        }
+    }
+    
+    Err(FrostError::InvalidParticipant(
+        format!("Could not derive an identifier from pubkey {}", pubkey_hex)
+    ))
+}
+
+// Two reads from the host source must succeed and differ. This catches a
+// missing backend or a stubbed one, not a subtly weak generator.
+#[cfg(feature = "full")]
+fn probe_rng<R: RngCore>(source: &mut R) -> Result<(), FrostError> {
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    source.try_fill_bytes(&mut first)
+        .and_then(|_| source.try_fill_bytes(&mut second))
+        .map_err(|e| FrostError::RngUnavailable(format!("The host's random source failed: {}", e)))?;
+    
+    if first == second {
+        return Err(FrostError::RngUnavailable(
+            "The host's random source returned the same output twice".to_string()
+        ));
+    }
+    Ok(())
+}
+
+// All randomness comes from a CSPRNG seeded by one fallible read of the host's
+// secure random source. OsRng panics when `crypto.getRandomValues` is missing
+// or throws, so it's never handed to frost directly.
+#[cfg(feature = "full")]
+fn secure_rng() -> Result<StdRng, FrostError> {
+    seeded_rng(&mut OsRng, None)
+}
+
+// Caller entropy is combined with the source's seed by hashing both into the
+// DRBG seed. XORing generator outputs would let a chosen input cancel out the
+// host's randomness; hashing keeps the seed unpredictable as long as either
+// input is, so weak or hostile extra entropy can't make things worse.
+#[cfg(feature = "full")]
+const EXTRA_ENTROPY_DOMAIN: &[u8] = b"frost-wasm-core/extra-entropy/v1";
+
+#[cfg(feature = "full")]
+fn seeded_rng<R: RngCore>(source: &mut R, extra_entropy: Option<&[u8]>) -> Result<StdRng, FrostError> {
+    let mut seed = <StdRng as SeedableRng>::Seed::default();
+    source.try_fill_bytes(&mut seed)
+        .map_err(|e| FrostError::RngUnavailable(e.to_string()))?;
+    
+    if let Some(extra_entropy) = extra_entropy {
+        let mut mixed: [u8; 32] = Sha256::new()
+            .chain_update(EXTRA_ENTROPY_DOMAIN)
+            .chain_update(seed)
+            .chain_update((extra_entropy.len() as u64).to_be_bytes())
+            .chain_update(extra_entropy)
+            .finalize()
+            .into();
+        seed.copy_from_slice(&mixed);
+        mixed.zeroize();
+    }
+    
+    let rng = StdRng::from_seed(seed);
+    seed.zeroize();
+    Ok(rng)
+}
+
+// Split a fresh secret into shares for identifiers 1..=max_participants, keyed
+// by the matching label or `participant_{i}` when no labels are given
+#[cfg(feature = "full")]
+fn deal_shares<R: RngCore + CryptoRng>(
+    threshold: u16,
+    max_participants: u16,
+    labels: Option<&[String]>,
+    rng: &mut R,
+) -> Result<DealerOutput, FrostError> {
+    split_shares(threshold, max_participants, labels, None, rng)
+}
+
+// Split `secret`, or a fresh secret when it is None, as for deal_shares
+#[cfg(feature = "full")]
+fn split_shares<R: RngCore + CryptoRng>(
+    threshold: u16,
+    max_participants: u16,
+    labels: Option<&[String]>,
+    secret: Option<&frost::SigningKey>,
+    rng: &mut R,
+) -> Result<DealerOutput, FrostError> {
+    if threshold == 0 || threshold > max_participants {
+        return Err(FrostError::InsufficientParticipants {
+            required: threshold,
+            actual: max_participants,
+        });
+    }
+    check_participant_count("max_participants", max_participants as usize)?;
+    
+    let labels: Vec<String> = match labels {
+        Some(labels) => {
+            if labels.len() != max_participants as usize {
+                return Err(FrostError::MalformedInput(
+                    format!("Expected {} labels, got {}", max_participants, labels.len())
+                ));
+            }
+            let mut seen = BTreeSet::new();
+            for label in labels {
+                if label.is_empty() || !seen.insert(label) {
+                    return Err(FrostError::MalformedInput(
+                        format!("Labels must be unique and non-empty: {:?}", label)
+                    ));
+                }
+            }
+            labels.to_vec()
+        }
+        None => (1..=max_participants).map(|i| format!("participant_{}", i)).collect(),
+    };
+    
+    // Create identifiers for all participants
+    let mut identifiers = Vec::new();
+    for i in 1..=max_participants {
+        let identifier = FrostIdentifier::try_from(i)
+            .map_err(|e| FrostError::InvalidParticipant(format!("Invalid identifier {}: {}", i, e)))?;
+        identifiers.push(identifier);
+    }
+    
+    // Split an even-y secret, so the group key is its own BIP340 lift and
+    // Nostr verifiers need no parity adjustment
+    let secret = match secret {
+        Some(secret) => *secret,
+        None => frost::SigningKey::new(rng),
+    };
+    let secret = even_y_signing_key(secret)?;
+    let (shares, group_public_key) = if threshold == 1 {
+        any_signer_keys(&secret, &identifiers)?
+    } else {
+        let (shares, group_public_key) = frost::keys::split(
+            &secret,
+            max_participants,
+            threshold,
+            IdentifierList::Custom(&identifiers),
+            rng,
+        ).map_err(|e| FrostError::KeygenError(format!("Trusted dealer failed: {}", e)))?;
+        
+        let mut serialized = BTreeMap::new();
+        for (identifier, share) in shares {
+            let share_data = serde_json::to_string(&share)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize share: {}", e)))?;
+            serialized.insert(identifier, share_data);
+        }
+        (serialized, group_public_key)
+    };
+    
+    // Store shares under the label for each identifier
+    let mut serialized_shares = BTreeMap::new();
+    let mut identifier_table = BTreeMap::new();
+    for (identifier, label) in identifiers.iter().zip(labels) {
+        let share_data = shares[identifier].clone();
+        serialized_shares.insert(label.clone(), share_data);
+        identifier_table.insert(label, identifier_to_hex(identifier));
+    }
+    
+    // Serialize group public key
+    let group_public_key_serialized = serde_json::to_string(&group_public_key)
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group public key: {}", e)))?;
+    
+    Ok(DealerOutput {
+        group_public_key: group_public_key_serialized,
+        shares: serialized_shares,
+        identifiers: identifier_table,
+    })
+}
+
+// Key packages for a 1-of-N group, where every participant's share is the
+// secret itself and any one of them signs alone. frost's split rejects a
+// threshold of 1, so these are built like single_signer_keys, and dealt as
+// key packages rather than secret shares.
+#[cfg(feature = "full")]
+fn any_signer_keys(
+    secret: &frost::SigningKey,
+    identifiers: &[FrostIdentifier],
+) -> Result<(BTreeMap<FrostIdentifier, String>, PublicKeyPackage<Secp256K1Sha256>), FrostError> {
+    let secret_hex = Zeroizing::new(hex::encode(secret.serialize()));
+    let mut key_packages = BTreeMap::new();
+    let mut verifying_shares = BTreeMap::new();
+    for identifier in identifiers {
+        let (key_package, _) = single_signer_keys(*identifier, &secret_hex)?;
+        verifying_shares.insert(*identifier, *key_package.verifying_share());
+        let key_package_data = serde_json::to_string(&key_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))?;
+        key_packages.insert(*identifier, key_package_data);
+    }
+    
+    Ok((key_packages, PublicKeyPackage::new(verifying_shares, frost::VerifyingKey::from(secret))))
+}
+
+// The signing key, negated if its public key has odd y. The x-only public
+// key, and so the Nostr identity, is the same either way.
+#[cfg(feature = "full")]
+fn even_y_signing_key(signing_key: frost::SigningKey) -> Result<frost::SigningKey, FrostError> {
+    let public_key = frost::VerifyingKey::from(&signing_key).serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize public key: {}", e)))?;
+    if public_key[0] != 0x03 {
+        return Ok(signing_key);
+    }
+    
+    let mut secret_bytes = signing_key.serialize();
+    let secret = Option::<k256::Scalar>::from(k256::Scalar::from_repr(k256::FieldBytes::clone_from_slice(&secret_bytes)));
+    secret_bytes.zeroize();
+    let mut negated = secret
+        .map(|secret| (-secret).to_bytes())
+        .ok_or(FrostError::KeygenError("Signing key is not a valid scalar".to_string()))?;
+    let negated_key = frost::SigningKey::deserialize(negated.as_slice())
+        .map_err(|e| FrostError::KeygenError(format!("Failed to negate signing key: {}", e)));
+    negated.zeroize();
+    negated_key
+}
+
+// Key package and group key for a 1-of-1 group, where the group key is simply
+// the participant's own key. Built by hand because frost's constructors for
+// generated keys reject min_signers below 2.
+#[cfg(feature = "full")]
+fn single_signer_keys(
+    identifier: FrostIdentifier,
+    signing_key_hex: &str,
+) -> Result<(KeyPackage, PublicKeyPackage<Secp256K1Sha256>), FrostError> {
+    let mut signing_key_bytes = hex_to_bytes(signing_key_hex, "signing key")?;
+    
+    let signing_key = frost::SigningKey::deserialize(&signing_key_bytes)
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid signing key: {}", e)));
+    let signing_share = frost::keys::SigningShare::deserialize(&signing_key_bytes)
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid signing share: {}", e)));
+    signing_key_bytes.zeroize();
+    let (signing_key, signing_share) = (signing_key?, signing_share?);
+    
+    let verifying_key = frost::VerifyingKey::from(&signing_key);
+    let verifying_share = frost::keys::VerifyingShare::from(signing_share);
+    
+    let key_package = KeyPackage::new(identifier, signing_share, verifying_share, verifying_key, 1);
+    let public_key_package = PublicKeyPackage::new(
+        BTreeMap::from([(identifier, verifying_share)]),
+        verifying_key,
+    );
+    
+    Ok((key_package, public_key_package))
+}
+
+// Exported nonces must come from this ceremony, be new to this state, and
+// belong to a signer who doesn't already hold nonces here
+#[cfg(feature = "full")]
+fn check_nonce_import(state: &SigningState, exported: &ExportedNonces) -> Result<(), FrostError> {
+    if state.ceremony_id.as_deref() != Some(exported.ceremony_id.as_str()) {
+        return Err(FrostError::InvalidStateTransition(
+            format!("Nonces belong to ceremony {}", exported.ceremony_id)
+        ));
+    }
+    
+    if state.imported_nonce_ids.contains(&exported.nonce_id) {
+        return Err(FrostError::InvalidStateTransition(
+            format!("Nonces {} were already imported", exported.nonce_id)
+        ));
+    }
+    
+    if !state.signers.contains(&exported.participant_id) {
+        return Err(FrostError::InvalidParticipant(
+            format!("Participant {} is not a signer", exported.participant_id)
+        ));
+    }
+    
+    if state.round1_packages.contains_key(&exported.participant_id) {
+        return Err(FrostError::InvalidStateTransition(
+            format!("Participant {} already holds nonces for this ceremony", exported.participant_id)
+        ));
+    }
+    
+    Ok(())
+}
+
+// Produce a participant's signature share with the nonces it committed in
+// round 1 and record it in the state, returning the share
+#[cfg(feature = "full")]
+fn sign_share(
+    state: &mut SigningState,
+    participant_id: &str,
+    key_package: &KeyPackage,
+    signing_package: &frost::SigningPackage,
+    timings: &mut Timings,
+) -> Result<round2::SignatureShare<Secp256K1Sha256>, FrostError> {
+    if state.current_round != 2 {
+        return Err(FrostError::InvalidStateTransition(
+            format!("Expected round 2, got round {}", state.current_round)
+        ));
+    }
+    
+    // The signers committed to the message in this state; a signing package
+    // carrying any other message was substituted after commitments
+    if signing_package.message() != state.message.as_slice() {
+        return Err(FrostError::SigningError("message mismatch".to_string()));
+    }
+    
+    // Get this participant's nonces from round 1
+    let participant_round1_data = state.round1_packages.get(participant_id)
+        .ok_or(FrostError::InvalidParticipant(format!("Participant {} not found in round 1", participant_id)))?;
+    
+    let (nonces, commitments): (frost::round1::SigningNonces, frost::round1::SigningCommitments) = 
+        serde_json::from_str(participant_round1_data)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize nonces: {}", e)))?;
+    
+    // Shares are aggregated under the identifier recorded in round 1, so the
+    // key package must be the one that produced this participant's nonces
+    let identifier_hex = identifier_to_hex(key_package.identifier());
+    if state.signer_identifiers.get(participant_id) != Some(&identifier_hex) {
+        return Err(FrostError::InvalidParticipant(
+            format!("Key package does not belong to {}, who committed in round 1", participant_id)
+        ));
+    }
+    
+    // Signing with fewer commitments than the threshold can never aggregate
+    ensure_threshold_met(
+        *key_package.min_signers(),
+        signing_package.signing_commitments().len(),
+    )?;
+    
+    // The package is only final once it carries every signer's commitment;
+    // a share over an earlier, partial package can't be aggregated with the rest
+    let missing: Vec<&str> = state.signers.iter()
+        .filter(|signer| !state.signer_identifiers.get(*signer)
+            .and_then(|identifier_hex| identifier_from_hex(identifier_hex).ok())
+            .is_some_and(|identifier| signing_package.signing_commitments().contains_key(&identifier)))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(FrostError::InvalidStateTransition(
+            format!("Signing package is not final, missing commitments from {}", missing.join(", "))
+        ));
+    }
+    
+    // A package carrying some other commitment for this signer, such as one
+    // replayed from an earlier ceremony, can't be signed with these nonces
+    if signing_package.signing_commitments().get(key_package.identifier()) != Some(&commitments) {
+        return Err(FrostError::SigningError(format!(
+            "stale commitment: the signing package's commitment for {} is not the one made in this ceremony", participant_id
+        )));
+    }
+    
+    // Generate signature share
+    let signature_share = timings.measure("signing", || match &state.taproot {
+        Some(spend) => taproot::sign_with_tweak(signing_package, &nonces, key_package, spend),
+        None => round2::sign(signing_package, &nonces, key_package)
+            .map_err(|e| FrostError::SigningError(format!("Failed to generate signature share: {}", e))),
+    })?;
+    
+    // Serialize and store the signature share
+    let signature_share_serialized = serde_json::to_string(&signature_share)
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize signature share: {}", e)))?;
+    
+    state.signature_shares.insert(participant_id.to_string(), signature_share_serialized);
+    // A share this module just produced with the signer's own key needs no check
+    state.validated_identifiers.insert(identifier_hex);
+    log_event(LogEvent::PackageAccepted {
+        ceremony: CeremonyKind::Signing,
+        round: 2,
+        participant: participant_id,
+        ceremony_id: state.ceremony_id.as_deref(),
+    });
+    
+    Ok(signature_share)
+}
+
+// Parse a weighted participant's map of virtual label to key package,
+// rejecting labels that belong to another participant
+#[cfg(feature = "full")]
+fn parse_weighted_key_packages(
+    participant_id: &str,
+    key_packages_json: &str,
+) -> Result<BTreeMap<String, String>, FrostError> {
+    let key_packages: BTreeMap<String, String> = serde_json::from_str(key_packages_json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to parse key packages: {}", e)))?;
+    if key_packages.is_empty() {
+        return Err(FrostError::MalformedInput("key_packages_json must contain at least one key package".to_string()));
+    }
+    check_participant_count("key_packages_json", key_packages.len())?;
+    
+    for label in key_packages.keys() {
+        let owned = label.rsplit_once('#')
+            .is_some_and(|(owner, index)| owner == participant_id && index.parse::<u16>().is_ok_and(|index| index >= 1));
+        if !owned {
+            return Err(FrostError::InvalidParticipant(
+                format!("{} is not a virtual participant of {}", label, participant_id)
+            ));
+        }
+    }
+    
+    Ok(key_packages)
+}
+
+// Generate one signer's round 1 nonces and record them in the state,
+// returning the public commitment for the coordinator
+#[cfg(feature = "full")]
+fn commit_signer(
+    state: &mut SigningState,
+    participant_id: &str,
+    key_package: &KeyPackage,
+    group_public_key: Option<&PublicKeyPackage<Secp256K1Sha256>>,
+    timings: &mut Timings,
+) -> Result<SignerCommitment, FrostError> {
+    check_signer_group(state, key_package, group_public_key)?;
+    check_signer_identifier(state, participant_id, key_package)?;
+    
+    // Generate nonces for signing round 1
+    let mut rng = secure_rng()?;
+    let (nonces, commitments) = timings.measure("nonce_generation", || {
+        round1::commit(key_package.signing_share(), &mut rng)
+    });
+    
+    // Serialize the nonces and commitments for storage
+    let round1_data = serde_json::to_string(&(nonces, commitments))
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize round1 data: {}", e)))?;
+    
+    state.round1_packages.insert(participant_id.to_string(), round1_data);
+    state.signer_identifiers.insert(participant_id.to_string(), identifier_to_hex(key_package.identifier()));
+    state.threshold = Some(*key_package.min_signers());
+    
+    // In a 1-of-N group any one signer completes the ceremony, so the first
+    // to commit becomes the only signer instead of everyone waiting on the rest
+    if *key_package.min_signers() == 1 {
+        state.signers = vec![participant_id.to_string()];
+        state.signer_identifiers.retain(|signer, _| signer == participant_id);
+    }
+    
+    // Check if we have enough participants to advance
+    if state.round1_packages.len() >= state.signers.len() {
+        state.current_round = 2;
+    }
+    log_round1_accepted(CeremonyKind::Signing, participant_id, state.current_round == 2, state.ceremony_id.as_deref());
+    
+    // Return the commitments (public part) for coordination
+    Ok(SignerCommitment {
+        identifier: identifier_to_hex(key_package.identifier()),
+        ceremony_binding: Some(commitment_binding(state.ceremony_id.as_deref(), &state.message, key_package.identifier(), &commitments)?),
+        commitment: commitments,
+    })
+}
+
+// Log a round 1 package, followed by the start of round 2 if it completed round 1
+#[cfg(feature = "full")]
+fn log_round1_accepted(ceremony: CeremonyKind, participant: &str, round_complete: bool, ceremony_id: Option<&str>) {
+    log_event(LogEvent::PackageAccepted { ceremony, round: 1, participant, ceremony_id });
+    if round_complete {
+        log_event(LogEvent::RoundStarted { ceremony, round: 2, ceremony_id });
+    }
+}
+
+// The threshold recorded when signers committed. Never defaulted: guessing
+// low would let a ceremony shed signers or aggregate below its quorum.
+#[cfg(feature = "full")]
+fn recorded_threshold(state: &SigningState) -> Result<u16, FrostError> {
+    state.threshold.ok_or(FrostError::InvalidStateTransition(
+        "The signing state records no threshold; a signer must commit in round 1 first".to_string()
+    ))
+}
+
+// Shares only aggregate over the package built from this state's message and
+// round 1 commitments; a package differing in either was substituted after
+// the signers committed
+#[cfg(feature = "full")]
+fn ensure_package_matches_state(
+    state: &SigningState,
+    signing_package: &frost::SigningPackage,
+) -> Result<(), FrostError> {
+    if signing_package.message() != state.message.as_slice() {
+        return Err(FrostError::SigningError("message mismatch".to_string()));
+    }
+    if signing_package.signing_commitments() != build_signing_package_from_state(state)?.signing_commitments() {
+        return Err(FrostError::SigningError(
+            "commitment mismatch: the signing package's commitments are not the ones recorded in round 1".to_string()
+        ));
+    }
+    Ok(())
+}
+
+// Once every signer has a share in the state, aggregate them into the final
+// signature and record it. Returns None while shares are still outstanding.
+#[cfg(feature = "full")]
+fn aggregate_if_complete(
+    state: &mut SigningState,
+    threshold: u16,
+    signing_package: &frost::SigningPackage,
+    group_public_key_json: Option<&str>,
+    timings: &mut Timings,
+) -> Result<Option<String>, FrostError> {
+    ensure_package_matches_state(state, signing_package)?;
+    
+    if state.signature_shares.len() < state.signers.len() {
+        return Ok(None);
+    }
+    
+    let group_public_key_json = group_public_key_json.ok_or(FrostError::MalformedInput(
+        "The group public key is required to aggregate the final signature".to_string()
+    ))?;
+    
+    // Deserialize the group public key package from keygen
+    let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+    
+    // Report a missing quorum as such rather than as a failed aggregation
+    ensure_threshold_met(threshold, state.signature_shares.len())?;
+    
+    // Collect all signature shares under the identifiers recorded in round 1
+    let mut signature_shares = BTreeMap::new();
+    let mut participants = BTreeMap::new();
+    for (participant, share_data) in &state.signature_shares {
+        let share: round2::SignatureShare<Secp256K1Sha256> = serde_json::from_str(share_data)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize share for {}: {}", participant, e)))?;
+        
+        let identifier_hex = state.signer_identifiers.get(participant)
+            .ok_or(FrostError::InvalidParticipant(format!("No identifier recorded for {}", participant)))?;
+        
+        let identifier = identifier_from_hex(identifier_hex)?;
+        signature_shares.insert(identifier, share);
+        participants.insert(identifier, participant.as_str());
+    }
+    check_group_matches_key_packages(state, &group_public_key, signature_shares.keys())?;
+    
+
+    // Fail closed: every share is checked against its signer's commitment
+    // before aggregating, whether or not it was checked on arrival, so a bad
+    // share is named and no signature is produced. Taproot shares are for
+    // the tweaked key; their aggregation names a bad share itself.
+    if state.taproot.is_none() {
+        timings.measure("share_verification", || verify_signature_shares(
+            &signature_shares,
+            signing_package,
+            &group_public_key,
+            |identifier| participants[identifier].to_string(),
+        ))?;
+    }
+    
+    // Aggregate the signature using real FROST
+    let aggregated = timings.measure("aggregation", || match &state.taproot {
+        Some(spend) => taproot::aggregate_with_tweak(
+            signing_package,
+            &signature_shares,
+            &group_public_key,
+            spend,
+            |identifier| participants[identifier].to_string(),
+        ),
+        None => frost::aggregate(signing_package, &signature_shares, &group_public_key)
+            .map_err(|e| FrostError::SigningError(format!("Failed to aggregate signature: {}", e)))
+            .and_then(|group_signature| serde_json::to_string(&group_signature)
+                .map_err(|e| FrostError::SerializationError(format!("Failed to serialize final signature: {}", e)))),
+    });
+    log_event(LogEvent::AggregationAttempted {
+        shares: signature_shares.len(),
+        success: aggregated.is_ok(),
+        ceremony_id: state.ceremony_id.as_deref(),
+    });
+    let final_sig_serialized = aggregated?;
+    
+    state.final_signature = Some(final_sig_serialized.clone());
+    Ok(Some(final_sig_serialized))
+}
+
+// Build the signing package from the commitments recorded in round 1
+#[cfg(feature = "full")]
+fn build_signing_package_from_state(state: &SigningState) -> Result<frost::SigningPackage, FrostError> {
+    Ok(frost::SigningPackage::new(state_commitments(state)?, &state.message))
+}
+
+// The public commitments recorded in round 1, keyed by signer identifier
+#[cfg(feature = "full")]
+fn state_commitments(
+    state: &SigningState,
+) -> Result<BTreeMap<FrostIdentifier, frost::round1::SigningCommitments>, FrostError> {
+    let mut commitments = BTreeMap::new();
+    for (participant, round1_data) in &state.round1_packages {
+        let identifier_hex = state.signer_identifiers.get(participant)
+            .ok_or(FrostError::InvalidParticipant(format!("No identifier recorded for {}", participant)))?;
+        
+        let (_nonces, participant_commitments): (frost::round1::SigningNonces, frost::round1::SigningCommitments) = 
+            serde_json::from_str(round1_data)
+                .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize commitments for {}: {}", participant, e)))?;
+        
+        commitments.insert(identifier_from_hex(identifier_hex)?, participant_commitments);
+    }
+    
+    // A coordinator resumed from a transcript holds commitments without nonces
+    for (identifier_hex, commitment) in &state.transcript_commitments {
+        let commitment = serde_json::from_str(commitment)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize commitment for {}: {}", identifier_hex, e)))?;
+        commitments.insert(identifier_from_hex(identifier_hex)?, commitment);
+    }
+    
+    Ok(commitments)
+}
+
+// Build the signing package from `{ identifier, commitment }` values collected
+// by a coordinator, signing `message`
+#[cfg(feature = "full")]
+fn build_signing_package_from_commitments(
+    message: &[u8],
+    commitments_json: &str,
+) -> Result<frost::SigningPackage, FrostError> {
+    let signer_commitments: Vec<SignerCommitment> = serde_json::from_str(commitments_json)
+        .map_err(|e| FrostError::MalformedInput(format!("Failed to parse commitments: {}", e)))?;
+    
+    let mut commitments = BTreeMap::new();
+    for signer_commitment in signer_commitments {
+        let identifier = identifier_from_hex(&signer_commitment.identifier)?;
+        if commitments.insert(identifier, signer_commitment.commitment).is_some() {
+            return Err(FrostError::InvalidParticipant(
+                format!("Duplicate commitment for identifier {}", signer_commitment.identifier)
+            ));
+        }
+    }
+    
+    Ok(frost::SigningPackage::new(commitments, message))
+}
+
+// === WASM MEMORY OPTIMIZATION ===
+
+// Use wee_alloc as the global allocator for smaller WASM binary size. It does
+// no locking, so threaded (`parallel`) builds keep the default allocator.
+#[cfg(all(feature = "full", not(feature = "parallel")))]
+#[global_allocator]
+static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use super::*;
+    use frost_secp256k1::rand_core::Error as RngError;
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+
+    // Tests that change a process-wide setting hold this lock so they run one
+    // at a time, and put the old value back with a `Restore` guard
+    static GLOBAL_SETTINGS: Mutex<()> = Mutex::new(());
+
+    fn lock_global_settings() -> MutexGuard<'static, ()> {
+        GLOBAL_SETTINGS.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Runs its closure when dropped, so a setting is restored even if the test panics
+    struct Restore<F: FnMut()>(F);
+
+    impl<F: FnMut()> Drop for Restore<F> {
+        fn drop(&mut self) {
+            (self.0)();
+        }
+    }
+
+    // Stands in for a sandbox where crypto.getRandomValues throws
+    struct FailingRng;
+
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0u8; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0u8; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.try_fill_bytes(dest).expect("FailingRng never produces random bytes");
+        }
+
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), RngError> {
+            Err(RngError::new("crypto.getRandomValues is unavailable"))
+        }
+    }
+
+    #[test]
+    fn rng_failure_is_reported_instead_of_panicking() {
+        let result = seeded_rng(&mut FailingRng, None);
+        assert!(matches!(result, Err(FrostError::RngUnavailable(_))));
+    }
+
+    // A host source that "works" but always returns zeroes
+    struct ConstantRng;
+
+    impl RngCore for ConstantRng {
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RngError> {
+            dest.fill(0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rng_probe_rejects_failing_and_stuck_sources() {
+        assert!(matches!(probe_rng(&mut FailingRng), Err(FrostError::RngUnavailable(_))));
+        assert!(matches!(probe_rng(&mut ConstantRng), Err(FrostError::RngUnavailable(_))));
+        assert!(probe_rng(&mut OsRng).is_ok());
+    }
+
+    // Shares dealt from a fixed host seed, so only the extra entropy varies
+    fn shares_with_extra_entropy(extra_entropy: Option<&[u8]>) -> BTreeMap<String, String> {
+        let mut source = StdRng::seed_from_u64(7);
+        let mut rng = seeded_rng(&mut source, extra_entropy).unwrap();
+        deal_shares(2, 3, None, &mut rng).unwrap().shares
+    }
+
+    #[test]
+    fn extra_entropy_changes_the_generated_shares() {
+        let plain = shares_with_extra_entropy(None);
+        let first = shares_with_extra_entropy(Some(b"operator entropy one"));
+        let second = shares_with_extra_entropy(Some(b"operator entropy two"));
+
+        assert_ne!(plain, first);
+        assert_ne!(first, second);
+        assert_eq!(first, shares_with_extra_entropy(Some(b"operator entropy one")));
+    }
+
+    #[cfg(feature = "dangerous-recovery")]
+    #[test]
+    fn reconstructed_secret_matches_the_group_key() {
+        let dealt = deal_shares(2, 3, None, &mut secure_rng().unwrap()).unwrap();
+        let two_shares: BTreeMap<&String, &String> = dealt.shares.iter().take(2).collect();
+
+        let result: FrostResult<String> = serde_json::from_str(
+            &reconstruct_secret(&serde_json::to_string(&two_shares).unwrap(), 2)
+        ).unwrap();
+        let secret = hex::decode(result.data.unwrap()).unwrap();
+        let signing_key = frost::SigningKey::deserialize(&secret).unwrap();
+
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> =
+            serde_json::from_str(&dealt.group_public_key).unwrap();
+        assert_eq!(frost::VerifyingKey::from(&signing_key), *group_public_key.verifying_key());
+    }
+
+    #[cfg(feature = "dangerous-recovery")]
+    #[test]
+    fn reconstruction_rejects_an_empty_set_or_a_zero_threshold() {
+        for threshold in [0, 2] {
+            let result: FrostResult<String> = serde_json::from_str(&reconstruct_secret("{}", threshold)).unwrap();
+            assert_eq!(result.error_code, Some(FrostErrorCode::MalformedInput as u32));
+            assert!(result.data.is_none());
+        }
+    }
+
+    // A 2-of-2 dealer split where participant 1's share comes from another
+    // split but is stamped with this group's key, as a buggy dealer might
+    fn inconsistent_share_set() -> (String, String) {
+        let mut rng = secure_rng().unwrap();
+        let identifiers = [FrostIdentifier::try_from(1).unwrap(), FrostIdentifier::try_from(2).unwrap()];
+        let (shares, group_public_key) = frost::keys::generate_with_dealer(
+            2, 2, IdentifierList::Custom(&identifiers), &mut rng,
+        ).unwrap();
+        let (other_shares, _) = frost::keys::generate_with_dealer(
+            2, 2, IdentifierList::Custom(&identifiers), &mut rng,
+        ).unwrap();
+
+        let mut key_packages = BTreeMap::new();
+        let mut verifying_shares = BTreeMap::new();
+        for identifier in identifiers {
+            let dealt = if identifier == identifiers[0] { &other_shares } else { &shares };
+            let key_package = KeyPackage::try_from(dealt[&identifier].clone()).unwrap();
+            let key_package = KeyPackage::new(
+                identifier,
+                *key_package.signing_share(),
+                *key_package.verifying_share(),
+                *group_public_key.verifying_key(),
+                2,
+            );
+            verifying_shares.insert(identifier, *key_package.verifying_share());
+            key_packages.insert(identifier_to_hex(&identifier), serde_json::to_string(&key_package).unwrap());
+        }
+        let group_public_key = PublicKeyPackage::new(verifying_shares, *group_public_key.verifying_key());
+
+        (serde_json::to_string(&key_packages).unwrap(), serde_json::to_string(&group_public_key).unwrap())
+    }
+
+    #[test]
+    fn share_sets_must_interpolate_to_the_group_key() {
+        let dealt = deal_shares(3, 5, None, &mut secure_rng().unwrap()).unwrap();
+        let result: FrostResult<ShareSetValidation> = serde_json::from_str(&validate_share_set(
+            &serde_json::to_string(&dealt.shares).unwrap(),
+            &dealt.group_public_key,
+            3,
+        )).unwrap();
+        let validation = result.data.unwrap();
+        assert_eq!(validation.subsets_checked.len(), 2);
+        assert!(validation.subsets_checked.iter().all(|subset| subset.len() == 3));
+        let covered: BTreeSet<&String> = validation.subsets_checked.iter().flatten().collect();
+        assert_eq!(covered, dealt.shares.keys().collect::<BTreeSet<_>>());
+
+        let (shares_json, group_public_key_json) = inconsistent_share_set();
+        let result: FrostResult<ShareSetValidation> =
+            serde_json::from_str(&validate_share_set(&shares_json, &group_public_key_json, 2)).unwrap();
+        assert!(!result.success);
+        assert_eq!(result.error_code, Some(FrostErrorCode::IntegrityError as u32));
+    }
+
+    #[test]
+    fn share_sets_reject_a_signing_share_that_misses_its_verifying_share() {
+        let mut rng = secure_rng().unwrap();
+        let (shares, group_public_key) = frost::keys::generate_with_dealer(
+            2, 3, IdentifierList::Default, &mut rng,
+        ).unwrap();
+        let (other_shares, _) = frost::keys::generate_with_dealer(
+            2, 3, IdentifierList::Default, &mut rng,
+        ).unwrap();
+
+        // The corrupted package keeps the group's verifying share, so the
+        // public values all interpolate to the group key
+        let mut key_packages = BTreeMap::new();
+        for (index, (identifier, share)) in shares.into_iter().enumerate() {
+            let mut key_package = KeyPackage::try_from(share).unwrap();
+            if index == 2 {
+                let other = KeyPackage::try_from(other_shares[&identifier].clone()).unwrap();
+                key_package = KeyPackage::new(
+                    identifier,
+                    *other.signing_share(),
+                    *key_package.verifying_share(),
+                    *key_package.verifying_key(),
+                    2,
+                );
+            }
+            key_packages.insert(format!("participant_{}", index + 1), serde_json::to_string(&key_package).unwrap());
+        }
+
+        let result: FrostResult<ShareSetValidation> = serde_json::from_str(&validate_share_set(
+            &serde_json::to_string(&key_packages).unwrap(),
+            &serde_json::to_string(&group_public_key).unwrap(),
+            2,
+        )).unwrap();
+        assert_eq!(result.error_code, Some(FrostErrorCode::IntegrityError as u32));
+        assert!(result.error.unwrap().contains("participant_3"));
+    }
+
+    // Run keygen round 1 through the exports, returning the state JSON
+    fn keygen_round1_state(threshold: u16, participants: &[&str]) -> String {
+        let mut state_json = create_keygen_state(threshold, participants.len() as u16, None, None, None);
+        for participant in participants {
+            let result: FrostResult<(KeygenState, String)> =
+                serde_json::from_str(&keygen_round1(&state_json, participant, None, None, None)).unwrap();
+            state_json = serde_json::to_string(&FrostResult::ok(result.data.unwrap().0)).unwrap();
+        }
+        state_json
+    }
+
+    // Finish the DKG from a round 1 state with frost directly, returning the group key
+    fn dkg_group_key(state_json: &str) -> PublicKeyPackage<Secp256K1Sha256> {
+        let state: FrostResult<KeygenState> = serde_json::from_str(state_json).unwrap();
+        let state = state.data.unwrap();
+
+        let mut secrets = BTreeMap::new();
+        let mut packages = BTreeMap::new();
+        for (participant, round1_data) in &state.round1_packages {
+            let identifier = identifier_from_hex(&state.participant_identifiers[participant]).unwrap();
+            let entry = read_round1_entry(round1_data, participant, &mut Vec::new()).unwrap();
+            secrets.insert(identifier, entry.secret);
+            packages.insert(identifier, entry.package);
+        }
+
+        let others = |identifier: &FrostIdentifier| -> BTreeMap<_, _> {
+            packages.iter()
+                .filter(|(other, _)| *other != identifier)
+                .map(|(other, package)| (*other, package.clone()))
+                .collect()
+        };
+
+        let mut round2_secrets = BTreeMap::new();
+        let mut round2_received: BTreeMap<FrostIdentifier, BTreeMap<_, _>> = BTreeMap::new();
+        for (identifier, secret) in secrets {
+            let (round2_secret, outgoing) = dkg::part2(secret, &others(&identifier)).unwrap();
+            for (recipient, package) in outgoing {
+                round2_received.entry(recipient).or_default().insert(identifier, package);
+            }
+            round2_secrets.insert(identifier, round2_secret);
+        }
+
+        let (identifier, round2_secret) = round2_secrets.iter().next().unwrap();
+        let (_key_package, group_public_key) =
+            dkg::part3(round2_secret, &others(identifier), &round2_received[identifier]).unwrap();
+        group_public_key
+    }
+
+    #[test]
+    fn dkg_transcript_verifies_against_the_generated_group_key() {
+        let state_json = keygen_round1_state(2, &["alice", "bob", "carol"]);
+        let group_public_key = serde_json::to_string(&dkg_group_key(&state_json)).unwrap();
+
+        let transcript: FrostResult<serde_json::Value> =
+            serde_json::from_str(&dkg_public_transcript(&state_json)).unwrap();
+        let transcript_json = transcript.data.unwrap().to_string();
+        assert!(!transcript_json.contains("coefficients"));
+
+        let verified: FrostResult<bool> =
+            serde_json::from_str(&verify_dkg_transcript(&transcript_json, &group_public_key)).unwrap();
+        assert_eq!(verified.data, Some(true));
+
+        let other_key = serde_json::to_string(&dkg_group_key(&keygen_round1_state(2, &["alice", "bob", "carol"]))).unwrap();
+        let verified: FrostResult<bool> =
+            serde_json::from_str(&verify_dkg_transcript(&transcript_json, &other_key)).unwrap();
+        assert_eq!(verified.data, Some(false));
+    }
+
+    #[test]
+    fn dkg_transcript_rejects_stripped_shares_and_a_wrong_threshold() {
+        let state_json = keygen_round1_state(2, &["alice", "bob", "carol"]);
+        let group_public_key = dkg_group_key(&state_json);
+        let transcript: FrostResult<serde_json::Value> =
+            serde_json::from_str(&dkg_public_transcript(&state_json)).unwrap();
+        let transcript = transcript.data.unwrap();
+        let verifies = |transcript: &serde_json::Value, group_public_key: &PublicKeyPackage<Secp256K1Sha256>| {
+            let verified: FrostResult<bool> = serde_json::from_str(&verify_dkg_transcript(
+                &transcript.to_string(),
+                &serde_json::to_string(group_public_key).unwrap(),
+            )).unwrap();
+            verified.data.unwrap()
+        };
+        assert!(verifies(&transcript, &group_public_key));
+
+        // A group package missing verifying shares, or with none at all
+        let mut stripped = group_public_key.verifying_shares().clone();
+        stripped.pop_first();
+        assert!(!verifies(&transcript, &PublicKeyPackage::new(stripped, *group_public_key.verifying_key())));
+        assert!(!verifies(&transcript, &PublicKeyPackage::new(BTreeMap::new(), *group_public_key.verifying_key())));
+
+        // Commitments of two coefficients don't belong to a 3-of-3 transcript
+        let mut wrong_threshold = transcript.clone();
+        wrong_threshold["threshold"] = serde_json::json!(3);
+        assert!(!verifies(&wrong_threshold, &group_public_key));
+
+        // The participant list must match the packages
+        let mut missing_participant = transcript.clone();
+        missing_participant["participants"].as_object_mut().unwrap().remove("carol");
+        assert!(!verifies(&missing_participant, &group_public_key));
+    }
+
+    #[test]
+    fn participant_counts_past_u16_are_rejected() {
+        assert_eq!(participant_count(u16::MAX as usize).unwrap(), u16::MAX);
+        assert!(matches!(
+            participant_count(u16::MAX as usize + 1),
+            Err(FrostError::InvalidParticipant(_))
+        ));
+    }
+
+    #[test]
+    fn oversized_round1_maps_do_not_wrap_the_count() {
+        let _settings = lock_global_settings();
+        let limits = (
+            MAX_JSON_BYTES.load(Ordering::Relaxed),
+            MAX_MESSAGE_BYTES.load(Ordering::Relaxed),
+            MAX_PARTICIPANTS.load(Ordering::Relaxed),
+        );
+        let _restore = Restore(move || set_input_limits(limits.0, limits.1, limits.2));
+        set_input_limits(16 * 1024 * 1024, 64 * 1024, 255);
+
+        // 65536 entries would cast to a count of 0
+        let round1_packages: BTreeMap<String, String> = (0..=u16::MAX as usize)
+            .map(|i| (format!("p{}", i), String::new()))
+            .collect();
+        let state = KeygenState {
+            kind: CeremonyKind::Keygen,
+            threshold: 2,
+            max_participants: 3,
+            current_round: 2,
+            round1_packages: round1_packages.clone(),
+            key_packages: BTreeMap::new(),
+            group_public_key: None,
+            participant_identifiers: BTreeMap::new(),
+            ceremony_id: None,
+            created_at: None,
+            expires_at: None,
+            commit_reveal: false,
+            round1_commitments: BTreeMap::new(),
+            removed_participants: BTreeSet::new(),
+        };
+        let state_json = serde_json::to_string(&FrostResult::ok(state)).unwrap();
+        let round1_packages_json = serde_json::to_string(&round1_packages).unwrap();
+
+        let result: FrostResult<serde_json::Value> = serde_json::from_str(
+            &keygen_round2(&state_json, "p0", &round1_packages_json)
+        ).unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.error_code, Some(FrostErrorCode::InvalidParticipant as u32));
+    }
+
+    // Unwrap the data of an export's FrostResult
+    fn export_data<T: serde::de::DeserializeOwned>(result_json: &str) -> T {
+        let result: FrostResult<T> = serde_json::from_str(result_json).unwrap();
+        result.data.unwrap_or_else(|| panic!("{:?}", result.error))
+    }
+
+    // A finished keygen state holding a dealer's shares for alice and bob
+    fn completed_keygen_state() -> String {
+        let dealt = deal_shares(2, 2, None, &mut secure_rng().unwrap()).unwrap();
+        let labels = ["alice", "bob"];
+        let state = KeygenState {
+            kind: CeremonyKind::Keygen,
+            threshold: 2,
+            max_participants: 2,
+            current_round: 2,
+            round1_packages: BTreeMap::new(),
+            key_packages: labels.iter().map(|label| label.to_string()).zip(dealt.shares.into_values()).collect(),
+            group_public_key: Some(dealt.group_public_key),
+            participant_identifiers: BTreeMap::new(),
+            ceremony_id: None,
+            created_at: None,
+            expires_at: None,
+            commit_reveal: false,
+            round1_commitments: BTreeMap::new(),
+            removed_participants: BTreeSet::new(),
+        };
+        serde_json::to_string(&FrostResult::ok(state)).unwrap()
+    }
+
+    #[test]
+    fn non_contiguous_identifiers_sign_and_verify() {
+        let identifiers: Vec<FrostIdentifier> = [5u16, 17, 42].into_iter()
+            .map(|i| FrostIdentifier::try_from(i).unwrap())
+            .collect();
+        let (shares, group_public_key) = frost::keys::generate_with_dealer(
+            3, 2, IdentifierList::Custom(&identifiers), &mut secure_rng().unwrap(),
+        ).unwrap();
+        let group_public_key = serde_json::to_string(&group_public_key).unwrap();
+        let wrapped = |state: &SigningState| serde_json::to_string(&FrostResult::ok(state.clone())).unwrap();
+
+        // Sign with identifiers 42 and 5, out of label order
+        let signers = [("carol", identifiers[2]), ("alice", identifiers[0])];
+        let message = b"non-contiguous identifiers";
+        let mut state: SigningState = export_data(&create_signing_state(message, r#"["carol","alice"]"#, None, None, None));
+        for (signer, identifier) in &signers {
+            let share = serde_json::to_string(&shares[identifier]).unwrap();
+            state = export_data::<(SigningState, SignerCommitment)>(&signing_round1(&wrapped(&state), signer, &share, None)).0;
+        }
+        assert_eq!(state.signer_identifiers["carol"], identifier_to_hex(&identifiers[2]));
+
+        let signing_package: String = export_data(&build_signing_package(&wrapped(&state), None));
+        for (signer, identifier) in &signers {
+            let share = serde_json::to_string(&shares[identifier]).unwrap();
+            state = export_data::<(SigningState, Option<String>)>(&signing_round2(
+                &wrapped(&state), signer, &share, &signing_package, Some(group_public_key.clone()),
+            )).0;
+        }
+
+        let signature = state.final_signature.unwrap();
+        assert!(export_data::<bool>(&verify_signature(message, &signature, &group_public_key)));
+    }
+
+    #[test]
+    fn build_info_reports_the_locked_frost_versions() {
+        let build_info: BuildInfo = export_data(&build_info());
+
+        // Cargo.toml asks for "2.1.0", a caret range, so any locked 2.x release qualifies
+        let is_locked_2x = |version: &str| {
+            let release = version.split(['-', '+']).next().unwrap_or_default();
+            let parts: Vec<&str> = release.split('.').collect();
+            parts.len() == 3
+                && parts[0] == "2"
+                && parts.iter().all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        };
+        assert!(is_locked_2x(&build_info.frost_core), "{}", build_info.frost_core);
+        assert!(is_locked_2x(&build_info.frost_secp256k1), "{}", build_info.frost_secp256k1);
+        assert_eq!(build_info.frost_core, env!("FROST_CORE_VERSION"));
+        assert_eq!(build_info.ciphersuite, "FROST-secp256k1-SHA256-v1");
+        assert!(build_info.features.contains(&"full".to_string()));
+        assert!(!build_info.git_commit.is_empty());
+    }
+
+    #[test]
+    fn hex_outputs_are_big_endian() {
+        // Identifiers are SerializeScalar: 32 bytes, most significant first
+        let one = FrostIdentifier::try_from(1u16).unwrap();
+        assert_eq!(identifier_to_hex(&one), format!("{}01", "00".repeat(31)));
+        let larger = FrostIdentifier::try_from(0x0102u16).unwrap();
+        assert_eq!(identifier_to_hex(&larger), format!("{}0102", "00".repeat(30)));
+        assert_eq!(identifier_from_hex(&identifier_to_hex(&larger)).unwrap(), larger);
+
+        // Points are compressed SEC1 (parity byte, then big-endian x), and the
+        // x-only key drops the parity byte. 3G is BIP340 test vector 0's key.
+        let (_, group_public_key) = single_signer_keys(one, &format!("{}03", "00".repeat(31))).unwrap();
+        let x = "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9";
+        assert_eq!(hex::encode(group_public_key.verifying_key().serialize().unwrap()), format!("02{}", x));
+        assert_eq!(hex::encode(group_xonly_pubkey(&group_public_key).unwrap()), x);
+
+        // A FROST signature is SerializeElement(R) || SerializeScalar(z)
+        let generator = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let signature = frost::Signature::new(k256::ProjectivePoint::GENERATOR, k256::Scalar::ONE);
+        let expected = format!("{}{}01", generator, "00".repeat(31));
+        assert_eq!(hex::encode(signature.serialize().unwrap()), expected);
+        assert_eq!(serde_json::to_string(&signature).unwrap(), format!("\"{}\"", expected));
+
+        // A BIP340 signature is x(R) || s, both big-endian (test vector 0)
+        let bip340: String = export_data(&nostr::sign_schnorr(&[0; 32], &format!("{}03", "00".repeat(31)), Some(vec![0; 32])));
+        assert_eq!(
+            bip340,
+            "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+             25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+        );
+    }
+
+    #[test]
+    fn parallel_and_serial_share_verification_agree() {
+        let labels: Vec<String> = (0..20).map(|i| format!("signer{:02}", i)).collect();
+        let dealt = deal_shares(20, 20, Some(&labels), &mut secure_rng().unwrap()).unwrap();
+        let wrapped = |state: &SigningState| serde_json::to_string(&FrostResult::ok(state.clone())).unwrap();
+        let mut state: SigningState = export_data(&create_signing_state(
+            b"large group", &serde_json::to_string(&labels).unwrap(), None, None, None,
+        ));
+        for signer in &labels {
+            let (next, _): (SigningState, SignerCommitment) =
+                export_data(&signing_round1(&wrapped(&state), signer, &dealt.shares[signer], None));
+            state = next;
+        }
+        let signing_package: String = export_data(&build_signing_package(&wrapped(&state), None));
+        for signer in &labels {
+            let (next, _): (SigningState, Option<String>) = export_data(&signing_round2(
+                &wrapped(&state), signer, &dealt.shares[signer], &signing_package, Some(dealt.group_public_key.clone()),
+            ));
+            state = next;
+        }
+        let mut tampered = state.clone();
+        tampered.signature_shares.insert("signer07".to_string(), state.signature_shares["signer12"].clone());
+        tampered.signature_shares.insert("signer15".to_string(), state.signature_shares["signer03"].clone());
+
+        let aggregate = |state: &SigningState| aggregate_signature(&wrapped(state), &signing_package, &dealt.group_public_key);
+        let _settings = lock_global_settings();
+        let parallel = PARALLEL_VERIFICATION.load(Ordering::Relaxed);
+        let _restore = Restore(move || set_parallel_verification(parallel));
+        set_parallel_verification(false);
+        let (serial_valid, serial_tampered) = (aggregate(&state), aggregate(&tampered));
+        set_parallel_verification(true);
+        let (parallel_valid, parallel_tampered) = (aggregate(&state), aggregate(&tampered));
+
+        assert_eq!(serial_valid, parallel_valid);
+        assert_eq!(serial_tampered, parallel_tampered);
+        let rejected: FrostResult<serde_json::Value> = serde_json::from_str(&parallel_tampered).unwrap();
+        assert!(rejected.error.unwrap().contains("Invalid signature share from signer07"));
+    }
+
+    #[test]
+    fn mnemonics_with_an_impossible_threshold_are_rejected() {
+        let _settings = lock_global_settings();
+        let dealt = deal_shares(2, 3, None, &mut secure_rng().unwrap()).unwrap();
+        let mnemonic: String = export_data(&share_to_mnemonic(dealt.shares.values().next().unwrap()));
+        let bytes = mnemonic_to_bytes(&mnemonic, SHARE_MNEMONIC_BYTES).unwrap();
+
+        // The participant limit defaults to 255
+        for threshold in [0u16, 256] {
+            let mut bytes = bytes.clone();
+            bytes[98..].copy_from_slice(&threshold.to_be_bytes());
+            let result: FrostResult<String> = serde_json::from_str(&share_from_mnemonic(&mnemonic_from_bytes(&bytes))).unwrap();
+            assert_eq!(result.error_code, Some(FrostErrorCode::MalformedInput as u32));
+            assert!(result.error.unwrap().contains(&format!("Invalid threshold {}", threshold)));
+        }
+    }
+
+    #[test]
+    fn pooled_results_match_unpooled_and_leave_an_empty_buffer() {
+        let result = FrostResult::ok(export_data::<KeygenState>(&completed_keygen_state()));
+        let unpooled = serde_json::to_string(&result).unwrap();
+
+        let _settings = lock_global_settings();
+        let pooling = BUFFER_POOLING.load(Ordering::Relaxed);
+        let _restore = Restore(move || set_buffer_pooling(pooling));
+        set_buffer_pooling(true);
+        assert_eq!(result_json(&result), unpooled);
+        assert_eq!(result_json(&result), unpooled);
+        SERIALIZATION_BUFFER.with(|buffer| {
+            let buffer = buffer.borrow();
+            assert!(buffer.is_empty());
+            assert!(buffer.capacity() >= unpooled.len());
+        });
+
+        set_buffer_pooling(false);
+        SERIALIZATION_BUFFER.with(|buffer| assert_eq!(buffer.borrow().capacity(), 0));
+    }
+}
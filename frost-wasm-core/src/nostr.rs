@@ -0,0 +1,738 @@
+// ABOUTME: Nostr event hashing and BIP340 verification against the FROST group key
+// ABOUTME: Lets relays validate a threshold-signed event in one call
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use k256::schnorr;
+use bech32::{primitives::decode::CheckedHrpstring, Bech32, Hrp};
+#[cfg(feature = "full")]
+use frost_secp256k1::rand_core::RngCore;
+#[cfg(feature = "full")]
+use zeroize::Zeroize;
+
+use frost_secp256k1::Secp256K1Sha256;
+use frost_core::keys::PublicKeyPackage;
+
+use crate::encoding::hex_to_bytes;
+use crate::{check_input_sizes, check_message_size, check_present, FrostError, FrostResult};
+#[cfg(feature = "full")]
+use crate::secure_rng;
+
+// A NIP-01 event. `id`, `sig`, and possibly `pubkey` are absent on unsigned
+// templates.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NostrEvent {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub pubkey: String,
+    pub created_at: u64,
+    pub kind: u32,
+    pub tags: Vec<Vec<String>>,
+    pub content: String,
+    #[serde(default)]
+    pub sig: String,
+}
+
+// Outcome of each check performed on an event
+#[derive(Serialize, Deserialize)]
+pub struct NostrEventVerification {
+    pub id_matches: bool,
+    pub pubkey_matches: bool,
+    pub signature_valid: bool,
+    pub valid: bool,
+}
+
+/// Verify a signed Nostr event against the FROST group public key
+///
+/// Recomputes the event id, checks the event pubkey is the group key's x-only
+/// form, and verifies the BIP340 signature over the recomputed id. Each check
+/// is reported separately so callers can tell a tampered event from a bad sig.
+#[wasm_bindgen]
+pub fn verify_nostr_event(event_json: &str, group_public_key_json: &str) -> String {
+    let result = (|| -> Result<NostrEventVerification, FrostError> {
+        check_input_sizes(&[
+            ("event_json", event_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let event: NostrEvent = serde_json::from_str(event_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize event: {}", e)))?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        let event_id = event_id(&event)?;
+        let id_matches = hex::encode(event_id) == event.id.to_lowercase();
+        
+        let event_pubkey = decode_fixed::<32>(&event.pubkey, "pubkey")?;
+        let pubkey_matches = event_pubkey == crate::group_xonly_pubkey(&group_public_key)?;
+        
+        let signature = decode_fixed::<64>(&event.sig, "sig")?;
+        let signature_valid = verify_bip340(&event_id, &signature, &event_pubkey);
+        
+        Ok(NostrEventVerification {
+            id_matches,
+            pubkey_matches,
+            signature_valid,
+            valid: id_matches && pubkey_matches && signature_valid,
+        })
+    })();
+    
+    match result {
+        Ok(verification) => {
+            serde_json::to_string(&FrostResult::ok(verification)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<NostrEventVerification>::err(e)).unwrap()
+        }
+    }
+}
+
+/// The exact NIP-01 serialization of an event, whose SHA-256 is its id
+///
+/// `[0,pubkey,created_at,kind,tags,content]` with no whitespace. Strings are
+/// UTF-8 with only line feed, double quote, backslash, carriage return, tab,
+/// backspace, and form feed escaped (as `\n`, `\"`, `\\`, `\r`, `\t`, `\b`,
+/// `\f`); everything else, other control characters and emoji included, is
+/// written verbatim. Escapes in `event_json` itself are decoded first, so a
+/// client's `\u00e9` and a literal `é` give the same serialization. Event
+/// ids computed anywhere in this module use this serialization.
+#[wasm_bindgen]
+pub fn canonicalize_event(event_json: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("event_json", event_json)])?;
+        
+        let event: NostrEvent = serde_json::from_str(event_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize event: {}", e)))?;
+        
+        Ok(canonical_serialization(&event))
+    })();
+    
+    match result {
+        Ok(serialized) => {
+            serde_json::to_string(&FrostResult::ok(serialized)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Fill in an event template's `pubkey`, `id`, and `sig` and check the result
+///
+/// `signature_64_hex` is the BIP340 signature over the event id, such as the
+/// final signature of a ceremony that signed the id. The id is recomputed
+/// from the template with `xonly_pubkey_hex` as its pubkey, and the signature
+/// must verify over it, so only a ready-to-publish event is returned. A
+/// template that already names a different pubkey is rejected.
+#[wasm_bindgen]
+pub fn finalize_nostr_event(event_template_json: &str, signature_64_hex: &str, xonly_pubkey_hex: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("event_template_json", event_template_json),
+            ("signature_64_hex", signature_64_hex),
+            ("xonly_pubkey_hex", xonly_pubkey_hex),
+        ])?;
+        
+        let mut event: NostrEvent = serde_json::from_str(event_template_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize event template: {}", e)))?;
+        let xonly_pubkey = decode_fixed::<32>(xonly_pubkey_hex, "pubkey")?;
+        let signature = decode_fixed::<64>(signature_64_hex, "signature")?;
+        
+        let pubkey_hex = hex::encode(xonly_pubkey);
+        if !event.pubkey.is_empty() && event.pubkey.to_lowercase() != pubkey_hex {
+            return Err(FrostError::MalformedInput(
+                format!("Event template pubkey {} does not match {}", event.pubkey, pubkey_hex)
+            ));
+        }
+        event.pubkey = pubkey_hex;
+        
+        let event_id = event_id(&event)?;
+        if !verify_bip340(&event_id, &signature, &xonly_pubkey) {
+            return Err(FrostError::SigningError(
+                format!("Signature does not verify over event id {}", hex::encode(event_id))
+            ));
+        }
+        event.id = hex::encode(event_id);
+        event.sig = hex::encode(signature);
+        
+        serde_json::to_string(&event)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize event: {}", e)))
+    })();
+    
+    match result {
+        Ok(event) => {
+            serde_json::to_string(&FrostResult::ok(event)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Strict BIP340 verification of a 64-byte signature under a 32-byte x-only pubkey
+///
+/// The pubkey is lifted to the point with even y, as relays do, so this gives
+/// the same answer as libsecp256k1's `schnorrsig_verify`.
+#[wasm_bindgen]
+pub fn verify_signature_xonly(message: &[u8], signature_hex: &str, xonly_pubkey_hex: &str) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[
+            ("signature_hex", signature_hex),
+            ("xonly_pubkey_hex", xonly_pubkey_hex),
+        ])?;
+        check_message_size(message)?;
+        
+        let signature = decode_fixed::<64>(signature_hex, "signature")?;
+        let xonly_pubkey = decode_fixed::<32>(xonly_pubkey_hex, "pubkey")?;
+        
+        Ok(verify_bip340(message, &signature, &xonly_pubkey))
+    })();
+    
+    match result {
+        Ok(valid) => {
+            serde_json::to_string(&FrostResult::ok(valid)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Strict BIP340 verification of a 64-byte signature under a NIP-19 `npub`
+///
+/// Decodes the npub to its 32-byte x-only pubkey and verifies as
+/// `verify_signature_xonly` does. Anything but a bech32 string with the
+/// `npub` prefix and a 32-byte payload is `MalformedInput`.
+#[wasm_bindgen]
+pub fn verify_signature_npub(message: &[u8], signature_64_hex: &str, npub: &str) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[
+            ("signature_64_hex", signature_64_hex),
+            ("npub", npub),
+        ])?;
+        check_message_size(message)?;
+        
+        let signature = decode_fixed::<64>(signature_64_hex, "signature")?;
+        let xonly_pubkey = decode_npub(npub)?;
+        
+        Ok(verify_bip340(message, &signature, &xonly_pubkey))
+    })();
+    
+    match result {
+        Ok(valid) => {
+            serde_json::to_string(&FrostResult::ok(valid)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Single-key BIP340 signature of `message` under a 32-byte secret key
+///
+/// `aux_rand` is the 32 bytes of auxiliary randomness BIP340 mixes into the
+/// nonce; given the same key, message, and aux_rand the signature matches
+/// other BIP340 implementations byte for byte. When omitted, fresh randomness
+/// is drawn from the host's secure source.
+#[cfg(feature = "full")]
+#[wasm_bindgen]
+pub fn sign_schnorr(message: &[u8], secret_key_hex: &str, aux_rand: Option<Vec<u8>>) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("secret_key_hex", secret_key_hex)])?;
+        check_message_size(message)?;
+        
+        let aux_rand: [u8; 32] = match aux_rand {
+            Some(aux_rand) => aux_rand.as_slice().try_into().map_err(|_| FrostError::MalformedInput(
+                format!("Expected aux_rand to be 32 bytes, got {}", aux_rand.len())
+            ))?,
+            None => {
+                let mut aux_rand = [0u8; 32];
+                secure_rng()?.fill_bytes(&mut aux_rand);
+                aux_rand
+            }
+        };
+        
+        let mut secret_key = decode_fixed::<32>(secret_key_hex, "secret key")?;
+        let signing_key = schnorr::SigningKey::from_bytes(&secret_key)
+            .map_err(|_| FrostError::MalformedInput("Secret key is not a valid scalar".to_string()));
+        secret_key.zeroize();
+        
+        let signature = signing_key?.sign_raw(message, &aux_rand)
+            .map_err(|e| FrostError::SigningError(format!("BIP340 signing failed: {}", e)))?;
+        
+        Ok(hex::encode(signature.to_bytes()))
+    })();
+    
+    match result {
+        Ok(signature) => {
+            serde_json::to_string(&FrostResult::ok(signature)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// BIP340 tagged hash `sha256(sha256(tag) || sha256(tag) || message)`, hex encoded
+#[wasm_bindgen]
+pub fn tagged_hash(tag: &str, message: &[u8]) -> String {
+    let result = check_input_sizes(&[("tag", tag)])
+        .and_then(|_| check_message_size(message))
+        .map(|_| hex::encode(bip340_tagged_hash(tag, message)));
+    
+    match result {
+        Ok(hash) => {
+            serde_json::to_string(&FrostResult::ok(hash)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Plain SHA-256 of `message`, hex encoded (a Nostr event id is the SHA-256 of
+/// its serialized form)
+#[wasm_bindgen]
+pub fn sha256(message: &[u8]) -> String {
+    let result = check_message_size(message)
+        .map(|_| hex::encode(Sha256::digest(message)));
+    
+    match result {
+        Ok(hash) => {
+            serde_json::to_string(&FrostResult::ok(hash)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// A NIP-46 response, before it is NIP-44 encrypted into a kind 24133 event
+#[derive(Serialize, Deserialize)]
+struct Nip46Response {
+    id: String,
+    result: String,
+}
+
+/// The NIP-46 response to a `connect` request, as the JSON to encrypt to the client
+///
+/// `request_id` is the id of the client's `connect` request. `secret` is the
+/// secret from a `nostrconnect://` URI, which NIP-46 has the signer return as
+/// the result so the client can tell the response is genuine; without one the
+/// result is `"ack"`. Only the content is built: encrypting it to
+/// `client_pubkey` and wrapping it in a kind 24133 event is the transport's job.
+#[wasm_bindgen]
+pub fn nip46_connect_ack(request_id: &str, client_pubkey: &str, secret: Option<String>) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("request_id", request_id),
+            ("client_pubkey", client_pubkey),
+            ("secret", secret.as_deref().unwrap_or_default()),
+        ])?;
+        check_present(&[("request_id", request_id)])?;
+        
+        let client_pubkey = decode_fixed::<32>(client_pubkey, "client pubkey")?;
+        schnorr::VerifyingKey::from_bytes(&client_pubkey)
+            .map_err(|_| FrostError::MalformedInput("Client pubkey is not a valid x-only key".to_string()))?;
+        
+        let result = match secret {
+            Some(secret) if secret.is_empty() => {
+                return Err(FrostError::MalformedInput("secret is empty".to_string()));
+            }
+            Some(secret) => secret,
+            None => "ack".to_string(),
+        };
+        
+        serde_json::to_string(&Nip46Response { id: request_id.to_string(), result })
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize response: {}", e)))
+    })();
+    
+    match result {
+        Ok(content) => {
+            serde_json::to_string(&FrostResult::ok(content)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// How a group key appears as a Nostr pubkey. `parity_adjustment_needed` is
+// true for an odd-y key: BIP340 verifiers lift the x-only key to even y, so
+// signatures must be made for the negated key.
+#[derive(Serialize, Deserialize)]
+pub struct NostrCompatibility {
+    pub xonly_pubkey: String,
+    pub parity_adjustment_needed: bool,
+}
+
+/// Check the group verifying key can be used as a Nostr (BIP340 x-only) pubkey
+///
+/// Returns the x-only pubkey and whether the key has odd y. Trusted dealer
+/// splits always produce even-y keys; a DKG group key may have either parity,
+/// because its transcript and preview commit to the key as generated.
+#[wasm_bindgen]
+pub fn assert_nostr_compatible(group_public_key_json: &str) -> String {
+    let result = (|| -> Result<NostrCompatibility, FrostError> {
+        check_input_sizes(&[("group_public_key_json", group_public_key_json)])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        let compressed = group_public_key.verifying_key().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?;
+        let xonly_pubkey = crate::group_xonly_pubkey(&group_public_key)?;
+        
+        schnorr::VerifyingKey::from_bytes(&xonly_pubkey)
+            .map_err(|_| FrostError::MalformedInput("The group key has no BIP340 x-only form".to_string()))?;
+        
+        Ok(NostrCompatibility {
+            xonly_pubkey: hex::encode(xonly_pubkey),
+            parity_adjustment_needed: compressed[0] == 0x03,
+        })
+    })();
+    
+    match result {
+        Ok(compatibility) => {
+            serde_json::to_string(&FrostResult::ok(compatibility)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<NostrCompatibility>::err(e)).unwrap()
+        }
+    }
+}
+
+// First line of every attestation message, naming the format and its version
+const ATTESTATION_HEADER: &str = "nip05-attestation/v1";
+
+/// The canonical message a group signs to attest that `xonly_pubkey_hex`
+/// belongs to `domain`, NIP-05 style
+///
+/// The message is three newline-separated lines: the format header, the
+/// domain in lowercase, and the pubkey as lowercase hex. Domains are
+/// case-insensitive, so `Example.COM` attests the same thing as `example.com`;
+/// anything that isn't a plain DNS name is rejected rather than normalized.
+/// Sign the UTF-8 bytes of the returned string in a signing ceremony, and
+/// check the result with `verify_attestation`.
+#[wasm_bindgen]
+pub fn build_attestation_message(domain: &str, xonly_pubkey_hex: &str) -> String {
+    let result = check_input_sizes(&[
+        ("domain", domain),
+        ("xonly_pubkey_hex", xonly_pubkey_hex),
+    ]).and_then(|_| attestation_message(domain, xonly_pubkey_hex));
+    
+    match result {
+        Ok(message) => {
+            serde_json::to_string(&FrostResult::ok(message)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Verify a group's FROST signature over the attestation for `domain` and
+/// `xonly_pubkey_hex`
+///
+/// Rebuilds the canonical message with `build_attestation_message`, so a
+/// verifier needs only the domain, the pubkey, and the signature.
+#[wasm_bindgen]
+pub fn verify_attestation(
+    domain: &str,
+    xonly_pubkey_hex: &str,
+    signature_json: &str,
+    group_public_key_json: &str
+) -> String {
+    let result = (|| -> Result<bool, FrostError> {
+        check_input_sizes(&[
+            ("domain", domain),
+            ("xonly_pubkey_hex", xonly_pubkey_hex),
+            ("signature_json", signature_json),
+            ("group_public_key_json", group_public_key_json),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+        
+        let message = attestation_message(domain, xonly_pubkey_hex)?;
+        
+        let signature: frost_core::Signature<Secp256K1Sha256> = serde_json::from_str(signature_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize signature: {}", e)))?;
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        
+        Ok(group_public_key.verifying_key().verify(message.as_bytes(), &signature).is_ok())
+    })();
+    
+    match result {
+        Ok(valid) => {
+            serde_json::to_string(&FrostResult::ok(valid)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<bool>::err(e)).unwrap()
+        }
+    }
+}
+
+fn attestation_message(domain: &str, xonly_pubkey_hex: &str) -> Result<String, FrostError> {
+    let domain = domain.to_ascii_lowercase();
+    let valid_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && label.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+    if domain.len() > 253 || !domain.contains('.') || !domain.split('.').all(valid_label) {
+        return Err(FrostError::MalformedInput(format!("{:?} is not a valid domain name", domain)));
+    }
+    
+    let xonly_pubkey = decode_fixed::<32>(xonly_pubkey_hex, "pubkey")?;
+    
+    Ok(format!("{}\n{}\n{}", ATTESTATION_HEADER, domain, hex::encode(xonly_pubkey)))
+}
+
+pub(crate) fn bip340_tagged_hash(tag: &str, message: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    Sha256::new()
+        .chain_update(tag_hash)
+        .chain_update(tag_hash)
+        .chain_update(message)
+        .finalize()
+        .into()
+}
+
+// NIP-01 event id: sha256 of the event's canonical serialization
+pub(crate) fn event_id(event: &NostrEvent) -> Result<[u8; 32], FrostError> {
+    Ok(Sha256::digest(canonical_serialization(event).as_bytes()).into())
+}
+
+// `[0,pubkey,created_at,kind,tags,content]` with no whitespace, each string
+// escaped by NIP-01's rules
+fn canonical_serialization(event: &NostrEvent) -> String {
+    let mut serialized = String::from("[0,");
+    push_canonical_string(&mut serialized, &event.pubkey);
+    serialized.push_str(&format!(",{},{},[", event.created_at, event.kind));
+    for (i, tag) in event.tags.iter().enumerate() {
+        if i > 0 {
+            serialized.push(',');
+        }
+        serialized.push('[');
+        for (j, value) in tag.iter().enumerate() {
+            if j > 0 {
+                serialized.push(',');
+            }
+            push_canonical_string(&mut serialized, value);
+        }
+        serialized.push(']');
+    }
+    serialized.push_str("],");
+    push_canonical_string(&mut serialized, &event.content);
+    serialized.push(']');
+    serialized
+}
+
+// NIP-01 escapes exactly these seven characters and writes every other one,
+// including the remaining control characters and all non-ASCII text, as raw
+// UTF-8. General JSON serializers write those control characters as \uXXXX,
+// which hashes to a different id.
+fn push_canonical_string(serialized: &mut String, value: &str) {
+    serialized.push('"');
+    for c in value.chars() {
+        match c {
+            '\n' => serialized.push_str("\\n"),
+            '"' => serialized.push_str("\\\""),
+            '\\' => serialized.push_str("\\\\"),
+            '\r' => serialized.push_str("\\r"),
+            '\t' => serialized.push_str("\\t"),
+            '\u{8}' => serialized.push_str("\\b"),
+            '\u{c}' => serialized.push_str("\\f"),
+            c => serialized.push(c),
+        }
+    }
+    serialized.push('"');
+}
+
+// Strict BIP340 verification of a 64-byte signature under an x-only pubkey
+pub(crate) fn verify_bip340(message: &[u8], signature: &[u8; 64], xonly_pubkey: &[u8; 32]) -> bool {
+    let Ok(verifying_key) = schnorr::VerifyingKey::from_bytes(xonly_pubkey) else {
+        return false;
+    };
+    let Ok(signature) = schnorr::Signature::try_from(signature.as_slice()) else {
+        return false;
+    };
+    
+    verifying_key.verify_raw(message, &signature).is_ok()
+}
+
+// NIP-19 prefix of a bech32-encoded public key
+const NPUB_HRP: Hrp = Hrp::parse_unchecked("npub");
+
+// Decode a NIP-19 npub to its x-only pubkey
+pub(crate) fn decode_npub(npub: &str) -> Result<[u8; 32], FrostError> {
+    let checked = CheckedHrpstring::new::<Bech32>(npub)
+        .map_err(|e| FrostError::MalformedInput(format!("Invalid npub: {}", e)))?;
+    if checked.hrp() != NPUB_HRP {
+        return Err(FrostError::MalformedInput(
+            format!("Expected an npub, got a {} string", checked.hrp())
+        ));
+    }
+    
+    let bytes: Vec<u8> = checked.byte_iter().collect();
+    bytes.try_into().map_err(|bytes: Vec<u8>| FrostError::MalformedInput(
+        format!("Expected npub to be 32 bytes, got {}", bytes.len())
+    ))
+}
+
+// Decode a hex field that must be exactly N bytes
+pub(crate) fn decode_fixed<const N: usize>(value_hex: &str, field: &str) -> Result<[u8; N], FrostError> {
+    let bytes = hex_to_bytes(value_hex, field)?;
+    
+    bytes.try_into().map_err(|bytes: Vec<u8>| FrostError::MalformedInput(
+        format!("Expected {} to be {} bytes, got {}", field, N, bytes.len())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::{schnorr::Signature, Keypair, Message, Secp256k1, SecretKey, XOnlyPublicKey};
+
+    fn verify_with_our_code(message: &[u8; 32], signature: &[u8; 64], xonly_pubkey: &[u8; 32]) -> bool {
+        let result: FrostResult<bool> = serde_json::from_str(&verify_signature_xonly(
+            message,
+            &hex::encode(signature),
+            &hex::encode(xonly_pubkey),
+        )).unwrap();
+        result.data.unwrap()
+    }
+
+    fn verify_with_libsecp(message: &[u8; 32], signature: &[u8; 64], xonly_pubkey: &[u8; 32]) -> bool {
+        let (Ok(signature), Ok(xonly_pubkey)) = (
+            Signature::from_slice(signature),
+            XOnlyPublicKey::from_slice(xonly_pubkey),
+        ) else {
+            return false;
+        };
+        Secp256k1::verification_only()
+            .verify_schnorr(&signature, &Message::from_digest(*message), &xonly_pubkey)
+            .is_ok()
+    }
+
+    #[test]
+    fn xonly_verification_agrees_with_libsecp256k1() {
+        let secp = Secp256k1::new();
+        
+        for i in 0u8..32 {
+            // Roughly half of these keys have odd y, so BIP340 signs with the negated secret
+            let secret = Sha256::digest([b'k', i]);
+            let keypair = Keypair::from_secret_key(&secp, &SecretKey::from_slice(&secret).unwrap());
+            let xonly_pubkey = keypair.x_only_public_key().0.serialize();
+            let message: [u8; 32] = Sha256::digest([b'm', i]).into();
+            
+            let signature = secp.sign_schnorr_no_aux_rand(&Message::from_digest(message), &keypair).serialize();
+            let mut tampered = signature;
+            tampered[63] ^= 1;
+            let mut other_message = message;
+            other_message[0] ^= 1;
+            
+            for (message, signature) in [(&message, &signature), (&message, &tampered), (&other_message, &signature)] {
+                assert_eq!(
+                    verify_with_our_code(message, signature, &xonly_pubkey),
+                    verify_with_libsecp(message, signature, &xonly_pubkey),
+                );
+            }
+            assert!(verify_with_our_code(&message, &signature, &xonly_pubkey));
+        }
+    }
+
+    #[cfg(feature = "full")]
+    fn sign_with_our_code(message: &[u8; 32], secret: &[u8], aux_rand: Option<[u8; 32]>) -> [u8; 64] {
+        let result: FrostResult<String> = serde_json::from_str(&sign_schnorr(
+            message,
+            &hex::encode(secret),
+            aux_rand.map(|aux_rand| aux_rand.to_vec()),
+        )).unwrap();
+        decode_fixed::<64>(&result.data.unwrap(), "signature").unwrap()
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn aux_rand_signatures_match_libsecp256k1() {
+        let secp = Secp256k1::new();
+        
+        for i in 0u8..8 {
+            let secret = Sha256::digest([b'k', i]);
+            let keypair = Keypair::from_secret_key(&secp, &SecretKey::from_slice(&secret).unwrap());
+            let xonly_pubkey = keypair.x_only_public_key().0.serialize();
+            let message: [u8; 32] = Sha256::digest([b'm', i]).into();
+            let aux_rand: [u8; 32] = Sha256::digest([b'a', i]).into();
+            
+            let expected = secp.sign_schnorr_with_aux_rand(&Message::from_digest(message), &keypair, &aux_rand).serialize();
+            assert_eq!(sign_with_our_code(&message, &secret, Some(aux_rand)), expected);
+            
+            // Fresh aux_rand gives a different, equally valid signature each time
+            let first = sign_with_our_code(&message, &secret, None);
+            let second = sign_with_our_code(&message, &secret, None);
+            assert_ne!(first, expected);
+            assert_ne!(first, second);
+            assert!(verify_with_libsecp(&message, &first, &xonly_pubkey));
+            assert!(verify_with_libsecp(&message, &second, &xonly_pubkey));
+        }
+    }
+
+    #[test]
+    fn nostr_compatibility_reports_the_group_key_parity() {
+        let compatibility = |secret_key_hex: &str| {
+            let signing_key = frost_secp256k1::SigningKey::deserialize(&hex::decode(secret_key_hex).unwrap()).unwrap();
+            let group_public_key = PublicKeyPackage::new(
+                std::collections::BTreeMap::new(),
+                frost_secp256k1::VerifyingKey::from(&signing_key),
+            );
+            let result: FrostResult<NostrCompatibility> = serde_json::from_str(
+                &assert_nostr_compatible(&serde_json::to_string(&group_public_key).unwrap())
+            ).unwrap();
+            result.data.unwrap()
+        };
+        let generator_x = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+        // G has even y, and -G shares its x coordinate with odd y
+        let even = compatibility(&format!("{}01", "00".repeat(31)));
+        assert_eq!(even.xonly_pubkey, generator_x);
+        assert!(!even.parity_adjustment_needed);
+
+        let odd = compatibility("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+        assert_eq!(odd.xonly_pubkey, generator_x);
+        assert!(odd.parity_adjustment_needed);
+    }
+
+    #[test]
+    fn canonical_serialization_follows_nip01_escaping() {
+        // JSON escapes in the input, including a surrogate pair, next to raw UTF-8
+        let event_json = r#"{
+            "pubkey": "58f5d9cbcaeaf692b967ad149b09778ffadfcdb5593b8115b1d0d7eb68d36938",
+            "created_at": 1700000000,
+            "kind": 1,
+            "tags": [["t", "caf\u00e9"], ["e", "tab\there"]],
+            "content": "line\nquote\"slash\\cr\rtab\tbs\bff\fbell\u0007nul\u0000 \ud83d\ude00 😀"
+        }"#;
+        let canonical: FrostResult<String> = serde_json::from_str(&canonicalize_event(event_json)).unwrap();
+        let canonical = canonical.data.unwrap();
+
+        // The seven escapes, with BEL and NUL written raw and emoji as UTF-8
+        assert_eq!(
+            canonical,
+            "[0,\"58f5d9cbcaeaf692b967ad149b09778ffadfcdb5593b8115b1d0d7eb68d36938\",1700000000,1,\
+             [[\"t\",\"café\"],[\"e\",\"tab\\there\"]],\
+             \"line\\nquote\\\"slash\\\\cr\\rtab\\tbs\\bff\\fbell\u{7}nul\u{0} 😀 😀\"]",
+        );
+        assert!(!canonical.contains("\\u"));
+
+        // serde_json would write BEL and NUL as \u0007 and \u0000
+        let event: NostrEvent = serde_json::from_str(event_json).unwrap();
+        let generic = serde_json::to_string(&(0, &event.pubkey, event.created_at, event.kind, &event.tags, &event.content)).unwrap();
+        assert_ne!(generic, canonical);
+        assert_eq!(event_id(&event).unwrap(), <[u8; 32]>::from(Sha256::digest(canonical.as_bytes())));
+    }
+}
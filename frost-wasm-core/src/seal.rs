@@ -0,0 +1,132 @@
+// ABOUTME: HMAC sealing of ceremony state passed back and forth with the caller
+// ABOUTME: Detects tampering of the round maps between stateless WASM calls
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{check_input_sizes, FrostError, FrostResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+// Separates state MACs from anything else the server might MAC with the same key
+const SEAL_DOMAIN: &[u8] = b"frost-wasm-core/sealed-state/v1";
+
+// Shorter keys would make the MAC the weakest link in the ceremony
+const MIN_MAC_KEY_LEN: usize = 32;
+
+// A state together with the MAC binding it to its ceremony and round. The
+// state is kept as the exact string that was sealed, so callers can move the
+// envelope around freely without invalidating the MAC.
+#[derive(Serialize, Deserialize)]
+pub struct SealedState {
+    pub ceremony_id: String,
+    pub round: u8,
+    pub state: String,
+    pub mac: String,
+}
+
+// The fields of a keygen or signing state that the MAC is bound to
+#[derive(Deserialize)]
+struct SealedFields {
+    #[serde(default)]
+    ceremony_id: Option<String>,
+    current_round: u8,
+}
+
+/// Seal a keygen or signing state with an HMAC-SHA256 under `mac_key`
+///
+/// The MAC covers the state's ceremony id, its current round, and the state
+/// JSON byte for byte. The state must have been created with a ceremony id.
+#[wasm_bindgen]
+pub fn seal_state(state_json: &str, mac_key: &[u8]) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("state_json", state_json)])?;
+
+        let state_result: FrostResult<SealedFields> = serde_json::from_str(state_json)
+            .map_err(|e| FrostError::MalformedInput(e.to_string()))?;
+
+        let fields = state_result.data.ok_or(FrostError::InvalidStateTransition(
+            "Invalid state provided".to_string()
+        ))?;
+
+        let ceremony_id = fields.ceremony_id.ok_or(FrostError::InvalidStateTransition(
+            "State has no ceremony id to seal against".to_string()
+        ))?;
+
+        let mac = state_mac(mac_key, &ceremony_id, fields.current_round, state_json)?;
+
+        let sealed = SealedState {
+            ceremony_id,
+            round: fields.current_round,
+            state: state_json.to_string(),
+            mac: hex::encode(mac.finalize().into_bytes()),
+        };
+
+        serde_json::to_string(&sealed)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize sealed state: {}", e)))
+    })();
+
+    match result {
+        Ok(sealed) => {
+            serde_json::to_string(&FrostResult::ok(sealed)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Check the MAC on a sealed state and return the state JSON it protects
+///
+/// Fails with `IntegrityError` if the state, ceremony id, or round were
+/// changed after sealing, or if the envelope was sealed under another key.
+#[wasm_bindgen]
+pub fn open_state(sealed_json: &str, mac_key: &[u8]) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("sealed_json", sealed_json)])?;
+
+        let sealed: SealedState = serde_json::from_str(sealed_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize sealed state: {}", e)))?;
+
+        let tag = hex::decode(&sealed.mac)
+            .map_err(|e| FrostError::IntegrityError(format!("Invalid MAC encoding: {}", e)))?;
+
+        state_mac(mac_key, &sealed.ceremony_id, sealed.round, &sealed.state)?
+            .verify_slice(&tag)
+            .map_err(|_| FrostError::IntegrityError(
+                format!("State for ceremony {} failed its integrity check", sealed.ceremony_id)
+            ))?;
+
+        Ok(sealed.state)
+    })();
+
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// HMAC over the domain, the length-prefixed ceremony id, the round, and the
+// state bytes. The length prefix stops bytes moving between id and state.
+fn state_mac(mac_key: &[u8], ceremony_id: &str, round: u8, state_json: &str) -> Result<HmacSha256, FrostError> {
+    if mac_key.len() < MIN_MAC_KEY_LEN {
+        return Err(FrostError::IntegrityError(
+            format!("MAC key must be at least {} bytes, got {}", MIN_MAC_KEY_LEN, mac_key.len())
+        ));
+    }
+
+    let mut mac = HmacSha256::new_from_slice(mac_key)
+        .map_err(|e| FrostError::IntegrityError(format!("Invalid MAC key: {}", e)))?;
+    mac.update(SEAL_DOMAIN);
+    mac.update(&(ceremony_id.len() as u64).to_be_bytes());
+    mac.update(ceremony_id.as_bytes());
+    mac.update(&[round]);
+    mac.update(state_json.as_bytes());
+    Ok(mac)
+}
@@ -0,0 +1,151 @@
+// ABOUTME: Per-application subkeys derived from the FROST group key by an additive tweak
+// ABOUTME: Tweaks the group package and each key package so signers sign under the subkey
+
+use wasm_bindgen::prelude::*;
+use std::collections::BTreeMap;
+use k256::elliptic_curve::{ops::Reduce, sec1::ToEncodedPoint, PrimeField};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use frost_core::keys::PublicKeyPackage;
+use frost_secp256k1::{self as frost, Secp256K1Sha256};
+
+use crate::{check_input_sizes, check_present, parse_key_package, sec1_point, FrostError, FrostResult};
+
+// Separates app subkey tweaks from every other hash of the group key
+const APP_SUBKEY_DOMAIN: &[u8] = b"frost-wasm-core/app-subkey/v1";
+
+/// The group public key package for an application's subkey
+///
+/// The subkey is `Y' = Y + tG` for the group key `Y`, with the tweak
+/// `t = H(domain || len(app_id) || app_id || Y)` reduced to a scalar (`len` is
+/// 8 bytes big-endian, `Y` is compressed SEC1). Every verifying share is moved
+/// by the same `tG`, so the package works anywhere a group package does:
+/// aggregation, `verify_signature`, and so on. The same group and `app_id`
+/// always give the same subkey.
+///
+/// Subkeys are unlinkable only to someone who doesn't know `Y`: `t` hashes the
+/// group key, so without it two apps' subkeys look like unrelated keys, while
+/// anyone holding `Y` can derive the subkey for any `app_id` they guess. The
+/// tweak is public to that holder, so subkeys separate identities but not key
+/// material, and a leaked subkey share is as good as the group share it came
+/// from. A subkey may have odd y; see `assert_nostr_compatible` before using
+/// one as a Nostr identity.
+#[wasm_bindgen]
+pub fn derive_app_subkey(group_public_key_json: &str, app_id: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("group_public_key_json", group_public_key_json),
+            ("app_id", app_id),
+        ])?;
+        check_present(&[("group_public_key_json", group_public_key_json), ("app_id", app_id)])?;
+
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+
+        let tweak = app_tweak(group_public_key.verifying_key(), app_id)?;
+        let mut verifying_shares = BTreeMap::new();
+        for (identifier, verifying_share) in group_public_key.verifying_shares() {
+            verifying_shares.insert(*identifier, tweak_verifying_share(verifying_share, &tweak)?);
+        }
+        let subkey = PublicKeyPackage::new(verifying_shares, tweak_verifying_key(group_public_key.verifying_key(), &tweak)?);
+
+        serde_json::to_string(&subkey)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize subkey package: {}", e)))
+    })();
+
+    match result {
+        Ok(subkey) => {
+            serde_json::to_string(&FrostResult::ok(subkey)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+/// A signer's key package for an application's subkey
+///
+/// Adds the tweak of `derive_app_subkey` to the signing share, the verifying
+/// share, and the group key, keeping the identifier and threshold. Since the
+/// Lagrange coefficients of any signing set sum to one, shares moved by the
+/// same `t` are shares of the group secret plus `t`, and the usual signing
+/// rounds with these key packages produce a signature under the subkey.
+#[wasm_bindgen]
+pub fn derive_app_key_package(key_package_json: &str, app_id: &str) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[
+            ("key_package_json", key_package_json),
+            ("app_id", app_id),
+        ])?;
+        check_present(&[("key_package_json", key_package_json), ("app_id", app_id)])?;
+
+        let key_package = parse_key_package(key_package_json)?;
+        let tweak = app_tweak(key_package.verifying_key(), app_id)?;
+
+        let signing_share = Zeroizing::new(key_package.signing_share().serialize());
+        let share_scalar = Option::<k256::Scalar>::from(
+            k256::Scalar::from_repr(k256::FieldBytes::clone_from_slice(&signing_share))
+        ).ok_or(FrostError::CorruptedShare("Signing share is not a valid scalar".to_string()))?;
+        let tweaked_share = Zeroizing::new((share_scalar + tweak).to_bytes());
+        let tweaked_share = frost::keys::SigningShare::deserialize(tweaked_share.as_slice())
+            .map_err(|e| FrostError::KeygenError(format!("Failed to tweak signing share: {}", e)))?;
+
+        let subkey_package = frost::keys::KeyPackage::new(
+            *key_package.identifier(),
+            tweaked_share,
+            tweak_verifying_share(key_package.verifying_share(), &tweak)?,
+            tweak_verifying_key(key_package.verifying_key(), &tweak)?,
+            *key_package.min_signers(),
+        );
+
+        serde_json::to_string(&subkey_package)
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize key package: {}", e)))
+    })();
+
+    match result {
+        Ok(key_package) => {
+            serde_json::to_string(&FrostResult::ok(key_package)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// t = H(domain || len(app_id) || app_id || Y), reduced to a scalar
+fn app_tweak(group_key: &frost::VerifyingKey, app_id: &str) -> Result<k256::Scalar, FrostError> {
+    let group_key = group_key.serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?;
+    let digest = Sha256::new()
+        .chain_update(APP_SUBKEY_DOMAIN)
+        .chain_update((app_id.len() as u64).to_be_bytes())
+        .chain_update(app_id)
+        .chain_update(group_key)
+        .finalize();
+
+    Ok(<k256::Scalar as Reduce<k256::U256>>::reduce_bytes(&digest))
+}
+
+// The compressed SEC1 encoding of `point + tG`
+fn tweaked_point(point: &[u8], tweak: &k256::Scalar) -> Result<Vec<u8>, FrostError> {
+    let tweaked = sec1_point(point)? + k256::ProjectivePoint::GENERATOR * tweak;
+    Ok(tweaked.to_affine().to_encoded_point(true).as_bytes().to_vec())
+}
+
+fn tweak_verifying_key(verifying_key: &frost::VerifyingKey, tweak: &k256::Scalar) -> Result<frost::VerifyingKey, FrostError> {
+    let bytes = verifying_key.serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize group key: {}", e)))?;
+    frost::VerifyingKey::deserialize(&tweaked_point(&bytes, tweak)?)
+        .map_err(|e| FrostError::KeygenError(format!("Failed to tweak group key: {}", e)))
+}
+
+fn tweak_verifying_share(
+    verifying_share: &frost::keys::VerifyingShare,
+    tweak: &k256::Scalar,
+) -> Result<frost::keys::VerifyingShare, FrostError> {
+    let bytes = verifying_share.serialize()
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize verifying share: {}", e)))?;
+    frost::keys::VerifyingShare::deserialize(&tweaked_point(&bytes, tweak)?)
+        .map_err(|e| FrostError::KeygenError(format!("Failed to tweak verifying share: {}", e)))
+}
@@ -0,0 +1,333 @@
+// ABOUTME: Signing Bitcoin taproot key-path spends with the FROST group key
+// ABOUTME: Runs round 2 and aggregation under the secp256k1-tr ciphersuite with a BIP341 tweak
+
+use wasm_bindgen::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use zeroize::Zeroizing;
+
+use frost_core::keys::PublicKeyPackage;
+use frost_secp256k1::{self as frost, Secp256K1Sha256};
+use frost_secp256k1_tr::{self as tr, keys::Tweak};
+
+use crate::nostr::decode_fixed;
+use crate::{check_input_sizes, check_present, new_signing_state, FrostError, FrostResult, SigningState, TaprootSpend};
+
+/// Initialize a signing ceremony over a Bitcoin taproot key-path sighash
+///
+/// `sighash` is the 32-byte BIP341 signature hash of the spending transaction
+/// and is signed as is, with no further hashing. The group key is the taproot
+/// internal key `P`. Round 2 and aggregation apply the BIP341 tweak: `P` is
+/// taken with even y, `t = hash_TapTweak(x(P) || merkle_root)`, and shares are
+/// produced for the output key `Q = P + tG`. Leave out `merkle_root` for a
+/// key-only (BIP86) output, where `t = hash_TapTweak(x(P))`; pass the script
+/// tree's 32-byte root when the output has one. `taproot_output_key` returns
+/// `x(Q)` for building the scriptPubKey.
+///
+/// The ceremony then runs through `signing_round1`, `build_signing_package`,
+/// and `signing_round2` as usual, and the final signature is the 64-byte BIP340
+/// signature for the key-path witness. Shares can't be checked on arrival with
+/// `add_signature_share` in these ceremonies.
+#[wasm_bindgen]
+pub fn create_signing_state_bitcoin(
+    sighash: &[u8],
+    signers_json: &str,
+    merkle_root: Option<Vec<u8>>,
+    ceremony_id: Option<String>,
+    created_at: Option<u64>,
+    expires_at: Option<u64>
+) -> String {
+    let result = (|| -> Result<SigningState, FrostError> {
+        if sighash.len() != 32 {
+            return Err(FrostError::MalformedInput(
+                format!("Expected a 32-byte sighash, got {} bytes", sighash.len())
+            ));
+        }
+
+        let taproot = TaprootSpend {
+            merkle_root: merkle_root.as_deref().map(checked_merkle_root).transpose()?.map(hex::encode),
+        };
+
+        new_signing_state(sighash, signers_json, ceremony_id, created_at, expires_at, Some(taproot))
+    })();
+
+    match result {
+        Ok(state) => {
+            serde_json::to_string(&FrostResult::ok(state)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SigningState>::err(e)).unwrap()
+        }
+    }
+}
+
+/// The x-only taproot output key for a group key, hex encoded
+///
+/// This is `x(Q)` for the output a `create_signing_state_bitcoin` ceremony with
+/// the same `merkle_root` signs for; the scriptPubKey is `OP_1 <x(Q)>`.
+#[wasm_bindgen]
+pub fn taproot_output_key(group_public_key_json: &str, merkle_root: Option<Vec<u8>>) -> String {
+    let result = (|| -> Result<String, FrostError> {
+        check_input_sizes(&[("group_public_key_json", group_public_key_json)])?;
+        check_present(&[("group_public_key_json", group_public_key_json)])?;
+
+        let group_public_key: PublicKeyPackage<Secp256K1Sha256> = serde_json::from_str(group_public_key_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to deserialize group public key: {}", e)))?;
+        let merkle_root = merkle_root.as_deref().map(checked_merkle_root).transpose()?;
+
+        let output_key = tr_public_key_package(&group_public_key)?.tweak(merkle_root.as_ref());
+        let output_key_bytes = output_key.verifying_key().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize output key: {}", e)))?;
+
+        Ok(hex::encode(&output_key_bytes[1..]))
+    })();
+
+    match result {
+        Ok(output_key) => {
+            serde_json::to_string(&FrostResult::ok(output_key)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<String>::err(e)).unwrap()
+        }
+    }
+}
+
+// Produce a signature share for the tweaked output key. The share is a plain
+// scalar, so it is handed back in the ciphersuite the state stores.
+pub(crate) fn sign_with_tweak(
+    signing_package: &frost::SigningPackage,
+    nonces: &frost::round1::SigningNonces,
+    key_package: &frost::keys::KeyPackage,
+    taproot: &TaprootSpend,
+) -> Result<frost::round2::SignatureShare, FrostError> {
+    let merkle_root = stored_merkle_root(taproot)?;
+
+    let signature_share = tr::round2::sign_with_tweak(
+        &tr_signing_package(signing_package)?,
+        &tr_nonces(nonces)?,
+        &tr_key_package(key_package)?,
+        merkle_root.as_ref().map(|root| root.as_slice()),
+    ).map_err(|e| FrostError::SigningError(format!("Failed to generate taproot signature share: {}", e)))?;
+
+    frost::round2::SignatureShare::deserialize(&signature_share.serialize())
+        .map_err(conversion_error)
+}
+
+// Aggregate shares into a BIP340 signature under the tweaked output key,
+// serialized like any other final signature. A bad share is reported under
+// its signer's name from `signer_name`.
+pub(crate) fn aggregate_with_tweak(
+    signing_package: &frost::SigningPackage,
+    signature_shares: &BTreeMap<frost::Identifier, frost::round2::SignatureShare>,
+    group_public_key: &PublicKeyPackage<Secp256K1Sha256>,
+    taproot: &TaprootSpend,
+    signer_name: impl Fn(&frost::Identifier) -> String,
+) -> Result<String, FrostError> {
+    let merkle_root = stored_merkle_root(taproot)?;
+
+    let mut tr_shares = BTreeMap::new();
+    for (identifier, share) in signature_shares {
+        let share = tr::round2::SignatureShare::deserialize(&share.serialize())
+            .map_err(conversion_error)?;
+        tr_shares.insert(tr_identifier(identifier)?, share);
+    }
+
+    let signature = tr::aggregate_with_tweak(
+        &tr_signing_package(signing_package)?,
+        &tr_shares,
+        &tr_public_key_package(group_public_key)?,
+        merkle_root.as_ref().map(|root| root.as_slice()),
+    ).map_err(|e| match e.culprit() {
+        Some(culprit) => {
+            let culprit = frost::Identifier::deserialize(&culprit.serialize())
+                .map(|identifier| signer_name(&identifier))
+                .unwrap_or_else(|_| hex::encode(culprit.serialize()));
+            FrostError::SigningError(format!("Invalid signature share from {}: {}", culprit, e))
+        }
+        None => FrostError::SigningError(format!("Failed to aggregate taproot signature: {}", e)),
+    })?;
+
+    serde_json::to_string(&signature)
+        .map_err(|e| FrostError::SerializationError(format!("Failed to serialize final signature: {}", e)))
+}
+
+fn checked_merkle_root(merkle_root: &[u8]) -> Result<[u8; 32], FrostError> {
+    merkle_root.try_into().map_err(|_| FrostError::MalformedInput(
+        format!("Expected a 32-byte merkle root, got {} bytes", merkle_root.len())
+    ))
+}
+
+fn stored_merkle_root(taproot: &TaprootSpend) -> Result<Option<[u8; 32]>, FrostError> {
+    taproot.merkle_root.as_deref()
+        .map(|merkle_root| decode_fixed::<32>(merkle_root, "merkle root"))
+        .transpose()
+}
+
+// The two ciphersuites share the secp256k1 group and differ only in hashing
+// and signature encoding, so keys, nonces, and commitments carry over by
+// re-encoding their scalars and points. The serialized packages themselves
+// can't be reused because they name their ciphersuite.
+
+fn conversion_error(e: impl Display) -> FrostError {
+    FrostError::SigningError(format!("Failed to convert to the taproot ciphersuite: {}", e))
+}
+
+fn tr_identifier(identifier: &frost::Identifier) -> Result<tr::Identifier, FrostError> {
+    tr::Identifier::deserialize(&identifier.serialize()).map_err(conversion_error)
+}
+
+fn tr_key_package(key_package: &frost::keys::KeyPackage) -> Result<tr::keys::KeyPackage, FrostError> {
+    let signing_share = Zeroizing::new(key_package.signing_share().serialize());
+    let verifying_share = key_package.verifying_share().serialize().map_err(conversion_error)?;
+    let verifying_key = key_package.verifying_key().serialize().map_err(conversion_error)?;
+
+    Ok(tr::keys::KeyPackage::new(
+        tr_identifier(key_package.identifier())?,
+        tr::keys::SigningShare::deserialize(&signing_share).map_err(conversion_error)?,
+        tr::keys::VerifyingShare::deserialize(&verifying_share).map_err(conversion_error)?,
+        tr::VerifyingKey::deserialize(&verifying_key).map_err(conversion_error)?,
+        *key_package.min_signers(),
+    ))
+}
+
+fn tr_public_key_package(group_public_key: &PublicKeyPackage<Secp256K1Sha256>) -> Result<tr::keys::PublicKeyPackage, FrostError> {
+    let mut verifying_shares = BTreeMap::new();
+    for (identifier, verifying_share) in group_public_key.verifying_shares() {
+        let verifying_share = verifying_share.serialize().map_err(conversion_error)?;
+        verifying_shares.insert(
+            tr_identifier(identifier)?,
+            tr::keys::VerifyingShare::deserialize(&verifying_share).map_err(conversion_error)?,
+        );
+    }
+
+    let verifying_key = group_public_key.verifying_key().serialize().map_err(conversion_error)?;
+    Ok(tr::keys::PublicKeyPackage::new(
+        verifying_shares,
+        tr::VerifyingKey::deserialize(&verifying_key).map_err(conversion_error)?,
+    ))
+}
+
+fn tr_nonces(nonces: &frost::round1::SigningNonces) -> Result<tr::round1::SigningNonces, FrostError> {
+    let hiding = Zeroizing::new(nonces.hiding().serialize());
+    let binding = Zeroizing::new(nonces.binding().serialize());
+
+    Ok(tr::round1::SigningNonces::from_nonces(
+        tr::round1::Nonce::deserialize(&hiding).map_err(conversion_error)?,
+        tr::round1::Nonce::deserialize(&binding).map_err(conversion_error)?,
+    ))
+}
+
+fn tr_signing_package(signing_package: &frost::SigningPackage) -> Result<tr::SigningPackage, FrostError> {
+    let mut commitments = BTreeMap::new();
+    for (identifier, commitment) in signing_package.signing_commitments() {
+        let hiding = commitment.hiding().serialize().map_err(conversion_error)?;
+        let binding = commitment.binding().serialize().map_err(conversion_error)?;
+        commitments.insert(
+            tr_identifier(identifier)?,
+            tr::round1::SigningCommitments::new(
+                tr::round1::NonceCommitment::deserialize(&hiding).map_err(conversion_error)?,
+                tr::round1::NonceCommitment::deserialize(&binding).map_err(conversion_error)?,
+            ),
+        );
+    }
+
+    Ok(tr::SigningPackage::new(commitments, signing_package.message()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_signing_package, signing_round1, signing_round2, SignerCommitment};
+    use frost::keys::IdentifierList;
+    use secp256k1::{schnorr::Signature, Keypair, Message, Scalar, Secp256k1, SecretKey, XOnlyPublicKey};
+    use sha2::{Digest, Sha256};
+
+    fn output_key(group_public_key_json: &str, merkle_root: Option<&str>) -> String {
+        let merkle_root = merkle_root.map(|root| hex::decode(root).unwrap());
+        let result: FrostResult<String> =
+            serde_json::from_str(&taproot_output_key(group_public_key_json, merkle_root)).unwrap();
+        result.data.unwrap()
+    }
+
+    // Unwrap the data of an export's FrostResult
+    fn data<T: serde::de::DeserializeOwned>(result_json: &str) -> T {
+        let result: FrostResult<T> = serde_json::from_str(result_json).unwrap();
+        result.data.unwrap_or_else(|| panic!("{:?}", result.error))
+    }
+
+    fn wrapped(state: &SigningState) -> String {
+        serde_json::to_string(&FrostResult::ok(state.clone())).unwrap()
+    }
+
+    // Run a 2-of-3 ceremony over `sighash` with shares of `secret_key`
+    fn sign_key_path(secret_key: &SecretKey, sighash: &[u8; 32], merkle_root: Option<[u8; 32]>) -> (String, [u8; 64]) {
+        let signing_key = frost::SigningKey::deserialize(&secret_key.secret_bytes()).unwrap();
+        let (shares, group_public_key) = frost::keys::split(
+            &signing_key, 3, 2, IdentifierList::Default, &mut rand::thread_rng(),
+        ).unwrap();
+        let group_public_key = serde_json::to_string(&group_public_key).unwrap();
+        let key_packages: Vec<String> = shares.into_values()
+            .take(2)
+            .map(|share| serde_json::to_string(&frost::keys::KeyPackage::try_from(share).unwrap()).unwrap())
+            .collect();
+        let signers = ["alice", "bob"];
+
+        let mut state: SigningState = data(&create_signing_state_bitcoin(
+            sighash,
+            &serde_json::to_string(&signers).unwrap(),
+            merkle_root.map(|root| root.to_vec()),
+            None,
+            None,
+            None,
+        ));
+        for (signer, key_package) in signers.iter().zip(&key_packages) {
+            state = data::<(SigningState, SignerCommitment)>(&signing_round1(&wrapped(&state), signer, key_package, None)).0;
+        }
+        let signing_package: String = data(&build_signing_package(&wrapped(&state), None));
+        for (signer, key_package) in signers.iter().zip(&key_packages) {
+            state = data::<(SigningState, Option<String>)>(&signing_round2(
+                &wrapped(&state), signer, key_package, &signing_package, Some(group_public_key.clone()),
+            )).0;
+        }
+
+        let signature: tr::Signature = serde_json::from_str(&state.final_signature.unwrap()).unwrap();
+        (group_public_key, signature.serialize().unwrap().try_into().unwrap())
+    }
+
+    // The BIP341 output key computed independently with libsecp256k1
+    fn libsecp_output_key(secret_key: &SecretKey, merkle_root: Option<[u8; 32]>) -> XOnlyPublicKey {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_secret_key(&secp, secret_key);
+        let (internal_key, _) = keypair.x_only_public_key();
+
+        let tag = Sha256::digest(b"TapTweak");
+        let mut hasher = Sha256::new();
+        hasher.update(tag);
+        hasher.update(tag);
+        hasher.update(internal_key.serialize());
+        if let Some(merkle_root) = merkle_root {
+            hasher.update(merkle_root);
+        }
+        let tweak = Scalar::from_be_bytes(hasher.finalize().into()).unwrap();
+
+        keypair.add_xonly_tweak(&secp, &tweak).unwrap().x_only_public_key().0
+    }
+
+    #[test]
+    fn key_path_signatures_verify_under_the_output_key() {
+        let secp = Secp256k1::new();
+        let sighash: [u8; 32] = Sha256::digest(b"taproot key path sighash").into();
+
+        for (i, merkle_root) in [None, Some(Sha256::digest(b"script tree").into())].into_iter().enumerate() {
+            let secret_key = SecretKey::from_slice(&Sha256::digest([b'k', i as u8])).unwrap();
+            let (group_public_key, signature) = sign_key_path(&secret_key, &sighash, merkle_root);
+
+            let output = libsecp_output_key(&secret_key, merkle_root);
+            let merkle_root_hex = merkle_root.map(hex::encode);
+            assert_eq!(output_key(&group_public_key, merkle_root_hex.as_deref()), hex::encode(output.serialize()));
+
+            let signature = Signature::from_slice(&signature).unwrap();
+            assert!(secp.verify_schnorr(&signature, &Message::from_digest(sighash), &output).is_ok());
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// ABOUTME: Implementation-neutral wire format for FROST round 1 signing commitments
+// ABOUTME: Fixed-length hex fields following the RFC 9591 secp256k1 encodings
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use frost_secp256k1 as frost;
+
+use crate::nostr::decode_fixed;
+use crate::{check_input_sizes, identifier_from_hex, identifier_to_hex, FrostError, FrostResult, SignerCommitment};
+
+// Names the ciphersuite and encoding so peers can reject anything else
+const WIRE_FORMAT: &str = "FROST-secp256k1-SHA256-v1/commitment";
+
+// SerializeScalar and SerializeElement sizes for FROST(secp256k1, SHA-256),
+// RFC 9591 section 6.5
+const SCALAR_LEN: usize = 32;
+const ELEMENT_LEN: usize = 33;
+
+// One signer's round 1 commitment on the wire. The fields are the inputs to
+// encode_group_commitment_list (RFC 9591 section 4.3) for this signer:
+//
+//   identifier  SerializeScalar(identifier), 32 bytes big-endian, 64 hex chars
+//   hiding      SerializeElement(hiding_nonce_commitment), 33-byte compressed
+//               SEC1 point, 66 hex chars
+//   binding     SerializeElement(binding_nonce_commitment), same encoding
+//
+// The commitments are the ones produced by commit() in RFC 9591 section 5.1.
+// Hex is lowercase on output and accepted in either case on input.
+#[derive(Serialize, Deserialize)]
+pub struct WireCommitment {
+    pub format: String,
+    pub identifier: String,
+    pub hiding: String,
+    pub binding: String,
+}
+
+/// Encode a signer commitment from `signing_round1` in the documented wire format
+#[wasm_bindgen]
+pub fn commitment_to_wire(signer_commitment_json: &str) -> String {
+    let result = (|| -> Result<WireCommitment, FrostError> {
+        check_input_sizes(&[("signer_commitment_json", signer_commitment_json)])?;
+
+        let signer_commitment: SignerCommitment = serde_json::from_str(signer_commitment_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse commitment: {}", e)))?;
+
+        let identifier = identifier_from_hex(&signer_commitment.identifier)?;
+        let hiding = signer_commitment.commitment.hiding().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize hiding commitment: {}", e)))?;
+        let binding = signer_commitment.commitment.binding().serialize()
+            .map_err(|e| FrostError::SerializationError(format!("Failed to serialize binding commitment: {}", e)))?;
+
+        Ok(WireCommitment {
+            format: WIRE_FORMAT.to_string(),
+            identifier: identifier_to_hex(&identifier),
+            hiding: hex::encode(hiding),
+            binding: hex::encode(binding),
+        })
+    })();
+
+    match result {
+        Ok(wire) => {
+            serde_json::to_string(&FrostResult::ok(wire)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<WireCommitment>::err(e)).unwrap()
+        }
+    }
+}
+
+/// Decode a wire format commitment into the signer commitment `build_signing_package` takes
+///
+/// Rejects other formats, fields of the wrong length, the zero identifier, and
+/// commitments that are not valid non-identity curve points.
+#[wasm_bindgen]
+pub fn commitment_from_wire(wire_json: &str) -> String {
+    let result = (|| -> Result<SignerCommitment, FrostError> {
+        check_input_sizes(&[("wire_json", wire_json)])?;
+
+        let wire: WireCommitment = serde_json::from_str(wire_json)
+            .map_err(|e| FrostError::MalformedInput(format!("Failed to parse wire commitment: {}", e)))?;
+
+        if wire.format != WIRE_FORMAT {
+            return Err(FrostError::MalformedInput(
+                format!("Unsupported commitment format {}, expected {}", wire.format, WIRE_FORMAT)
+            ));
+        }
+
+        let identifier_bytes = decode_fixed::<SCALAR_LEN>(&wire.identifier, "identifier")?;
+        let identifier = identifier_from_hex(&hex::encode(identifier_bytes))?;
+
+        let hiding = frost::round1::NonceCommitment::deserialize(&decode_fixed::<ELEMENT_LEN>(&wire.hiding, "hiding")?)
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid hiding commitment: {}", e)))?;
+        let binding = frost::round1::NonceCommitment::deserialize(&decode_fixed::<ELEMENT_LEN>(&wire.binding, "binding")?)
+            .map_err(|e| FrostError::MalformedInput(format!("Invalid binding commitment: {}", e)))?;
+
+        Ok(SignerCommitment {
+            identifier: identifier_to_hex(&identifier),
+            commitment: frost::round1::SigningCommitments::new(hiding, binding),
+            ceremony_binding: None,
+        })
+    })();
+
+    match result {
+        Ok(signer_commitment) => {
+            serde_json::to_string(&FrostResult::ok(signer_commitment)).unwrap()
+        }
+        Err(e) => {
+            serde_json::to_string(&FrostResult::<SignerCommitment>::err(e)).unwrap()
+        }
+    }
+}
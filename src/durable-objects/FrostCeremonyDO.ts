@@ -536,9 +536,9 @@ export class FrostCeremonyDO extends DurableObject {
         if (roundNumber === 1) {
           result = processSigningRound1(frostState, userId, data); // data should be key package
           if (result.success && result.data) {
-            const [newState, nonces] = result.data;
+            const [newState, commitment] = result.data;
             await this.ctx.storage.put('signing_state', JSON.stringify(newState));
-            participantResult = nonces;
+            participantResult = JSON.stringify(commitment);
           } else {
             return { success: false, error: result.error || 'Signing round 1 failed' };
           }
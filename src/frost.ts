@@ -3,6 +3,9 @@
 
 import wasmInit, * as frostWasm from './wasm/frost_wasm_core';
 
+// Stable numeric error codes reported in FrostResult.error_code
+export { FrostErrorCode } from './wasm/frost_wasm_core';
+
 // FROST initialization state
 let frostInitialized = false;
 let wasmAvailable = false;
@@ -37,24 +40,66 @@ export interface FrostResult<T = any> {
   success: boolean;
   data?: T;
   error?: string;
+  error_code?: number; // A FrostErrorCode value
+  timings?: Record<string, number>; // Nanoseconds per step, when collection is enabled
+  deprecations?: string[]; // Markers for legacy input formats that were read
 }
 
 export interface KeygenState {
+  kind?: 'keygen'; // Absent on states saved before the tag existed
   threshold: number;
   max_participants: number;
   current_round: number;
   round1_packages: Record<string, string>;
   key_packages: Record<string, string>;
   group_public_key?: string;
+  participant_identifiers?: Record<string, string>;
+  ceremony_id?: string;
+  created_at?: number;
+  expires_at?: number;
+  commit_reveal?: boolean;
+  round1_commitments?: Record<string, string>;
+  removed_participants?: string[];
 }
 
 export interface SigningState {
+  kind?: 'signing'; // Absent on states saved before the tag existed
   message: number[];
   current_round: number;
   signers: string[];
   round1_packages: Record<string, string>;
   signature_shares: Record<string, string>;
   final_signature?: string;
+  signer_identifiers?: Record<string, string>;
+  threshold?: number;
+  ceremony_id?: string;
+  validated_identifiers?: string[];
+  imported_nonce_ids?: string[];
+  taproot?: TaprootSpend;
+  group_verifying_key?: string;
+  transcript_commitments?: Record<string, string>;
+  signer_verifying_shares?: Record<string, string>;
+  created_at?: number;
+  expires_at?: number;
+}
+
+// The taproot output a Bitcoin signing ceremony signs for
+export interface TaprootSpend {
+  merkle_root?: string;
+}
+
+// Caller-supplied unix seconds stamped on a new ceremony; round functions reject it from expiresAt on
+export interface CeremonyLifetime {
+  createdAt: number;
+  expiresAt?: number;
+}
+
+// The lifetime as the optional u64 arguments the creation functions take
+function lifetimeArgs(lifetime?: CeremonyLifetime): [bigint | undefined, bigint | undefined] {
+  return [
+    lifetime === undefined ? undefined : BigInt(lifetime.createdAt),
+    lifetime?.expiresAt === undefined ? undefined : BigInt(lifetime.expiresAt),
+  ];
 }
 
 // High-level FROST interfaces
@@ -73,35 +118,74 @@ export interface FrostSignature {
 /**
  * Create a new keygen ceremony state
  */
-export function createKeygenCeremony(threshold: number, maxParticipants: number): FrostResult<KeygenState> {
+export function createKeygenCeremony(
+  threshold: number,
+  maxParticipants: number,
+  ceremonyId?: string,
+  lifetime?: CeremonyLifetime
+): FrostResult<KeygenState> {
   ensureInitialized();
   
   if (wasmAvailable) {
-    const resultJson = frostWasm.create_keygen_state(threshold, maxParticipants);
+    const resultJson = frostWasm.create_keygen_state(threshold, maxParticipants, ceremonyId, ...lifetimeArgs(lifetime));
     return JSON.parse(resultJson);
   } else {
     // Mock implementation when WASM is not available
     return {
       success: true,
       data: {
+        kind: 'keygen',
         threshold,
         max_participants: maxParticipants,
         current_round: 1,
         round1_packages: {},
         key_packages: {},
+        ceremony_id: ceremonyId,
+        created_at: lifetime?.createdAt,
+        expires_at: lifetime?.expiresAt,
       }
     };
   }
 }
 
+/**
+ * Require every participant to commit to its round 1 package before any is revealed
+ */
+export function enableCommitReveal(stateJson: string): FrostResult<KeygenState> {
+  return callWasm(() => frostWasm.enable_commit_reveal(stateJson));
+}
+
+/**
+ * Hash a round 1 entry or package for keygenCommit
+ */
+export function round1PackageHash(round1Json: string): FrostResult<string> {
+  return callWasm(() => frostWasm.round1_package_hash(round1Json));
+}
+
+/**
+ * Record a participant's commitment to the round 1 package it will later reveal
+ */
+export function keygenCommit(stateJson: string, participantId: string, packageHash: string): FrostResult<KeygenState> {
+  return callWasm(() => frostWasm.keygen_commit(stateJson, participantId, packageHash));
+}
+
 /**
  * Process participant data for keygen round 1
+ *
+ * Optional extraEntropy is mixed with (never substituted for) the host's random source.
+ * In commit-reveal mode, revealedRound1Json is the committed entry to record instead.
  */
-export function processKeygenRound1(stateJson: string, participantId: string): FrostResult<[KeygenState, string]> {
+export function processKeygenRound1(
+  stateJson: string,
+  participantId: string,
+  pubkeyHex?: string,
+  extraEntropy?: Uint8Array,
+  revealedRound1Json?: string
+): FrostResult<[KeygenState, string]> {
   ensureInitialized();
   
   if (wasmAvailable) {
-    const resultJson = frostWasm.keygen_round1(stateJson, participantId);
+    const resultJson = frostWasm.keygen_round1(stateJson, participantId, pubkeyHex, extraEntropy, revealedRound1Json);
     return JSON.parse(resultJson);
   } else {
     // Mock implementation
@@ -146,75 +230,325 @@ export function processKeygenRound2(
   }
 }
 
+/**
+ * Run keygen round 2 for several participants this server holds in one call
+ */
+export function processKeygenRound2Batch(
+  stateJson: string,
+  participantIds: string[],
+  round1PackagesJson: string
+): FrostResult<[KeygenState, Record<string, string>]> {
+  return callWasm(() => frostWasm.keygen_round2_batch(stateJson, JSON.stringify(participantIds), round1PackagesJson));
+}
+
+export interface KeygenResult {
+  group_public_key: string;
+  threshold: number;
+  max_participants: number;
+  participant_identifiers: Record<string, string>;
+  shares: Record<string, string>; // Key package JSON, or hex(nonce || ciphertext) when encrypted
+  share_encryption?: 'aes-256-gcm';
+}
+
+/**
+ * Package a completed keygen as the document to store, encrypting each share when 32-byte hex keys are given per label
+ */
+export function packageKeygenResult(stateJson: string, shareKeys?: Record<string, string>): FrostResult<KeygenResult> {
+  return callWasm(() => frostWasm.package_keygen_result(stateJson, shareKeys && JSON.stringify(shareKeys)));
+}
+
+/**
+ * Re-encrypt a stored share under a new key without returning its plaintext; the label must match the one it was stored under
+ */
+export function rewrapShare(ciphertext: string, oldKeyHex: string, newKeyHex: string, participant: string): FrostResult<string> {
+  return callWasm(() => frostWasm.rewrap_share(ciphertext, oldKeyHex, newKeyHex, participant));
+}
+
+/**
+ * Remove a participant before any key package exists; the rest must redo round 1
+ */
+export function removeParticipant(stateJson: string, participantId: string): FrostResult<KeygenState> {
+  return callWasm(() => frostWasm.remove_participant(stateJson, participantId));
+}
+
+export interface DkgTranscript {
+  ceremony_id?: string | null;
+  threshold: number;
+  max_participants: number;
+  participants: Record<string, string>;
+  packages: Record<string, unknown>;
+}
+
+/**
+ * Extract the public round 1 commitments of a keygen ceremony (no secrets) for publication
+ */
+export function dkgPublicTranscript(stateJson: string): FrostResult<DkgTranscript> {
+  return callWasm(() => frostWasm.dkg_public_transcript(stateJson));
+}
+
+/**
+ * Check a group key against a published DKG transcript
+ */
+export function verifyDkgTranscript(transcript: DkgTranscript, groupPublicKeyJson: string): FrostResult<boolean> {
+  return callWasm(() => frostWasm.verify_dkg_transcript(JSON.stringify(transcript), groupPublicKeyJson));
+}
+
+export interface GroupKeyPreview {
+  group_public_key: string;
+  xonly_pubkey: string;
+}
+
+/**
+ * Compute the group key a DKG will produce from every participant's round 1 package
+ */
+export function computeGroupKeyPreview(round1Packages: Record<string, string>): FrostResult<GroupKeyPreview> {
+  return callWasm(() => frostWasm.compute_group_key_preview(JSON.stringify(round1Packages)));
+}
+
+/**
+ * Derive a participant's FROST identifier from its x-only Nostr pubkey
+ */
+export function identifierFromPubkey(pubkeyHex: string): FrostResult<string> {
+  return callWasm(() => frostWasm.identifier_from_pubkey(pubkeyHex));
+}
+
 // === SIGNING FUNCTIONS ===
 
 /**
  * Create a new signing ceremony state
+ *
+ * String messages are UTF-8 encoded; pass raw bytes such as a Nostr event id as a Uint8Array.
+ * `signers` is a list of labels, or a table of labels to identifier hex (such as a dealer's
+ * `identifiers`) that pins each signer to the key package with that identifier.
  */
-export function createSigningCeremony(message: string, signers: string[]): FrostResult<SigningState> {
+export function createSigningCeremony(
+  message: string | Uint8Array,
+  signers: string[] | Record<string, string>,
+  ceremonyId?: string,
+  lifetime?: CeremonyLifetime
+): FrostResult<SigningState> {
   ensureInitialized();
+  const messageBytes = typeof message === 'string' ? new TextEncoder().encode(message) : message;
   
   if (wasmAvailable) {
-    const messageBytes = new TextEncoder().encode(message);
     const signersJson = JSON.stringify(signers);
-    const resultJson = frostWasm.create_signing_state(messageBytes, signersJson);
+    const resultJson = frostWasm.create_signing_state(messageBytes, signersJson, ceremonyId, ...lifetimeArgs(lifetime));
     return JSON.parse(resultJson);
   } else {
     // Mock implementation
     return {
       success: true,
       data: {
-        message: Array.from(new TextEncoder().encode(message)),
+        kind: 'signing',
+        message: Array.from(messageBytes),
         current_round: 1,
-        signers,
+        signers: Array.isArray(signers) ? signers : Object.keys(signers).sort(),
         round1_packages: {},
         signature_shares: {},
+        signer_identifiers: Array.isArray(signers) ? {} : signers,
+        ceremony_id: ceremonyId,
+        created_at: lifetime?.createdAt,
+        expires_at: lifetime?.expiresAt,
       }
     };
   }
 }
 
+/**
+ * Initialize a signing ceremony with every signer's identifier resolved against the group
+ *
+ * Signers are resolved through the table when one is given, otherwise each label is taken
+ * as a Nostr pubkey. The returned state's `signer_identifiers` holds the full mapping.
+ */
+export function createSigningCeremonyForGroup(
+  message: string | Uint8Array,
+  signers: string[] | Record<string, string>,
+  groupPublicKeyJson: string,
+  ceremonyId?: string,
+  lifetime?: CeremonyLifetime
+): FrostResult<SigningState> {
+  const messageBytes = typeof message === 'string' ? new TextEncoder().encode(message) : message;
+  return callWasm(() => frostWasm.create_signing_state_for_group(
+    messageBytes,
+    JSON.stringify(signers),
+    groupPublicKeyJson,
+    ceremonyId,
+    ...lifetimeArgs(lifetime)
+  ));
+}
+
+// Identifier of each signer that passed preflight, as the table createSigningCeremony takes
+export interface SigningPreflight {
+  signers: Record<string, string>;
+}
+
+/**
+ * Check a signing request before creating ceremony state: a 32-byte event id, at least
+ * `threshold` signers, and a loadable group member key package for each. Returns the first
+ * problem found.
+ */
+export function preflightSigning(
+  message: Uint8Array,
+  signers: Record<string, string>,
+  groupPublicKeyJson: string,
+  threshold: number
+): FrostResult<SigningPreflight> {
+  return callWasm(() => frostWasm.preflight_signing(message, JSON.stringify(signers), groupPublicKeyJson, threshold));
+}
+
+/**
+ * Initialize a signing ceremony over a 32-byte BIP341 sighash for a taproot key-path spend
+ *
+ * The group key is the internal key; the final signature is valid for the
+ * output key tweaked with `merkleRoot` (BIP86 when omitted).
+ */
+export function createSigningCeremonyBitcoin(
+  sighash: Uint8Array,
+  signers: string[],
+  merkleRoot?: Uint8Array,
+  ceremonyId?: string,
+  lifetime?: CeremonyLifetime
+): FrostResult<SigningState> {
+  return callWasm(() => frostWasm.create_signing_state_bitcoin(
+    sighash,
+    JSON.stringify(signers),
+    merkleRoot,
+    ceremonyId,
+    ...lifetimeArgs(lifetime)
+  ));
+}
+
+/**
+ * The group public key package for an application's subkey, derived from the group key and app id
+ */
+export function deriveAppSubkey(groupPublicKeyJson: string, appId: string): FrostResult<string> {
+  return callWasm(() => frostWasm.derive_app_subkey(groupPublicKeyJson, appId));
+}
+
+/**
+ * A signer's key package for an application's subkey; sign with it as with any key package
+ */
+export function deriveAppKeyPackage(keyPackageJson: string, appId: string): FrostResult<string> {
+  return callWasm(() => frostWasm.derive_app_key_package(keyPackageJson, appId));
+}
+
+/**
+ * The x-only taproot output key for a group key and optional script tree merkle root
+ */
+export function taprootOutputKey(groupPublicKeyJson: string, merkleRoot?: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.taproot_output_key(groupPublicKeyJson, merkleRoot));
+}
+
+// A signer's round 1 commitment labeled with its FROST identifier
+export interface SignerCommitment {
+  identifier: string;
+  commitment: unknown;
+  // Hex SHA-256 tying the commitment to its ceremony id and message
+  ceremony_binding?: string;
+}
+
+// Reference to a key package held in WASM memory; pass JSON.stringify(handle) as the key package JSON
+export interface KeyPackageHandle {
+  key_package_handle: number;
+}
+
+/**
+ * Load a key package once for a ceremony; it is wiped after the signer's round 2 share
+ */
+export function loadKeyPackage(keyPackageJson: string): FrostResult<KeyPackageHandle> {
+  return callWasm(() => frostWasm.load_key_package(keyPackageJson));
+}
+
+/**
+ * Wipe a loaded key package whose ceremony ended before round 2
+ */
+export function releaseKeyPackage(handle: KeyPackageHandle): FrostResult<boolean> {
+  return callWasm(() => frostWasm.release_key_package(handle.key_package_handle));
+}
+
+/**
+ * Override how many ceremonies may be open at once in this isolate (default 1024)
+ */
+export function setMaxCeremonies(maxCeremonies: number): void {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    frostWasm.set_max_ceremonies(maxCeremonies);
+  }
+}
+
+/**
+ * Register a ceremony as open; fails with TooManyCeremonies at the cap. Returns the open count.
+ */
+export function openCeremony(ceremonyId: string): FrostResult<number> {
+  return callWasm(() => frostWasm.open_ceremony(ceremonyId));
+}
+
+/**
+ * Free a finished or abandoned ceremony's slot; returns whether it was open
+ */
+export function closeCeremony(ceremonyId: string): FrostResult<boolean> {
+  return callWasm(() => frostWasm.close_ceremony(ceremonyId));
+}
+
+/**
+ * The number of ceremonies currently open in this isolate
+ */
+export function activeCeremonyCount(): number {
+  ensureInitialized();
+  return wasmAvailable ? frostWasm.active_ceremony_count() : 0;
+}
+
 /**
  * Process participant data for signing round 1 (nonce generation)
+ *
+ * Key packages from a group other than the first signer's, or than groupPublicKeyJson when given, are rejected.
  */
 export function processSigningRound1(
   stateJson: string, 
   participantId: string, 
-  keyPackageJson: string
-): FrostResult<[SigningState, string]> {
+  keyPackageJson: string,
+  groupPublicKeyJson?: string
+): FrostResult<[SigningState, SignerCommitment]> {
   ensureInitialized();
   
   if (wasmAvailable) {
-    const resultJson = frostWasm.signing_round1(stateJson, participantId, keyPackageJson);
+    const resultJson = frostWasm.signing_round1(stateJson, participantId, keyPackageJson, groupPublicKeyJson);
     return JSON.parse(resultJson);
   } else {
     // Mock implementation
     const state = JSON.parse(stateJson) as SigningState;
-    const nonces = `mock_nonces_${participantId}`;
-    state.round1_packages[participantId] = nonces;
+    state.round1_packages[participantId] = `mock_nonces_${participantId}`;
     if (Object.keys(state.round1_packages).length >= state.signers.length) {
       state.current_round = 2;
     }
+    const commitment = {
+      identifier: `mock_identifier_${participantId}`,
+      commitment: `mock_commitment_${participantId}`,
+    };
     return {
       success: true,
-      data: [state, nonces]
+      data: [state, commitment]
     };
   }
 }
 
 /**
  * Process participant data for signing round 2 (signature share generation)
+ *
+ * Only the final, aggregating call needs the group public key.
  */
 export function processSigningRound2(
   stateJson: string,
   participantId: string,
   keyPackageJson: string,
-  signingPackageJson: string
+  signingPackageJson: string,
+  groupPublicKeyJson?: string
 ): FrostResult<[SigningState, string | null]> {
   ensureInitialized();
   
   if (wasmAvailable) {
-    const resultJson = frostWasm.signing_round2(stateJson, participantId, keyPackageJson, signingPackageJson);
+    const resultJson = frostWasm.signing_round2(stateJson, participantId, keyPackageJson, signingPackageJson, groupPublicKeyJson);
     return JSON.parse(resultJson);
   } else {
     // Mock implementation
@@ -235,94 +569,1039 @@ export function processSigningRound2(
   }
 }
 
-// === UTILITY FUNCTIONS ===
+/**
+ * Expand participant weights into virtual participant labels, e.g. { server: 3 } gives server#1..server#3
+ *
+ * Deal or generate one share per label; the threshold then counts shares, so weight counts toward it.
+ */
+export function virtualParticipants(weights: Record<string, number>): FrostResult<string[]> {
+  return callWasm(() => frostWasm.virtual_participants(JSON.stringify(weights)));
+}
 
 /**
- * Generate FROST key shares using trusted dealer mode
+ * Run signing round 1 for every share of a weighted participant, keyed by virtual label
  */
-export function generateFrostShares(
-  privateKeyHex: string,
-  threshold: number,
-  maxParticipants: number
-): FrostResult<[string, Record<string, string>]> {
-  ensureInitialized();
-  
-  if (wasmAvailable) {
-    const resultJson = frostWasm.generate_frost_shares(privateKeyHex, threshold, maxParticipants);
-    return JSON.parse(resultJson);
-  } else {
-    // Mock implementation
-    const shares: Record<string, string> = {};
-    for (let i = 1; i <= maxParticipants; i++) {
-      shares[`participant_${i}`] = `mock_share_${i}`;
-    }
-    return {
-      success: true,
-      data: ['mock_group_public_key', shares]
-    };
-  }
+export function processSigningRound1Weighted(
+  stateJson: string,
+  participantId: string,
+  keyPackages: Record<string, string>,
+  groupPublicKeyJson?: string
+): FrostResult<[SigningState, SignerCommitment[]]> {
+  return callWasm(() => frostWasm.signing_round1_weighted(stateJson, participantId, JSON.stringify(keyPackages), groupPublicKeyJson));
 }
 
 /**
- * Verify a FROST signature
+ * Run signing round 2 for every share of a weighted participant, keyed by virtual label
  */
-export function verifyFrostSignature(
-  message: string,
-  signatureJson: string,
+export function processSigningRound2Weighted(
+  stateJson: string,
+  participantId: string,
+  keyPackages: Record<string, string>,
+  signingPackageJson: string,
+  groupPublicKeyJson?: string
+): FrostResult<[SigningState, string | null]> {
+  return callWasm(() => frostWasm.signing_round2_weighted(
+    stateJson, participantId, JSON.stringify(keyPackages), signingPackageJson, groupPublicKeyJson
+  ));
+}
+
+/**
+ * Rebuild a lost coordinator state in round 2 from the message and signers' commitments
+ *
+ * Signers labeled by identifier hex re-sign the returned package and shares go through addSignatureShare.
+ * A signer that lost its nonces must join a new ceremony; shares over fresh commitments are rejected.
+ */
+export function resumeSigningFromTranscript(
+  message: Uint8Array,
+  commitments: SignerCommitment[]
+): FrostResult<[SigningState, string]> {
+  return callWasm(() => frostWasm.resume_signing_from_transcript(message, JSON.stringify(commitments)));
+}
+
+/**
+ * Verify another signer's signature share and store it, aggregating once all shares are in
+ */
+export function addSignatureShare(
+  stateJson: string,
+  identifierHex: string,
+  shareJson: string,
+  signingPackageJson: string,
+  groupPublicKeyJson: string
+): FrostResult<[SigningState, string | null]> {
+  return callWasm(() =>
+    frostWasm.add_signature_share(stateJson, identifierHex, shareJson, signingPackageJson, groupPublicKeyJson)
+  );
+}
+
+export interface SigningRequest {
+  participant_id: string;
+  message: string;
+  signing_package: unknown;
+}
+
+export interface SigningResponse {
+  participant_id: string;
+  identifier: string;
+  share: unknown;
+}
+
+/**
+ * Bundle a signing package into a request for one signer (no secrets included)
+ */
+export function buildSigningRequest(participantId: string, signingPackageJson: string): FrostResult<SigningRequest> {
+  return callWasm(() => frostWasm.build_signing_request(participantId, signingPackageJson));
+}
+
+/**
+ * Answer a signing request with this signer's share, using its own state and key package
+ */
+export function fulfillSigningRequest(
+  stateJson: string,
+  request: SigningRequest,
+  keyPackageJson: string
+): FrostResult<[SigningState, SigningResponse]> {
+  return callWasm(() => frostWasm.fulfill_signing_request(stateJson, JSON.stringify(request), keyPackageJson));
+}
+
+/**
+ * Re-run aggregation over the stored shares; reproduces the recorded signature exactly
+ */
+export function aggregateSignature(
+  stateJson: string,
+  signingPackageJson: string,
   groupPublicKeyJson: string
+): FrostResult<[SigningState, string]> {
+  return callWasm(() => frostWasm.aggregate_signature(stateJson, signingPackageJson, groupPublicKeyJson));
+}
+
+/**
+ * Build the signing package from the state's round 1 commitments, or from commitments
+ * collected by a coordinator
+ */
+export function buildSigningPackage(stateJson: string, commitments?: SignerCommitment[]): FrostResult<string> {
+  const commitmentsJson = commitments && JSON.stringify(commitments);
+  return callWasm(() => frostWasm.build_signing_package(stateJson, commitmentsJson));
+}
+
+export interface CommitmentsBundle {
+  format: string;
+  message_digest: string;
+  commitments: SignerCommitment[];
+  checksum: string;
+}
+
+/**
+ * Export a round 2 state's public commitments, with a checksum, to carry to an air-gapped coordinator
+ */
+export function exportCommitmentsBundle(stateJson: string): FrostResult<CommitmentsBundle> {
+  return callWasm(() => frostWasm.export_commitments_bundle(stateJson));
+}
+
+/**
+ * Build the signing package offline from an exported bundle and the message it was exported for
+ */
+export function buildSigningPackageFromBundle(message: string | Uint8Array, bundle: CommitmentsBundle): FrostResult<string> {
+  const messageBytes = typeof message === 'string' ? new TextEncoder().encode(message) : message;
+  return callWasm(() => frostWasm.build_signing_package_from_bundle(messageBytes, JSON.stringify(bundle)));
+}
+
+/**
+ * Check a coordinator's signing package against one rebuilt from the commitments this signer
+ * received; refuse to sign when it returns false
+ */
+export function rebuildAndCompareSigningPackage(
+  message: Uint8Array,
+  commitments: SignerCommitment[],
+  coordinatorPackageJson: string
 ): FrostResult<boolean> {
-  ensureInitialized();
-  
-  if (wasmAvailable) {
-    const messageBytes = new TextEncoder().encode(message);
-    const resultJson = frostWasm.verify_signature(messageBytes, signatureJson, groupPublicKeyJson);
-    return JSON.parse(resultJson);
-  } else {
-    // Mock implementation - always return true for testing
-    return {
-      success: true,
-      data: true
-    };
-  }
+  return callWasm(() =>
+    frostWasm.rebuild_and_compare_signing_package(message, JSON.stringify(commitments), coordinatorPackageJson)
+  );
 }
 
-// === HELPER FUNCTIONS ===
+/**
+ * Hash each signer's round 1 nonce commitments (identifier hex to SHA-256 hex) for an audit log
+ */
+export function commitmentFingerprints(stateJson: string): FrostResult<Record<string, string>> {
+  return callWasm(() => frostWasm.commitment_fingerprints(stateJson));
+}
 
 /**
- * Ensure FROST is initialized before making calls
+ * The group nonce commitment R (compressed SEC1 hex) that a signing package's shares will
+ * aggregate to. Needs the group public key because binding factors hash it in.
  */
-function ensureInitialized(): void {
-  if (!frostInitialized) {
-    throw new Error('FROST not initialized. Call initializeFrost() first.');
-  }
+export function aggregateNonce(signingPackageJson: string, groupPublicKeyJson: string): FrostResult<string> {
+  return callWasm(() => frostWasm.aggregate_nonce(signingPackageJson, groupPublicKeyJson));
 }
 
 /**
- * Helper to parse and validate FROST results
+ * Aggregate shares (identifier hex -> share JSON) and verify the signature without touching state
+ *
+ * Returns the signature only if every share and the aggregate verify, so it can be checked before persisting.
  */
-export function parseFrostResult<T>(resultJson: string): T {
-  try {
-    const result: FrostResult<T> = JSON.parse(resultJson);
-    if (!result.success) {
-      throw new Error(result.error || 'FROST operation failed');
-    }
-    if (result.data === undefined) {
-      throw new Error('FROST operation returned no data');
-    }
-    return result.data;
-  } catch (error) {
-    if (error instanceof SyntaxError) {
-      throw new Error(`Invalid FROST result JSON: ${error.message}`);
-    }
-    throw error;
-  }
+export function tryAggregatePreview(
+  signingPackageJson: string,
+  shares: Record<string, string>,
+  groupPublicKeyJson: string
+): FrostResult<string> {
+  return callWasm(() => frostWasm.try_aggregate_preview(signingPackageJson, JSON.stringify(shares), groupPublicKeyJson));
+}
+
+export interface SignerInclusionProof {
+  format: string;
+  identifier: string;
+  message: string;
+  commitments: SignerCommitment[];
+  signature_share: string;
+  verifying_share: string;
+  group_public_key: string;
+}
+
+/**
+ * Prove a signer's share was valid for the signing package built from these commitments
+ *
+ * The proof holds only public values; the group public key supplies the signer's verifying share.
+ */
+export function signerInclusionProof(
+  identifier: string,
+  signatureShareJson: string,
+  commitments: SignerCommitment[],
+  signingPackageJson: string,
+  groupPublicKeyJson: string
+): FrostResult<SignerInclusionProof> {
+  return callWasm(() => frostWasm.signer_inclusion_proof(
+    identifier, signatureShareJson, JSON.stringify(commitments), signingPackageJson, groupPublicKeyJson
+  ));
+}
+
+/**
+ * Check an inclusion proof, and with the final signature that the share was part of it
+ */
+export function verifySignerInclusionProof(proof: SignerInclusionProof, signatureJson?: string): FrostResult<boolean> {
+  return callWasm(() => frostWasm.verify_signer_inclusion_proof(JSON.stringify(proof), signatureJson));
+}
+
+export interface NostrEvent {
+  id: string;
+  pubkey: string;
+  created_at: number;
+  kind: number;
+  tags: string[][];
+  content: string;
+  sig: string;
+}
+
+export interface ApprovalSummary {
+  message: string;
+  event?: NostrEvent | null;
+  signers: string[];
+  digest: string;
+}
+
+/**
+ * Summarize a signing package for human approval
+ *
+ * When the message is an event id, pass the event to have its kind and content
+ * included; it is rejected unless its id equals the message.
+ */
+export function signingApprovalSummary(signingPackageJson: string, eventJson?: string): FrostResult<ApprovalSummary> {
+  return callWasm(() => frostWasm.signing_approval_summary(signingPackageJson, eventJson));
+}
+
+/**
+ * A round 1 commitment in the implementation-neutral wire format (RFC 9591 encodings)
+ */
+export interface WireCommitment {
+  format: string;
+  identifier: string;
+  hiding: string;
+  binding: string;
+}
+
+/**
+ * Encode a signer commitment for exchange with other FROST implementations
+ */
+export function commitmentToWire(commitment: SignerCommitment): FrostResult<WireCommitment> {
+  return callWasm(() => frostWasm.commitment_to_wire(JSON.stringify(commitment)));
+}
+
+/**
+ * Decode a wire format commitment into a signer commitment
+ */
+export function commitmentFromWire(wire: WireCommitment): FrostResult<SignerCommitment> {
+  return callWasm(() => frostWasm.commitment_from_wire(JSON.stringify(wire)));
+}
+
+/**
+ * Drop a signer that committed in round 1 but never submitted a signature share
+ */
+export function dropSigner(stateJson: string, participantId: string): FrostResult<[SigningState, string | null]> {
+  return callWasm(() => frostWasm.drop_signer(stateJson, participantId));
+}
+
+/**
+ * Move a signer's unused nonces out of the state so another process can sign with them
+ */
+export function exportSigningNonces(stateJson: string, participantId: string): FrostResult<[SigningState, string]> {
+  return callWasm(() => frostWasm.export_signing_nonces(stateJson, participantId));
+}
+
+/**
+ * Restore exported nonces into a signing state; each export can be imported once
+ */
+export function importSigningNonces(stateJson: string, exportedJson: string): FrostResult<SigningState> {
+  return callWasm(() => frostWasm.import_signing_nonces(stateJson, exportedJson));
+}
+
+export interface SignedMessageCache {
+  group_id: string;
+  message_hashes: string[];
+}
+
+export interface MessageCheck {
+  already_signed: boolean;
+  cache: SignedMessageCache;
+}
+
+/**
+ * Advisory replay check: flags a message this group already signed and records new ones
+ *
+ * Persist the returned cache once the signature is produced.
+ */
+export function checkMessageNotSigned(
+  groupId: string,
+  message: Uint8Array,
+  cache?: SignedMessageCache
+): FrostResult<MessageCheck> {
+  return callWasm(() => frostWasm.check_message_not_signed(groupId, message, cache && JSON.stringify(cache)));
+}
+
+export interface SignerPolicy {
+  required?: string[];
+  optional?: string[]; // When given, signers outside required and optional are rejected
+  min_signers: number;
+}
+
+export type PolicyFailure =
+  | { requirement: 'required_signer'; signer: string }
+  | { requirement: 'allowed_signers'; signer: string }
+  | { requirement: 'min_signers'; required: number; actual: number };
+
+export interface PolicyCheck {
+  satisfied: boolean;
+  failures: PolicyFailure[];
+}
+
+/**
+ * Evaluate a per-operation signer policy (required, optional, minimum count) against the signers
+ */
+export function checkSignerPolicy(signers: string[], policy: SignerPolicy): FrostResult<PolicyCheck> {
+  return callWasm(() => frostWasm.check_signer_policy(JSON.stringify(signers), JSON.stringify(policy)));
+}
+
+// === UTILITY FUNCTIONS ===
+
+/**
+ * Generate FROST key shares using trusted dealer mode
+ *
+ * Shares are keyed by `labels` (one per participant, in identifier order),
+ * or `participant_1..n` when no labels are given.
+ */
+export function generateFrostShares(
+  privateKeyHex: string,
+  threshold: number,
+  maxParticipants: number,
+  labels?: string[]
+): FrostResult<[string, Record<string, string>]> {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    const resultJson = frostWasm.generate_frost_shares(privateKeyHex, threshold, maxParticipants, labels && JSON.stringify(labels));
+    return JSON.parse(resultJson);
+  } else {
+    // Mock implementation
+    const shares: Record<string, string> = {};
+    for (let i = 1; i <= maxParticipants; i++) {
+      shares[labels?.[i - 1] ?? `participant_${i}`] = `mock_share_${i}`;
+    }
+    return {
+      success: true,
+      data: ['mock_group_public_key', shares]
+    };
+  }
+}
+
+export interface DealerOutput {
+  group_public_key: string;
+  shares: Record<string, string>;
+  identifiers: Record<string, string>;
+}
+
+/**
+ * Wrap a key package or dealer share in a checksummed envelope for storage. Anything that
+ * takes a key package accepts the envelope and fails with CorruptedShare if it changed.
+ */
+export function checksumShare(shareJson: string): FrostResult<string> {
+  return callWasm(() => frostWasm.checksum_share(shareJson));
+}
+
+/**
+ * Encode a key package as a 75-word BIP39-style mnemonic for a cold backup. The words are the secret share.
+ */
+export function shareToMnemonic(keyPackageJson: string): FrostResult<string> {
+  return callWasm(() => frostWasm.share_to_mnemonic(keyPackageJson));
+}
+
+/**
+ * Restore a key package from its mnemonic; a wrong word fails the checksum with CorruptedShare
+ */
+export function shareFromMnemonic(words: string): FrostResult<string> {
+  return callWasm(() => frostWasm.share_from_mnemonic(words));
+}
+
+/**
+ * Generate key shares for many independent groups in one call (trusted dealer mode)
+ */
+export function generateFrostSharesBatch(
+  specs: { threshold: number; max_participants: number; labels?: string[] }[]
+): FrostResult<DealerOutput[]> {
+  return callWasm(() => frostWasm.generate_frost_shares_batch(JSON.stringify(specs)));
+}
+
+/**
+ * Split an existing private key into shares reproducibly: the same key, parameters, and domainSep
+ * always give the same shares
+ *
+ * No fresh randomness is used, so anyone holding the key can recompute every share. Use a distinct
+ * domainSep per split, and generateFrostShares when reproducibility isn't needed.
+ */
+export function splitDeterministic(
+  privateKeyHex: string,
+  threshold: number,
+  maxParticipants: number,
+  domainSep: string
+): FrostResult<DealerOutput> {
+  return callWasm(() => frostWasm.split_deterministic(privateKeyHex, threshold, maxParticipants, domainSep));
+}
+
+export interface NormalizedGroup {
+  group_public_key: string;
+  key_packages: Record<string, string>;
+  identifiers: Record<string, string>;
+}
+
+/**
+ * Relabel key packages as participant_1..n in identifier order (shares and group key unchanged)
+ */
+export function normalizeIdentifiers(
+  groupPublicKeyJson: string,
+  keyPackages: Record<string, string>
+): FrostResult<NormalizedGroup> {
+  return callWasm(() => frostWasm.normalize_identifiers(groupPublicKeyJson, JSON.stringify(keyPackages)));
+}
+
+export interface ShareSetValidation {
+  subsets_checked: string[][];
+}
+
+/**
+ * Check every dealer share against its verifying share and that covering threshold subsets
+ * interpolate to the group key
+ */
+export function validateShareSet(
+  shares: Record<string, string>,
+  groupPublicKeyJson: string,
+  threshold: number
+): FrostResult<ShareSetValidation> {
+  return callWasm(() => frostWasm.validate_share_set(JSON.stringify(shares), groupPublicKeyJson, threshold));
+}
+
+export type ParticipationIssue =
+  | 'other_group'
+  | 'not_a_member'
+  | 'verifying_share_mismatch'
+  | 'inconsistent_key_package'
+  | 'threshold_mismatch'
+  | 'threshold_unreachable';
+
+export interface ParticipationCheck {
+  identifier: string;
+  member: boolean;
+  can_reach_threshold: boolean;
+  reasons: ParticipationIssue[];
+}
+
+/**
+ * Pre-flight check that a stored key package belongs to the group and can sign at the threshold
+ */
+export function canParticipate(
+  keyPackageJson: string,
+  groupPublicKeyJson: string,
+  threshold: number
+): FrostResult<ParticipationCheck> {
+  return callWasm(() => frostWasm.can_participate(keyPackageJson, groupPublicKeyJson, threshold));
+}
+
+export type AuditFinding =
+  | { finding: 'missing_member'; member: string }
+  | { finding: 'extra_member'; member: string }
+  | { finding: 'unreadable_share'; member: string; reason: string }
+  | { finding: 'other_group'; member: string }
+  | { finding: 'wrong_verifying_share'; member: string; identifier: string }
+  | { finding: 'inconsistent_key_package'; member: string }
+  | { finding: 'duplicate_identifier'; member: string; identifier: string }
+  | { finding: 'threshold_mismatch'; member: string; threshold: number }
+  | { finding: 'threshold_unreachable'; threshold: number; group_size: number };
+
+export interface GroupAudit {
+  healthy: boolean;
+  findings: AuditFinding[];
+}
+
+/**
+ * Health check of a group's key packages (label -> key package) against its stored threshold and member list
+ */
+export function auditGroup(
+  groupPublicKeyJson: string,
+  keyPackages: Record<string, string>,
+  expectedThreshold: number,
+  expectedMembers: string[]
+): FrostResult<GroupAudit> {
+  return callWasm(() => frostWasm.audit_group(
+    groupPublicKeyJson, JSON.stringify(keyPackages), expectedThreshold, JSON.stringify(expectedMembers)
+  ));
+}
+
+export interface KeyRotationRecord {
+  old_group_key: string;
+  new_group_key: string;
+  created_at: number;
+  signature: string;
+}
+
+/**
+ * Sign a statement with a threshold of the old group's key packages authorizing the new group key
+ */
+export function rotateGroupKeyAttestation(
+  oldGroupPublicKeyJson: string,
+  newGroupPublicKeyJson: string,
+  keyPackages: Record<string, string>,
+  createdAt: number
+): FrostResult<KeyRotationRecord> {
+  return callWasm(() => frostWasm.rotate_group_key_attestation(
+    oldGroupPublicKeyJson,
+    newGroupPublicKeyJson,
+    JSON.stringify(keyPackages),
+    BigInt(createdAt)
+  ));
+}
+
+/**
+ * Check that a key rotation record was signed by its old group key
+ */
+export function verifyKeyRotation(record: KeyRotationRecord): FrostResult<boolean> {
+  return callWasm(() => frostWasm.verify_key_rotation(JSON.stringify(record)));
+}
+
+/**
+ * Verify a FROST signature
+ */
+export function verifyFrostSignature(
+  message: string,
+  signatureJson: string,
+  groupPublicKeyJson: string
+): FrostResult<boolean> {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    const messageBytes = new TextEncoder().encode(message);
+    const resultJson = frostWasm.verify_signature(messageBytes, signatureJson, groupPublicKeyJson);
+    return JSON.parse(resultJson);
+  } else {
+    // Mock implementation - always return true for testing
+    return {
+      success: true,
+      data: true
+    };
+  }
+}
+
+/**
+ * Enable or disable per-step timing collection in FROST results
+ */
+export function setCollectTimings(enabled: boolean): void {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    frostWasm.set_collect_timings(enabled);
+  }
+}
+
+/**
+ * Allow or prevent share verification from using the thread pool (parallel builds only)
+ */
+export function setParallelVerification(enabled: boolean): void {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    frostWasm.set_parallel_verification(enabled);
+  }
+}
+
+/**
+ * Serialize round function results through one reusable, zeroized buffer to limit heap
+ * fragmentation in long-running isolates. Turning it off frees the buffer.
+ */
+export function setBufferPooling(enabled: boolean): void {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    frostWasm.set_buffer_pooling(enabled);
+  }
+}
+
+/**
+ * Size of the module's linear memory in bytes; it never shrinks, so this is the peak so far
+ */
+export function wasmMemoryBytes(): number {
+  ensureInitialized();
+  
+  return wasmAvailable ? frostWasm.wasm_memory_bytes() : 0;
+}
+
+/**
+ * Override the input size limits (defaults: 1 MiB JSON per argument, 64 KiB messages, 255 participants)
+ */
+export function setInputLimits(maxJsonBytes: number, maxMessageBytes: number, maxParticipants: number): void {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    frostWasm.set_input_limits(maxJsonBytes, maxMessageBytes, maxParticipants);
+  }
+}
+
+export type FrostLogEvent =
+  | { event: 'round_started'; ceremony: 'keygen' | 'signing'; round: number; ceremony_id: string | null }
+  | { event: 'package_accepted'; ceremony: 'keygen' | 'signing'; round: number; participant: string; ceremony_id: string | null }
+  | { event: 'aggregation_attempted'; shares: number; success: boolean; ceremony_id: string | null };
+
+/**
+ * Receive ceremony events (never key material or messages); call with no argument to stop
+ */
+export function setLogCallback(callback?: (event: FrostLogEvent) => void): void {
+  ensureInitialized();
+  
+  if (wasmAvailable) {
+    frostWasm.set_log_callback(callback ? (eventJson: string) => callback(JSON.parse(eventJson)) : undefined);
+  }
+}
+
+/**
+ * Describe a FrostErrorCode in plain English
+ */
+export function errorMessage(code: number): string {
+  ensureInitialized();
+  
+  if (!wasmAvailable) {
+    return 'FROST WASM module not available';
+  }
+  return frostWasm.error_message(code);
+}
+
+/**
+ * Re-serialize a state or package as canonical JSON (sorted keys, no whitespace)
+ */
+export function toCanonicalJson(json: string): FrostResult<string> {
+  return callWasm(() => frostWasm.to_canonical_json(json));
+}
+
+export interface NostrEventVerification {
+  id_matches: boolean;
+  pubkey_matches: boolean;
+  signature_valid: boolean;
+  valid: boolean;
+}
+
+export interface NostrCompatibility {
+  xonly_pubkey: string;
+  parity_adjustment_needed: boolean;
+}
+
+/**
+ * Check the group key works as a Nostr x-only pubkey and whether it has odd y
+ *
+ * Trusted dealer keys are always even-y; DKG keys may need a parity adjustment.
+ */
+export function assertNostrCompatible(groupPublicKeyJson: string): FrostResult<NostrCompatibility> {
+  return callWasm(() => frostWasm.assert_nostr_compatible(groupPublicKeyJson));
+}
+
+/**
+ * Verify a signed Nostr event against the FROST group public key
+ */
+export function verifyNostrEvent(eventJson: string, groupPublicKeyJson: string): FrostResult<NostrEventVerification> {
+  return callWasm(() => frostWasm.verify_nostr_event(eventJson, groupPublicKeyJson));
+}
+
+/**
+ * The exact NIP-01 serialization an event's id is the SHA-256 of
+ *
+ * Only `\n`, `\"`, `\\`, `\r`, `\t`, `\b`, and `\f` are escaped; other control characters and
+ * emoji are written verbatim, unlike `JSON.stringify`.
+ */
+export function canonicalizeEvent(eventJson: string): FrostResult<string> {
+  return callWasm(() => frostWasm.canonicalize_event(eventJson));
+}
+
+/**
+ * Insert the pubkey, id, and sig into an event template, checking the signature over the
+ * recomputed id. Returns the ready-to-publish event JSON.
+ */
+export function finalizeNostrEvent(eventTemplateJson: string, signatureHex: string, xonlyPubkeyHex: string): FrostResult<string> {
+  return callWasm(() => frostWasm.finalize_nostr_event(eventTemplateJson, signatureHex, xonlyPubkeyHex));
+}
+
+/**
+ * The canonical NIP-05-style attestation that a pubkey belongs to a domain. Sign its UTF-8
+ * bytes in a signing ceremony; verifiers rebuild it from the domain and pubkey.
+ */
+export function buildAttestationMessage(domain: string, xonlyPubkeyHex: string): FrostResult<string> {
+  return callWasm(() => frostWasm.build_attestation_message(domain, xonlyPubkeyHex));
+}
+
+/**
+ * Verify a group's FROST signature over the attestation for a domain and pubkey
+ */
+export function verifyAttestation(
+  domain: string,
+  xonlyPubkeyHex: string,
+  signatureJson: string,
+  groupPublicKeyJson: string
+): FrostResult<boolean> {
+  return callWasm(() => frostWasm.verify_attestation(domain, xonlyPubkeyHex, signatureJson, groupPublicKeyJson));
+}
+
+/**
+ * Seal a ceremony state with an HMAC bound to its ceremony id and round
+ */
+export function sealState(stateJson: string, macKey: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.seal_state(stateJson, macKey));
+}
+
+/**
+ * Check a sealed state's HMAC and return the state JSON it protects
+ */
+export function openState(sealedJson: string, macKey: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.open_state(sealedJson, macKey));
+}
+
+/**
+ * Strict BIP340 verification of a 64-byte hex signature under a 32-byte x-only pubkey
+ */
+export function verifySignatureXonly(
+  message: Uint8Array,
+  signatureHex: string,
+  xonlyPubkeyHex: string
+): FrostResult<boolean> {
+  return callWasm(() => frostWasm.verify_signature_xonly(message, signatureHex, xonlyPubkeyHex));
+}
+
+/**
+ * Strict BIP340 verification of a 64-byte hex signature under a NIP-19 npub
+ */
+export function verifySignatureNpub(message: Uint8Array, signatureHex: string, npub: string): FrostResult<boolean> {
+  return callWasm(() => frostWasm.verify_signature_npub(message, signatureHex, npub));
+}
+
+/**
+ * Single-key BIP340 signature, with optional 32 bytes of aux_rand (fresh randomness when omitted)
+ */
+export function signSchnorr(
+  message: Uint8Array,
+  secretKeyHex: string,
+  auxRand?: Uint8Array
+): FrostResult<string> {
+  return callWasm(() => frostWasm.sign_schnorr(message, secretKeyHex, auxRand));
+}
+
+/**
+ * The NIP-46 `connect` response content ("ack", or the nostrconnect:// secret), to be
+ * NIP-44 encrypted to the client
+ */
+export function nip46ConnectAck(requestId: string, clientPubkey: string, secret?: string): FrostResult<string> {
+  return callWasm(() => frostWasm.nip46_connect_ack(requestId, clientPubkey, secret));
+}
+
+/**
+ * NIP-44 v2 conversation key between a secret key and a peer's x-only pubkey, hex encoded.
+ * Use a dedicated transport key: the FROST group secret is never held whole.
+ */
+export function nip44ConversationKey(secretKeyHex: string, peerPubkeyHex: string): FrostResult<string> {
+  return callWasm(() => frostWasm.nip44_conversation_key(secretKeyHex, peerPubkeyHex));
+}
+
+/**
+ * NIP-44 v2 encrypt to a peer, returning the base64 payload. Omit the nonce outside of tests.
+ */
+export function nip44Encrypt(
+  plaintext: string,
+  secretKeyHex: string,
+  peerPubkeyHex: string,
+  nonce?: Uint8Array
+): FrostResult<string> {
+  return callWasm(() => frostWasm.nip44_encrypt(plaintext, secretKeyHex, peerPubkeyHex, nonce));
+}
+
+/**
+ * NIP-44 v2 decrypt a base64 payload from a peer
+ */
+export function nip44Decrypt(payload: string, secretKeyHex: string, peerPubkeyHex: string): FrostResult<string> {
+  return callWasm(() => frostWasm.nip44_decrypt(payload, secretKeyHex, peerPubkeyHex));
+}
+
+/**
+ * NIP-44 v2 encrypt under a conversation key from ecdhAggregate
+ */
+export function nip44EncryptWithKey(plaintext: string, conversationKeyHex: string, nonce?: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.nip44_encrypt_with_key(plaintext, conversationKeyHex, nonce));
+}
+
+/**
+ * NIP-44 v2 decrypt under a conversation key from ecdhAggregate
+ */
+export function nip44DecryptWithKey(payload: string, conversationKeyHex: string): FrostResult<string> {
+  return callWasm(() => frostWasm.nip44_decrypt_with_key(payload, conversationKeyHex));
+}
+
+export interface EcdhPartial {
+  point: string;
+  proof: string;
+}
+
+export interface EcdhState {
+  peer_pubkey: string;
+  threshold: number;
+  signers: string[];
+  verifying_shares: Record<string, string>;
+  partials: Record<string, EcdhPartial>;
+}
+
+/**
+ * Open a threshold ECDH session between the group key and a peer's x-only pubkey.
+ * `signers` are identifier hex strings from the group public key package.
+ */
+export function ecdhRound1(
+  peerPubkeyHex: string,
+  signers: string[],
+  groupPublicKeyJson: string,
+  threshold: number
+): FrostResult<EcdhState> {
+  return callWasm(() => frostWasm.ecdh_round1(peerPubkeyHex, JSON.stringify(signers), groupPublicKeyJson, threshold));
+}
+
+/**
+ * Add a signer's proven partial ECDH point; the state is the FrostResult from the previous step
+ */
+export function ecdhRound2(stateJson: string, keyPackageJson: string): FrostResult<EcdhState> {
+  return callWasm(() => frostWasm.ecdh_round2(stateJson, keyPackageJson));
+}
+
+/**
+ * Check every partial and combine them into the NIP-44 conversation key, hex encoded
+ */
+export function ecdhAggregate(stateJson: string): FrostResult<string> {
+  return callWasm(() => frostWasm.ecdh_aggregate(stateJson));
+}
+
+/**
+ * BIP340 tagged hash of a message, hex encoded
+ */
+export function taggedHash(tag: string, message: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.tagged_hash(tag, message));
+}
+
+/**
+ * SHA-256 of a message, hex encoded
+ */
+export function sha256(message: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.sha256(message));
+}
+
+// Decoders return a JSON byte array; hand callers a Uint8Array instead
+function asBytes(result: FrostResult<number[]>): FrostResult<Uint8Array> {
+  return { ...result, data: result.data && Uint8Array.from(result.data) };
+}
+
+/**
+ * Encode bytes as lowercase hex
+ */
+export function encodeHex(bytes: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.encode_hex(bytes));
+}
+
+/**
+ * Decode hex (either case), rejecting odd lengths and non-hex characters
+ */
+export function decodeHex(valueHex: string): FrostResult<Uint8Array> {
+  return asBytes(callWasm(() => frostWasm.decode_hex(valueHex)));
+}
+
+/**
+ * Encode bytes as unpadded base64url
+ */
+export function encodeBase64url(bytes: Uint8Array): FrostResult<string> {
+  return callWasm(() => frostWasm.encode_base64url(bytes));
+}
+
+/**
+ * Decode unpadded base64url, rejecting padding and the standard alphabet
+ */
+export function decodeBase64url(value: string): FrostResult<Uint8Array> {
+  return asBytes(callWasm(() => frostWasm.decode_base64url(value)));
+}
+
+export type SignatureFormat = 'compact64' | 'hex' | 'base64';
+
+/**
+ * Re-encode a FROST (65-byte) or BIP340 (64-byte) signature JSON; compact64 is the 64-byte BIP340 form as hex
+ */
+export function signatureToFormat(signatureJson: string, format: SignatureFormat): FrostResult<string> {
+  return callWasm(() => frostWasm.signature_to_format(signatureJson, format));
+}
+
+// === HELPER FUNCTIONS ===
+
+/**
+ * Ensure FROST is initialized before making calls
+ */
+function ensureInitialized(): void {
+  if (!frostInitialized) {
+    throw new Error('FROST not initialized. Call initializeFrost() first.');
+  }
+}
+
+/**
+ * Call a WASM function that has no mock fallback
+ */
+function callWasm<T>(call: () => string): FrostResult<T> {
+  ensureInitialized();
+  
+  if (!wasmAvailable) {
+    return { success: false, error: 'FROST WASM module not available' };
+  }
+  return JSON.parse(call());
+}
+
+/**
+ * Helper to parse and validate FROST results
+ */
+export function parseFrostResult<T>(resultJson: string): T {
+  try {
+    const result: FrostResult<T> = JSON.parse(resultJson);
+    if (!result.success) {
+      throw new Error(result.error || 'FROST operation failed');
+    }
+    if (result.data === undefined) {
+      throw new Error('FROST operation returned no data');
+    }
+    return result.data;
+  } catch (error) {
+    if (error instanceof SyntaxError) {
+      throw new Error(`Invalid FROST result JSON: ${error.message}`);
+    }
+    throw error;
+  }
+}
+
+/**
+ * Generate cryptographically secure random bytes (hex), at most 1024 per call
+ */
+export function randomBytes(length: number): FrostResult<string> {
+  return callWasm(() => frostWasm.random_bytes(length));
+}
+
+export interface StateSizeEstimate {
+  keygen_bytes: number;
+  signing_bytes: number;
+}
+
+/**
+ * Estimate the serialized size of completed keygen and signing states for a ceremony shape
+ */
+export function estimateStateSize(threshold: number, maxParticipants: number): FrostResult<StateSizeEstimate> {
+  return callWasm(() => frostWasm.estimate_state_size(threshold, maxParticipants));
+}
+
+/**
+ * Confirm the host's secure random source works; fails with RngUnavailable otherwise
+ */
+export function selfTest(): FrostResult<boolean> {
+  return callWasm(() => frostWasm.self_test());
+}
+
+export interface Capabilities {
+  version: string;
+  ciphersuite: string;
+  keygen: boolean;
+  signing: boolean;
+  trusted_dealer: boolean;
+  nostr_verification: boolean;
+  repair: boolean;
+  reshare: boolean;
+  taproot: boolean;
+  secret_recovery: boolean;
+}
+
+/**
+ * Report the ciphersuite and features compiled into the loaded WASM module
+ */
+export function capabilities(): FrostResult<Capabilities> {
+  return callWasm(() => frostWasm.capabilities());
+}
+
+export interface BuildInfo {
+  version: string;
+  frost_core: string;
+  frost_secp256k1: string;
+  frost_secp256k1_tr: string;
+  git_commit: string;
+  ciphersuite: string;
+  features: string[];
+}
+
+/**
+ * Exact FROST crate versions, git commit, and features of the loaded WASM module,
+ * for diagnosing packages that fail to deserialize across versions
+ */
+export function buildInfo(): FrostResult<BuildInfo> {
+  return callWasm(() => frostWasm.build_info());
+}
+
+export interface CompletionStatus {
+  ceremony: 'keygen' | 'signing';
+  complete: boolean;
+  result: string | null; // Group public key or final signature once complete
+}
+
+/**
+ * Report whether a keygen or signing state is terminal, with its outcome if so
+ */
+export function isComplete(stateJson: string): FrostResult<CompletionStatus> {
+  return callWasm(() => frostWasm.is_complete(stateJson));
+}
+
+/**
+ * Report whether a keygen or signing state has expired as of nowUnix (seconds) and should be discarded
+ */
+export function checkExpired(stateJson: string, nowUnix: number): FrostResult<boolean> {
+  return callWasm(() => frostWasm.check_expired(stateJson, BigInt(nowUnix)));
 }
 
 /**
  * Get human-readable status for ceremony states
  */
 export function getCeremonyStatus(state: KeygenState | SigningState): string {
-  if ('threshold' in state) {
+  if (state.kind === 'keygen' || (state.kind === undefined && 'max_participants' in state)) {
     // Keygen state
     const keygenState = state as KeygenState;
     if (keygenState.group_public_key) return 'READY';
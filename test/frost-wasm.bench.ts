@@ -0,0 +1,145 @@
+// ABOUTME: Benchmarks for FROST WASM operations that have batched alternatives
+// ABOUTME: Run with `npm run bench` to compare batched and looped calls, serial and parallel paths, and buffer pooling
+
+import { bench, describe, beforeAll, afterAll } from 'vitest';
+import {
+  initializeFrost,
+  generateFrostShares,
+  generateFrostSharesBatch,
+  createKeygenCeremony,
+  processKeygenRound1,
+  processKeygenRound2,
+  processKeygenRound2Batch,
+  createSigningCeremony,
+  processSigningRound1,
+  processSigningRound2,
+  buildSigningPackage,
+  aggregateSignature,
+  setParallelVerification,
+  setBufferPooling,
+  wasmMemoryBytes,
+} from '../src/frost';
+
+const GROUPS = 50;
+
+describe('Trusted dealer provisioning', () => {
+  beforeAll(async () => {
+    await initializeFrost();
+  });
+
+  bench(`${GROUPS} looped generate_frost_shares calls`, () => {
+    for (let i = 0; i < GROUPS; i++) {
+      generateFrostShares('', 2, 3);
+    }
+  });
+
+  bench(`one generate_frost_shares_batch call for ${GROUPS} groups`, () => {
+    generateFrostSharesBatch(Array.from({ length: GROUPS }, () => ({ threshold: 2, max_participants: 3 })));
+  });
+});
+
+describe('DKG round 2 for server-held participants', () => {
+  const participants = ['alice', 'bob', 'carol'];
+  const held = ['alice', 'bob'];
+  let stateJson = '';
+  let round1PackagesJson = '';
+
+  beforeAll(async () => {
+    await initializeFrost();
+    let state = createKeygenCeremony(2, participants.length).data!;
+    for (const participant of participants) {
+      [state] = processKeygenRound1(JSON.stringify({ success: true, data: state }), participant).data!;
+    }
+    stateJson = JSON.stringify({ success: true, data: state });
+    round1PackagesJson = JSON.stringify(state.round1_packages);
+  });
+
+  bench(`${held.length} looped keygen_round2 calls`, () => {
+    let current = stateJson;
+    for (const participant of held) {
+      const [state] = processKeygenRound2(current, participant, round1PackagesJson).data!;
+      current = JSON.stringify({ success: true, data: state });
+    }
+  });
+
+  bench(`one keygen_round2_batch call for ${held.length} participants`, () => {
+    processKeygenRound2Batch(stateJson, held, round1PackagesJson);
+  });
+});
+
+describe('Share verification before aggregation', () => {
+  const SIGNERS = 20;
+  const signers = Array.from({ length: SIGNERS }, (_, i) => `signer${i}`);
+  let stateJson = '';
+  let signingPackage = '';
+  let groupPublicKey = '';
+
+  beforeAll(async () => {
+    await initializeFrost();
+    const [dealt] = generateFrostSharesBatch([{ threshold: SIGNERS, max_participants: SIGNERS, labels: signers }]).data!;
+    groupPublicKey = dealt.group_public_key;
+    let state = createSigningCeremony('benchmark', signers).data!;
+    for (const signer of signers) {
+      [state] = processSigningRound1(JSON.stringify({ success: true, data: state }), signer, dealt.shares[signer]).data!;
+    }
+    signingPackage = buildSigningPackage(JSON.stringify({ success: true, data: state })).data!;
+    for (const signer of signers) {
+      [state] = processSigningRound2(
+        JSON.stringify({ success: true, data: state }), signer, dealt.shares[signer], signingPackage, groupPublicKey
+      ).data!;
+    }
+    stateJson = JSON.stringify({ success: true, data: state });
+  });
+
+  // Only a module built with `./build.sh --parallel` and a started thread pool differs
+  bench(`serial verification of ${SIGNERS} shares`, () => {
+    setParallelVerification(false);
+    aggregateSignature(stateJson, signingPackage, groupPublicKey);
+  });
+
+  bench(`parallel verification of ${SIGNERS} shares`, () => {
+    setParallelVerification(true);
+    aggregateSignature(stateJson, signingPackage, groupPublicKey);
+  });
+});
+
+describe('Serialization buffer pooling', () => {
+  const PARTICIPANTS = 10;
+  const participants = Array.from({ length: PARTICIPANTS }, (_, i) => `participant${i}`);
+  const growth: Record<string, number> = {};
+
+  beforeAll(async () => {
+    await initializeFrost();
+  });
+
+  afterAll(() => {
+    setBufferPooling(false);
+    console.log(`Linear memory growth over all ${PARTICIPANTS}-participant keygens (bytes):`, growth);
+  });
+
+  // One full DKG, recording how much linear memory it grew
+  function keygen(variant: string) {
+    const before = wasmMemoryBytes();
+    let state = createKeygenCeremony(PARTICIPANTS, PARTICIPANTS).data!;
+    for (const participant of participants) {
+      [state] = processKeygenRound1(JSON.stringify({ success: true, data: state }), participant).data!;
+    }
+    const round1PackagesJson = JSON.stringify(state.round1_packages);
+    for (const participant of participants) {
+      [state] = processKeygenRound2(JSON.stringify({ success: true, data: state }), participant, round1PackagesJson).data!;
+    }
+    growth[variant] = (growth[variant] ?? 0) + wasmMemoryBytes() - before;
+  }
+
+  // Memory never shrinks, so the pooled run goes first: the unpooled run can
+  // reuse whatever it grew, and any saving shown is a lower bound
+  bench(`${PARTICIPANTS}-participant keygen with pooling`, () => {
+    setBufferPooling(true);
+    keygen('pooled');
+  });
+
+  bench(`${PARTICIPANTS}-participant keygen without pooling`, () => {
+    setBufferPooling(false);
+    keygen('unpooled');
+  });
+});
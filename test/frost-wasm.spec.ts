@@ -1,7 +1,7 @@
 // ABOUTME: Tests for FROST WASM module to verify real cryptographic operations
 // ABOUTME: Validates that threshold signatures work correctly and are not mocked
 
-import { describe, it, expect, beforeAll } from 'vitest';
+import { describe, it, expect, beforeAll, vi } from 'vitest';
 import {
   initializeFrost,
   createKeygenCeremony,
@@ -13,11 +13,152 @@ import {
   generateFrostShares,
   verifyFrostSignature,
   getCeremonyStatus,
+  dropSigner,
+  buildSigningPackage,
+  identifierFromPubkey,
+  verifyNostrEvent,
+  generateFrostSharesBatch,
+  toCanonicalJson,
+  sealState,
+  openState,
+  addSignatureShare,
+  errorMessage,
+  FrostErrorCode,
+  exportSigningNonces,
+  importSigningNonces,
+  setInputLimits,
+  verifySignatureXonly,
+  verifySignatureNpub,
+  normalizeIdentifiers,
+  taggedHash,
+  sha256,
+  isComplete,
+  aggregateSignature,
+  capabilities,
+  buildInfo,
+  randomBytes,
+  removeParticipant,
+  commitmentToWire,
+  commitmentFromWire,
+  signingApprovalSummary,
+  rotateGroupKeyAttestation,
+  verifyKeyRotation,
+  checkMessageNotSigned,
+  dkgPublicTranscript,
+  selfTest,
+  estimateStateSize,
+  validateShareSet,
+  setLogCallback,
+  buildSigningRequest,
+  fulfillSigningRequest,
+  encodeHex,
+  decodeHex,
+  encodeBase64url,
+  decodeBase64url,
+  computeGroupKeyPreview,
+  checkSignerPolicy,
+  createSigningCeremonyBitcoin,
+  taprootOutputKey,
+  loadKeyPackage,
+  releaseKeyPackage,
+  checkExpired,
+  processKeygenRound2Batch,
+  packageKeygenResult,
+  enableCommitReveal,
+  round1PackageHash,
+  keygenCommit,
+  rebuildAndCompareSigningPackage,
+  commitmentFingerprints,
+  nip46ConnectAck,
+  nip44ConversationKey,
+  nip44Encrypt,
+  nip44Decrypt,
+  nip44EncryptWithKey,
+  ecdhRound1,
+  ecdhRound2,
+  ecdhAggregate,
+  aggregateNonce,
+  finalizeNostrEvent,
+  checksumShare,
+  virtualParticipants,
+  processSigningRound1Weighted,
+  processSigningRound2Weighted,
+  canParticipate,
+  resumeSigningFromTranscript,
+  setMaxCeremonies,
+  openCeremony,
+  closeCeremony,
+  activeCeremonyCount,
+  signerInclusionProof,
+  verifySignerInclusionProof,
+  splitDeterministic,
+  assertNostrCompatible,
+  tryAggregatePreview,
+  rewrapShare,
+  auditGroup,
+  signatureToFormat,
+  shareToMnemonic,
+  shareFromMnemonic,
+  exportCommitmentsBundle,
+  buildSigningPackageFromBundle,
+  buildAttestationMessage,
+  verifyAttestation,
+  createSigningCeremonyForGroup,
+  deriveAppSubkey,
+  deriveAppKeyPackage,
+  preflightSigning,
+  canonicalizeEvent,
+  type FrostLogEvent,
+  type SignatureFormat,
+  type SignerCommitment,
   type FrostResult,
   type KeygenState,
   type SigningState
 } from '../src/frost';
 
+// Round functions take the state wrapped in the FrostResult they returned
+function asStateJson(state: KeygenState | SigningState): string {
+  return JSON.stringify({ success: true, data: state });
+}
+
+// Dealer shares for a fresh group, ordered by identifier
+function dealerKeyPackages(threshold: number, maxParticipants: number): string[] {
+  const result = generateFrostShares('', threshold, maxParticipants);
+  expect(result.success).toBe(true);
+  const [, shares] = result.data!;
+  return Object.values(shares);
+}
+
+// Run signing round 1 for each signer with the matching key package
+function commitAll(state: SigningState, signers: string[], keyPackages: string[]): SigningState {
+  signers.forEach((signer, i) => {
+    const result = processSigningRound1(asStateJson(state), signer, keyPackages[i]);
+    expect(result.success).toBe(true);
+    [state] = result.data!;
+  });
+  return state;
+}
+
+// BIP340-signed kind 1 event (secret key 0x5a1e repeated)
+const SIGNED_EVENT = {
+  id: 'c0bc3924a07b9d6f559ab613dd136fb5e1cf3eee77538c9d3349a230f3d2d5ca',
+  pubkey: '58f5d9cbcaeaf692b967ad149b09778ffadfcdb5593b8115b1d0d7eb68d36938',
+  created_at: 1700000000,
+  kind: 1,
+  tags: [['t', 'frost']],
+  content: 'Threshold signed hello',
+  sig: '4cb6c1eacb775d7cc40bba9f7eb36ca2785d24df279908547dcbec82d234f43c32ebbf3de4778c7be43cc05c8954d264dd36c217a83af298a15b1f0c82824f03',
+};
+
+// Public key package whose group key is the given even-y x-only pubkey
+function groupKeyFor(xonlyPubkey: string): string {
+  return JSON.stringify({
+    header: { version: 0, ciphersuite: 'FROST-secp256k1-SHA256-v1' },
+    verifying_shares: {},
+    verifying_key: '02' + xonlyPubkey,
+  });
+}
+
 describe('FROST WASM Module Tests', () => {
   beforeAll(async () => {
     await initializeFrost();
@@ -151,12 +292,12 @@ describe('FROST WASM Module Tests', () => {
         const result = processSigningRound1(JSON.stringify(state), signer, keyPackage);
         expect(result.success).toBe(true);
         
-        const [newState, nonces] = result.data!;
+        const [newState, commitment] = result.data!;
         state = newState;
-        round1Packages[signer] = nonces;
+        round1Packages[signer] = JSON.stringify(commitment);
         
-        console.log(`Round 1 nonces for ${signer}:`, nonces);
-        expect(nonces).toContain(signer);
+        console.log(`Round 1 commitment for ${signer}:`, commitment);
+        expect(commitment.identifier).toContain(signer);
       }
       
       console.log('After Round 1 - State:', getCeremonyStatus(state));
@@ -218,6 +359,24 @@ describe('FROST WASM Module Tests', () => {
       const uniqueShares = new Set(shareValues);
       expect(uniqueShares.size).toBe(shareValues.length);
     });
+    
+    it('should key shares by the given labels in identifier order', () => {
+      const [, shares] = generateFrostShares('', 2, 3, ['carol', 'alice', 'bob']).data!;
+      expect(Object.keys(shares).sort()).toEqual(['alice', 'bob', 'carol']);
+      
+      const identifierOf = (share: string) => BigInt('0x' + JSON.parse(share).identifier);
+      expect(identifierOf(shares.carol)).toBe(1n);
+      expect(identifierOf(shares.alice)).toBe(2n);
+      expect(identifierOf(shares.bob)).toBe(3n);
+      
+      const [, fallback] = generateFrostShares('', 2, 3).data!;
+      expect(Object.keys(fallback)).toEqual(['participant_1', 'participant_2', 'participant_3']);
+    });
+    
+    it('should reject labels that do not name each participant once', () => {
+      const result = generateFrostShares('', 2, 3, ['alice', 'alice', 'bob']);
+      expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
   });
 
   describe('Signature Verification', () => {
@@ -324,9 +483,9 @@ describe('FROST WASM Module Tests', () => {
         const keyPackage = keygenState.key_packages[signer] || `mock_key_${signer}`;
         const result = processSigningRound1(JSON.stringify(signingState), signer, keyPackage);
         expect(result.success).toBe(true);
-        const [newState, nonces] = result.data!;
+        const [newState, commitment] = result.data!;
         signingState = newState;
-        signingRound1Packages[signer] = nonces;
+        signingRound1Packages[signer] = JSON.stringify(commitment);
       }
       console.log('5. Completed signing round 1');
       
@@ -371,4 +530,2395 @@ describe('FROST WASM Module Tests', () => {
       expect(getCeremonyStatus(signingState)).toBe('COMPLETE');
     });
   });
-});
\ No newline at end of file
+
+  describe('Dropping Signers', () => {
+    it('should drop a signer that committed but never signed in a 2-of-3', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['alice', 'bob', 'charlie'];
+      
+      let state = createSigningCeremony('Message with a stalled signer', signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      expect(state.current_round).toBe(2);
+      
+      const result = dropSigner(asStateJson(state), 'charlie');
+      expect(result.success).toBe(true);
+      
+      const [newState, signingPackage] = result.data!;
+      expect(newState.signers).toEqual(['alice', 'bob']);
+      expect(Object.keys(newState.round1_packages)).toEqual(['alice', 'bob']);
+      expect(newState.current_round).toBe(2);
+      
+      // The rebuilt package only carries the remaining commitments
+      expect(signingPackage).not.toBeNull();
+      expect(Object.keys(JSON.parse(signingPackage!).signing_commitments)).toHaveLength(2);
+    });
+
+    it('should refuse to drop below the threshold', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony('Message with too few signers', signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      
+      const result = dropSigner(asStateJson(state), 'bob');
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('InsufficientParticipants');
+    });
+
+    it('should not drop a signer before any threshold is recorded', () => {
+      const state = createSigningCeremony('Message nobody committed to', ['alice', 'bob', 'charlie']).data!;
+      
+      const result = dropSigner(asStateJson(state), 'charlie');
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('InvalidStateTransition');
+    });
+  });
+
+  describe('Canonical JSON', () => {
+    it('should produce identical bytes regardless of key order and whitespace', () => {
+      const state = createKeygenCeremony(2, 3).data!;
+      const reordered = Object.fromEntries(Object.entries(state).reverse());
+      
+      const first = toCanonicalJson(JSON.stringify(state));
+      const second = toCanonicalJson(JSON.stringify(reordered, null, 2));
+      
+      expect(first.success).toBe(true);
+      expect(second.success).toBe(true);
+      expect(first.data).toBe(second.data);
+      expect(first.data).not.toMatch(/\s/);
+      
+      const keys = Object.keys(JSON.parse(first.data!));
+      expect(keys).toEqual([...keys].sort());
+    });
+  });
+
+  describe('Threshold Enforcement', () => {
+    it('should report required and actual counts when signing below threshold', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['alice'];
+      
+      let state = createSigningCeremony('Message signed by too few', signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      
+      const packageResult = buildSigningPackage(asStateJson(state));
+      expect(packageResult.success).toBe(true);
+      
+      const result = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], packageResult.data!);
+      expect(result.success).toBe(false);
+      expect(result.error).toBe('InsufficientParticipants { required: 2, actual: 1 }');
+    });
+  });
+
+  describe('Pubkey-Derived Identifiers', () => {
+    const alicePubkey = '79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798';
+    const bobPubkey = 'c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5';
+
+    it('should always derive the same identifier for the same pubkey', () => {
+      const first = identifierFromPubkey(alicePubkey);
+      const second = identifierFromPubkey(alicePubkey);
+      
+      expect(first.success).toBe(true);
+      expect(first.data).toBe(second.data);
+      expect(identifierFromPubkey(bobPubkey).data).not.toBe(first.data);
+    });
+
+    it('should reject pubkeys that are not 32 bytes', () => {
+      const result = identifierFromPubkey('02' + alicePubkey);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('InvalidParticipant');
+    });
+
+    it('should use the derived identifier during keygen', () => {
+      const state = createKeygenCeremony(2, 3).data!;
+      
+      const result = processKeygenRound1(asStateJson(state), 'alice', alicePubkey);
+      expect(result.success).toBe(true);
+      
+      const [newState] = result.data!;
+      expect(newState.participant_identifiers!['alice']).toBe(identifierFromPubkey(alicePubkey).data);
+    });
+  });
+
+  describe('Nostr Event Verification', () => {
+    const groupKey = groupKeyFor(SIGNED_EVENT.pubkey);
+
+    it('should accept a correctly signed event from the group key', () => {
+      const result = verifyNostrEvent(JSON.stringify(SIGNED_EVENT), groupKey);
+      
+      expect(result.success).toBe(true);
+      expect(result.data).toEqual({
+        id_matches: true,
+        pubkey_matches: true,
+        signature_valid: true,
+        valid: true,
+      });
+    });
+
+    it('should report a tampered event separately from a bad signature', () => {
+      const tampered = { ...SIGNED_EVENT, content: 'Tampered content' };
+      const tamperedResult = verifyNostrEvent(JSON.stringify(tampered), groupKey);
+      expect(tamperedResult.data!.id_matches).toBe(false);
+      expect(tamperedResult.data!.signature_valid).toBe(false);
+      
+      const badSig = { ...SIGNED_EVENT, sig: SIGNED_EVENT.sig.replace(/^4c/, '4d') };
+      const badSigResult = verifyNostrEvent(JSON.stringify(badSig), groupKey);
+      expect(badSigResult.data!.id_matches).toBe(true);
+      expect(badSigResult.data!.signature_valid).toBe(false);
+      expect(badSigResult.data!.valid).toBe(false);
+    });
+
+    it('should flag an event signed by a different key than the group', () => {
+      const otherGroup = groupKeyFor('79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798');
+      const result = verifyNostrEvent(JSON.stringify(SIGNED_EVENT), otherGroup);
+      
+      expect(result.data!.pubkey_matches).toBe(false);
+      expect(result.data!.valid).toBe(false);
+    });
+    
+    it('should finalize a template into the same publishable event', () => {
+      const { id, pubkey, sig, ...template } = SIGNED_EVENT;
+      const result = finalizeNostrEvent(JSON.stringify(template), sig, pubkey);
+      expect(result.success).toBe(true);
+      expect(JSON.parse(result.data!)).toEqual(SIGNED_EVENT);
+      expect(verifyNostrEvent(result.data!, groupKey).data!.valid).toBe(true);
+      expect(id).toBe(JSON.parse(result.data!).id);
+      
+      const badSig = sig.replace(/^4c/, '4d');
+      expect(finalizeNostrEvent(JSON.stringify(template), badSig, pubkey).error_code).toBe(FrostErrorCode.SigningError);
+    });
+
+    it('should canonicalize an event with NIP-01 escaping', () => {
+      const { pubkey, created_at, kind, tags, content } = SIGNED_EVENT;
+      const canonical = canonicalizeEvent(JSON.stringify(SIGNED_EVENT));
+      expect(canonical.data).toBe(JSON.stringify([0, pubkey, created_at, kind, tags, content]));
+      expect(sha256(new TextEncoder().encode(canonical.data!)).data).toBe(SIGNED_EVENT.id);
+      
+      const escaped = { ...SIGNED_EVENT, content: 'a\nb"c\\d\re\tf\bg\fh\u0001i 😀' };
+      expect(canonicalizeEvent(JSON.stringify(escaped)).data)
+        .toBe(`[0,"${pubkey}",${created_at},${kind},[["t","frost"]],"a\\nb\\"c\\\\d\\re\\tf\\bg\\fh\u0001i 😀"]`);
+      expect(canonicalizeEvent('{"kind":1}').error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Batch Trusted Dealer', () => {
+    it('should generate an independent group for each spec', () => {
+      const specs = [
+        { threshold: 2, max_participants: 3 },
+        { threshold: 3, max_participants: 5 },
+      ];
+      
+      const result = generateFrostSharesBatch(specs);
+      expect(result.success).toBe(true);
+      
+      const groups = result.data!;
+      expect(groups).toHaveLength(2);
+      expect(Object.keys(groups[0].shares)).toHaveLength(3);
+      expect(Object.keys(groups[1].shares)).toHaveLength(5);
+      expect(groups[0].group_public_key).not.toBe(groups[1].group_public_key);
+    });
+
+    it('should reject the whole batch when any spec is invalid', () => {
+      const result = generateFrostSharesBatch([
+        { threshold: 2, max_participants: 3 },
+        { threshold: 4, max_participants: 3 },
+      ]);
+      
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('InsufficientParticipants');
+    });
+  });
+
+  describe('State Sealing', () => {
+    const macKey = new Uint8Array(32).fill(7);
+
+    function sealedKeygenState(): string {
+      let state = createKeygenCeremony(2, 3, 'ceremony-1').data!;
+      [state] = processKeygenRound1(asStateJson(state), 'alice').data!;
+      
+      const result = sealState(asStateJson(state), macKey);
+      expect(result.success).toBe(true);
+      return result.data!;
+    }
+
+    it('should return the exact state that was sealed', () => {
+      const state = createSigningCeremony('Message to seal', ['alice', 'bob'], 'ceremony-2').data!;
+      const stateJson = asStateJson(state);
+      
+      const sealed = sealState(stateJson, macKey);
+      expect(sealed.success).toBe(true);
+      expect(JSON.parse(sealed.data!)).toMatchObject({ ceremony_id: 'ceremony-2', round: 1 });
+      
+      const opened = openState(sealed.data!, macKey);
+      expect(opened.success).toBe(true);
+      expect(opened.data).toBe(stateJson);
+    });
+
+    it('should detect a tampered round map', () => {
+      const sealed = JSON.parse(sealedKeygenState());
+      const envelope = JSON.parse(sealed.state);
+      envelope.data.round1_packages.mallory = envelope.data.round1_packages.alice;
+      sealed.state = JSON.stringify(envelope);
+      
+      const result = openState(JSON.stringify(sealed), macKey);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('IntegrityError');
+    });
+
+    it('should detect a replayed ceremony id or round', () => {
+      const sealed = JSON.parse(sealedKeygenState());
+      
+      const otherCeremony = openState(JSON.stringify({ ...sealed, ceremony_id: 'ceremony-9' }), macKey);
+      expect(otherCeremony.error).toContain('IntegrityError');
+      
+      const otherRound = openState(JSON.stringify({ ...sealed, round: 2 }), macKey);
+      expect(otherRound.error).toContain('IntegrityError');
+    });
+
+    it('should reject a state sealed under a different key', () => {
+      const result = openState(sealedKeygenState(), new Uint8Array(32).fill(8));
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('IntegrityError');
+    });
+
+    it('should refuse to seal a state without a ceremony id', () => {
+      const state = createKeygenCeremony(2, 3).data!;
+      const result = sealState(asStateJson(state), macKey);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('ceremony id');
+    });
+  });
+
+  describe('Resuming From Serialized State', () => {
+    it('should finish signing in a fresh WASM instance from the round 1 state', async () => {
+      const message = 'Message signed across a restart';
+      const dealt = generateFrostShares('', 2, 3);
+      expect(dealt.success).toBe(true);
+      const [groupPublicKey, shares] = dealt.data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony(message, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const persisted = asStateJson(state);
+      
+      // Reload the module so nothing from round 1 survives in WASM memory
+      vi.resetModules();
+      const fresh = await import('../src/frost');
+      await fresh.initializeFrost();
+      
+      const signingPackage = fresh.buildSigningPackage(persisted);
+      expect(signingPackage.success).toBe(true);
+      
+      let stateJson = persisted;
+      let finalSignature: string | null = null;
+      signers.forEach((signer, i) => {
+        const result = fresh.processSigningRound2(stateJson, signer, keyPackages[i], signingPackage.data!, groupPublicKey);
+        expect(result.success).toBe(true);
+        const [newState, signature] = result.data!;
+        stateJson = asStateJson(newState);
+        finalSignature = signature;
+      });
+      
+      expect(finalSignature).not.toBeNull();
+      const verified = fresh.verifyFrostSignature(message, finalSignature!, groupPublicKey);
+      expect(verified.data).toBe(true);
+    });
+  });
+
+  describe('1-of-1 Ceremonies', () => {
+    it('should run keygen and signing for a single signer', () => {
+      const message = 'Message from a lone signer';
+      
+      let keygenState = createKeygenCeremony(1, 1).data!;
+      const round1 = processKeygenRound1(asStateJson(keygenState), 'solo');
+      expect(round1.success).toBe(true);
+      [keygenState] = round1.data!;
+      expect(keygenState.current_round).toBe(2);
+      
+      const round2 = processKeygenRound2(asStateJson(keygenState), 'solo', '{}');
+      expect(round2.success).toBe(true);
+      const [finalKeygenState, keyPackage] = round2.data!;
+      const groupPublicKey = finalKeygenState.group_public_key!;
+      expect(groupPublicKey).toBeDefined();
+      expect(JSON.parse(keyPackage).min_signers).toBe(1);
+      
+      let signingState = createSigningCeremony(message, ['solo']).data!;
+      signingState = commitAll(signingState, ['solo'], [keyPackage]);
+      expect(signingState.current_round).toBe(2);
+      
+      const signingPackage = buildSigningPackage(asStateJson(signingState));
+      expect(signingPackage.success).toBe(true);
+      
+      const signed = processSigningRound2(asStateJson(signingState), 'solo', keyPackage, signingPackage.data!, groupPublicKey);
+      expect(signed.success).toBe(true);
+      const [, signature] = signed.data!;
+      expect(signature).not.toBeNull();
+      
+      expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(true);
+    });
+  });
+
+  describe('Incremental Share Validation', () => {
+    const message = 'Message with shares checked on arrival';
+
+    // Alice signs locally; bob's share arrives from elsewhere
+    function coordinatorWithBobShare() {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony(message, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      const [bobState] = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage, groupPublicKey).data!;
+      const [aliceState] = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage, groupPublicKey).data!;
+      
+      return {
+        state: aliceState,
+        bobId: aliceState.signer_identifiers!['bob'],
+        bobShare: bobState.signature_shares['bob'],
+        aliceShare: aliceState.signature_shares['alice'],
+        signingPackage,
+        groupPublicKey,
+        keyPackages,
+      };
+    }
+
+    it('should validate, store, and aggregate a share from another signer', () => {
+      const { state, bobId, bobShare, signingPackage, groupPublicKey } = coordinatorWithBobShare();
+      
+      const result = addSignatureShare(asStateJson(state), bobId, bobShare, signingPackage, groupPublicKey);
+      expect(result.success).toBe(true);
+      
+      const [newState, signature] = result.data!;
+      expect(newState.validated_identifiers).toHaveLength(2);
+      expect(newState.validated_identifiers).toContain(bobId);
+      expect(signature).not.toBeNull();
+      expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(true);
+    });
+
+    it('should reject a bad share on arrival and name its signer', () => {
+      const { state, bobId, aliceShare, signingPackage, groupPublicKey } = coordinatorWithBobShare();
+      
+      const result = addSignatureShare(asStateJson(state), bobId, aliceShare, signingPackage, groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('Invalid signature share from bob');
+    });
+
+    it('should refuse a signing package substituted after the signers committed', () => {
+      const { state, bobId, groupPublicKey, keyPackages } = coordinatorWithBobShare();
+      const substitutions = [
+        { message, error: 'commitment mismatch' },
+        { message: 'A message the signers never approved', error: 'message mismatch' },
+      ];
+      
+      for (const substitution of substitutions) {
+        let other = commitAll(createSigningCeremony(substitution.message, ['alice', 'bob']).data!, ['alice', 'bob'], keyPackages);
+        const substituted = buildSigningPackage(asStateJson(other)).data!;
+        [other] = processSigningRound2(asStateJson(other), 'bob', keyPackages[1], substituted, groupPublicKey).data!;
+        
+        const result = addSignatureShare(asStateJson(state), bobId, other.signature_shares['bob'], substituted, groupPublicKey);
+        expect(result.success).toBe(false);
+        expect(result.error).toContain(substitution.error);
+      }
+    });
+    
+    it('should refuse to aggregate when a stored share is invalid', () => {
+      const { state, aliceShare, signingPackage, groupPublicKey } = coordinatorWithBobShare();
+      state.signature_shares['bob'] = aliceShare;
+      
+      const result = aggregateSignature(asStateJson(state), signingPackage, groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.SigningError);
+      expect(result.error).toContain('Invalid signature share from bob');
+      expect(result.data).toBeNull();
+    });
+  });
+
+  describe('Error Codes', () => {
+    it('should report a stable numeric code alongside the error', () => {
+      const result = createKeygenCeremony(0, 1);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InsufficientParticipants);
+      expect(result.error_code).toBe(2);
+    });
+
+    it('should leave the code unset on success', () => {
+      expect(createKeygenCeremony(2, 3).error_code).toBeUndefined();
+    });
+
+    it('should describe each code in plain English', () => {
+      const codes = Object.values(FrostErrorCode).filter((code): code is number => typeof code === 'number');
+      const messages = codes.map(errorMessage);
+      
+      expect(codes.length).toBeGreaterThan(0);
+      expect(new Set(messages).size).toBe(codes.length);
+      expect(messages).not.toContain('Unknown error');
+      expect(errorMessage(9999)).toBe('Unknown error');
+    });
+
+    it('should report malformed input separately from serialization failures', () => {
+      const badState = processKeygenRound1('{"success": true, "data": {"threshold": "two"}}', 'alice');
+      expect(badState.error_code).toBe(FrostErrorCode.MalformedInput);
+      
+      const badKey = verifyFrostSignature('message', '"00"', 'not json');
+      expect(badKey.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Nonce Hand-off', () => {
+    const message = 'Message signed after a nonce hand-off';
+
+    function committedState() {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony(message, signers, 'handoff-ceremony').data!;
+      state = commitAll(state, signers, keyPackages);
+      return { state, keyPackages, groupPublicKey };
+    }
+
+    it('should sign with nonces exported from one process and imported in another', () => {
+      const { state, keyPackages, groupPublicKey } = committedState();
+      
+      const exported = exportSigningNonces(asStateJson(state), 'alice');
+      expect(exported.success).toBe(true);
+      const [sentState, nonces] = exported.data!;
+      expect(sentState.round1_packages['alice']).toBeUndefined();
+      expect(sentState.current_round).toBe(1);
+      
+      // The receiving process picks up the state and the nonces separately
+      const imported = importSigningNonces(asStateJson(sentState), nonces);
+      expect(imported.success).toBe(true);
+      let received = imported.data!;
+      expect(received.current_round).toBe(2);
+      
+      const signingPackage = buildSigningPackage(asStateJson(received)).data!;
+      let signature: string | null = null;
+      ['alice', 'bob'].forEach((signer, i) => {
+        const result = processSigningRound2(asStateJson(received), signer, keyPackages[i], signingPackage, groupPublicKey);
+        expect(result.success).toBe(true);
+        [received, signature] = result.data!;
+      });
+      
+      expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(true);
+    });
+
+    it('should refuse to import the same nonces twice', () => {
+      const { state } = committedState();
+      const [sentState, nonces] = exportSigningNonces(asStateJson(state), 'alice').data!;
+      const received = importSigningNonces(asStateJson(sentState), nonces).data!;
+      
+      const { alice: _alice, ...withoutAlice } = received.round1_packages;
+      const replayed = importSigningNonces(asStateJson({ ...received, round1_packages: withoutAlice }), nonces);
+      expect(replayed.success).toBe(false);
+      expect(replayed.error).toContain('already imported');
+    });
+
+    it('should refuse nonces from another ceremony', () => {
+      const { state } = committedState();
+      const [sentState, nonces] = exportSigningNonces(asStateJson(state), 'alice').data!;
+      
+      const result = importSigningNonces(asStateJson({ ...sentState, ceremony_id: 'other-ceremony' }), nonces);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('handoff-ceremony');
+    });
+  });
+
+  describe('Input Size Limits', () => {
+    it('should reject oversized JSON before parsing it', () => {
+      const signers = Array.from({ length: 100_000 }, (_, i) => `signer_${i}_${'x'.repeat(16)}`);
+      
+      const result = createSigningCeremony('Message', signers);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InputTooLarge);
+      expect(result.error).toContain('field: "signers_json"');
+    });
+
+    it('should apply configured limits to messages and participant counts', () => {
+      setInputLimits(1024 * 1024, 16, 3);
+      try {
+        const longMessage = createSigningCeremony('A message longer than sixteen bytes', ['alice']);
+        expect(longMessage.error).toContain('field: "message"');
+        
+        const tooManyParticipants = createKeygenCeremony(2, 4);
+        expect(tooManyParticipants.error_code).toBe(FrostErrorCode.InputTooLarge);
+      } finally {
+        setInputLimits(1024 * 1024, 64 * 1024, 255);
+      }
+    });
+    
+    it('should name a blank or null package argument', () => {
+      const emptyKeyPackage = loadKeyPackage('');
+      expect(emptyKeyPackage.error_code).toBe(FrostErrorCode.MalformedInput);
+      expect(emptyKeyPackage.error).toContain('key_package_json is empty');
+      
+      const nullSigningPackage = buildSigningRequest('alice', 'null');
+      expect(nullSigningPackage.error).toContain('signing_package_json is empty');
+      
+      const blankGroupKey = verifyNostrEvent(JSON.stringify(SIGNED_EVENT), '  ');
+      expect(blankGroupKey.error).toContain('group_public_key_json is empty');
+    });
+  });
+
+  describe('X-only BIP340 Verification', () => {
+    const eventId = Uint8Array.from(Buffer.from(SIGNED_EVENT.id, 'hex'));
+
+    it('should accept a BIP340 signature over the event id', () => {
+      const result = verifySignatureXonly(eventId, SIGNED_EVENT.sig, SIGNED_EVENT.pubkey);
+      expect(result.success).toBe(true);
+      expect(result.data).toBe(true);
+    });
+
+    it('should reject a modified message or signature', () => {
+      const otherMessage = eventId.slice();
+      otherMessage[0] ^= 1;
+      expect(verifySignatureXonly(otherMessage, SIGNED_EVENT.sig, SIGNED_EVENT.pubkey).data).toBe(false);
+      
+      const badSig = SIGNED_EVENT.sig.replace(/^4c/, '4d');
+      expect(verifySignatureXonly(eventId, badSig, SIGNED_EVENT.pubkey).data).toBe(false);
+    });
+
+    it('should require a 32-byte x-only pubkey', () => {
+      const result = verifySignatureXonly(eventId, SIGNED_EVENT.sig, '02' + SIGNED_EVENT.pubkey);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('32 bytes');
+    });
+
+    it('should verify against the npub of the signing key', () => {
+      const npub = 'npub1tr6anj72atmf9wt8452fkzth3ladlnd4tyacz9d36rt7k6xndyuq6tzuwq';
+      expect(verifySignatureNpub(eventId, SIGNED_EVENT.sig, npub).data).toBe(true);
+      
+      const nsec = 'nsec1tr6anj72atmf9wt8452fkzth3ladlnd4tyacz9d36rt7k6xndyuqkafag4';
+      expect(verifySignatureNpub(eventId, SIGNED_EVENT.sig, nsec).error_code).toBe(FrostErrorCode.MalformedInput);
+      expect(verifySignatureNpub(eventId, SIGNED_EVENT.sig, npub.slice(0, -1) + 'p').error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Identifier Normalization', () => {
+    it('should relabel packages without changing the group key, and still sign', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const [first, , third] = Object.values(shares);
+      
+      // Churn left an unrelated label scheme and a missing participant
+      const result = normalizeIdentifiers(groupPublicKey, { carol: third, alice: first });
+      expect(result.success).toBe(true);
+      
+      const normalized = result.data!;
+      expect(Object.keys(normalized.key_packages)).toEqual(['participant_1', 'participant_2']);
+      expect(JSON.parse(normalized.group_public_key).verifying_key).toBe(JSON.parse(groupPublicKey).verifying_key);
+      expect(Object.keys(JSON.parse(normalized.group_public_key).verifying_shares)).toHaveLength(2);
+      
+      const message = 'Message signed after normalization';
+      const signers = ['participant_1', 'participant_2'];
+      let state = createSigningCeremony(message, signers).data!;
+      state = commitAll(state, signers, signers.map((label) => normalized.key_packages[label]));
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      let signature: string | null = null;
+      for (const signer of signers) {
+        const signed = processSigningRound2(
+          asStateJson(state),
+          signer,
+          normalized.key_packages[signer],
+          signingPackage,
+          normalized.group_public_key
+        );
+        expect(signed.success).toBe(true);
+        [state, signature] = signed.data!;
+      }
+      
+      expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(true);
+    });
+
+    it('should reject a key package from another group', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const [, otherShares] = generateFrostShares('', 2, 3).data!;
+      
+      const result = normalizeIdentifiers(groupPublicKey, {
+        alice: Object.values(shares)[0],
+        mallory: Object.values(otherShares)[1],
+      });
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('mallory');
+    });
+  });
+
+  describe('Identifier-Labeled Commitments', () => {
+    it('should build the same signing package from returned commitments as from the state', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony('Message with labeled commitments', signers).data!;
+      const commitments: SignerCommitment[] = [];
+      signers.forEach((signer, i) => {
+        const result = processSigningRound1(asStateJson(state), signer, keyPackages[i]);
+        expect(result.success).toBe(true);
+        const [newState, commitment] = result.data!;
+        expect(commitment.identifier).toBe(newState.signer_identifiers![signer]);
+        commitments.push(commitment);
+        state = newState;
+      });
+      
+      const fromState = buildSigningPackage(asStateJson(state));
+      const fromCommitments = buildSigningPackage(asStateJson(state), commitments.reverse());
+      expect(fromCommitments.success).toBe(true);
+      expect(JSON.parse(fromCommitments.data!)).toEqual(JSON.parse(fromState.data!));
+    });
+
+    it('should reject two commitments for the same identifier', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const state = createSigningCeremony('Message with a repeated signer', ['alice']).data!;
+      const [, commitment] = processSigningRound1(asStateJson(state), 'alice', keyPackages[0]).data!;
+      
+      const result = buildSigningPackage(asStateJson(state), [commitment, commitment]);
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('Duplicate commitment');
+    });
+  });
+
+  describe('Hash Helpers', () => {
+    it('should match known BIP340 tagged hashes', () => {
+      // Hash of the zero aux_rand that BIP340 test vector 0 mixes into its nonce
+      expect(taggedHash('BIP0340/aux', new Uint8Array(32)).data)
+        .toBe('54f169cfc9e2e5727480441f90ba25c488f461c70b5ea5dcaaf7af69270aa514');
+      expect(taggedHash('BIP0340/challenge', new Uint8Array()).data)
+        .toBe('c216d352f5818b7b4beacd4ae0a26fe888080823d2a598856661bcd54f1b3713');
+    });
+
+    it('should hash a serialized Nostr event to its id', () => {
+      const { pubkey, created_at, kind, tags, content } = SIGNED_EVENT;
+      const serialized = JSON.stringify([0, pubkey, created_at, kind, tags, content]);
+      
+      expect(sha256(new TextEncoder().encode(serialized)).data).toBe(SIGNED_EVENT.id);
+      expect(sha256(new Uint8Array()).data)
+        .toBe('e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855');
+    });
+  });
+
+  describe('Group Key Only For Aggregation', () => {
+    it('should let intermediate signers omit the group key', () => {
+      const message = 'Message where only the last signer has the group key';
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony(message, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      const intermediate = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage, '');
+      expect(intermediate.success).toBe(true);
+      [state] = intermediate.data!;
+      
+      const missingKey = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage);
+      expect(missingKey.success).toBe(false);
+      expect(missingKey.error).toContain('group public key is required');
+      
+      const final = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage, groupPublicKey);
+      expect(final.success).toBe(true);
+      const [, signature] = final.data!;
+      expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(true);
+    });
+  });
+
+  describe('Completion Status', () => {
+    it('should report keygen completion with the group public key', () => {
+      let state = createKeygenCeremony(1, 1).data!;
+      expect(isComplete(asStateJson(state)).data).toEqual({ ceremony: 'keygen', complete: false, result: null });
+      
+      [state] = processKeygenRound1(asStateJson(state), 'solo').data!;
+      [state] = processKeygenRound2(asStateJson(state), 'solo', '{}').data!;
+      
+      expect(isComplete(asStateJson(state)).data).toEqual({
+        ceremony: 'keygen',
+        complete: true,
+        result: state.group_public_key,
+      });
+    });
+
+    it('should report signing completion with the final signature', () => {
+      const state = createSigningCeremony('Message to poll', ['alice', 'bob']).data!;
+      expect(isComplete(asStateJson(state)).data).toEqual({ ceremony: 'signing', complete: false, result: null });
+      
+      const signed = { ...state, final_signature: '"signature"' };
+      expect(isComplete(asStateJson(signed)).data).toEqual({
+        ceremony: 'signing',
+        complete: true,
+        result: '"signature"',
+      });
+    });
+  });
+
+  describe('Extra Keygen Entropy', () => {
+    it('should accept operator entropy alongside the host RNG', () => {
+      const state = createKeygenCeremony(1, 1).data!;
+      const entropy = new TextEncoder().encode('entropy from an external source');
+      
+      const first = processKeygenRound1(asStateJson(state), 'solo', undefined, entropy);
+      const second = processKeygenRound1(asStateJson(state), 'solo', undefined, entropy);
+      expect(first.success).toBe(true);
+      expect(second.success).toBe(true);
+      
+      // The host RNG still contributes, so equal entropy doesn't mean equal keys
+      expect(first.data![1]).not.toBe(second.data![1]);
+    });
+  });
+
+  describe('Re-aggregation', () => {
+    function signedCeremony() {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony('Message aggregated twice', signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      signers.forEach((signer, i) => {
+        [state] = processSigningRound2(asStateJson(state), signer, keyPackages[i], signingPackage, groupPublicKey).data!;
+      });
+      return { state, signingPackage, groupPublicKey };
+    }
+
+    it('should reproduce the recorded signature byte for byte', () => {
+      const { state, signingPackage, groupPublicKey } = signedCeremony();
+      expect(state.final_signature).toBeDefined();
+      
+      const first = aggregateSignature(asStateJson(state), signingPackage, groupPublicKey);
+      const second = aggregateSignature(asStateJson(state), signingPackage, groupPublicKey);
+      expect(first.success).toBe(true);
+      expect(first.data![1]).toBe(state.final_signature);
+      expect(second.data![1]).toBe(first.data![1]);
+    });
+
+    it('should flag a recorded signature that re-aggregation does not reproduce', () => {
+      const { state, signingPackage, groupPublicKey } = signedCeremony();
+      const { state: other } = signedCeremony();
+      
+      const result = aggregateSignature(
+        asStateJson({ ...state, final_signature: other.final_signature }),
+        signingPackage,
+        groupPublicKey
+      );
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.IntegrityError);
+    });
+
+    it('should refuse to re-aggregate over a substituted signing package', () => {
+      const { state, groupPublicKey } = signedCeremony();
+      const { signingPackage: substituted } = signedCeremony();
+      
+      const result = aggregateSignature(asStateJson(state), substituted, groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.SigningError);
+      expect(result.error).toContain('commitment mismatch');
+    });
+
+    it('should refuse to re-aggregate without a recorded threshold', () => {
+      const { state, signingPackage, groupPublicKey } = signedCeremony();
+      
+      const result = aggregateSignature(asStateJson({ ...state, threshold: undefined }), signingPackage, groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+    });
+  });
+
+  describe('Capabilities', () => {
+    it('should describe the full build', () => {
+      const result = capabilities();
+      expect(result.success).toBe(true);
+      expect(result.data).toMatchObject({
+        ciphersuite: 'FROST-secp256k1-SHA256-v1',
+        keygen: true,
+        signing: true,
+        trusted_dealer: true,
+        nostr_verification: true,
+        taproot: true,
+        secret_recovery: false,
+      });
+    });
+    
+    it('should report the frost-core version the module was built with', () => {
+      const result = buildInfo();
+      expect(result.success).toBe(true);
+      expect(result.data!.frost_core).toMatch(/^2\.\d+\.\d+([-+].*)?$/);
+      expect(result.data!.ciphersuite).toBe('FROST-secp256k1-SHA256-v1');
+      expect(result.data!.features).toContain('full');
+    });
+  });
+
+  describe('DKG Round 1 Completeness', () => {
+    it('should stay in round 1 until every participant has a package', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      for (const participant of ['alice', 'bob']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      
+      expect(state.current_round).toBe(1);
+    });
+
+    it('should reject round 2 with threshold but not all round 1 packages', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      const round1Packages: Record<string, string> = {};
+      for (const participant of ['alice', 'bob']) {
+        const [newState, package_] = processKeygenRound1(asStateJson(state), participant).data!;
+        state = newState;
+        round1Packages[participant] = package_;
+      }
+      
+      // A state that advanced early at threshold, as older versions did
+      const earlyState = { ...state, current_round: 2 };
+      const result = processKeygenRound2(asStateJson(earlyState), 'alice', JSON.stringify(round1Packages));
+      expect(result.success).toBe(false);
+      expect(result.error).toBe('InsufficientParticipants { required: 3, actual: 2 }');
+    });
+  });
+
+  describe('Random Bytes', () => {
+    it('should return the requested number of fresh random bytes', () => {
+      const first = randomBytes(32);
+      const second = randomBytes(32);
+      
+      expect(first.success).toBe(true);
+      expect(first.data).toMatch(/^[0-9a-f]{64}$/);
+      expect(second.data).not.toBe(first.data);
+    });
+
+    it('should cap the length', () => {
+      const result = randomBytes(4096);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InputTooLarge);
+    });
+  });
+
+  describe('Participant Removal', () => {
+    it('should restart round 1 without a participant that dropped out', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      for (const participant of ['alice', 'bob', 'charlie']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      
+      const removed = removeParticipant(asStateJson(state), 'charlie');
+      expect(removed.success).toBe(true);
+      state = removed.data!;
+      expect(state.max_participants).toBe(2);
+      expect(state.current_round).toBe(1);
+      expect(state.round1_packages).toEqual({});
+      expect(state.removed_participants).toEqual(['charlie']);
+      expect(processKeygenRound1(asStateJson(state), 'charlie').error_code).toBe(FrostErrorCode.InvalidParticipant);
+      
+      const round1Packages: Record<string, string> = {};
+      for (const participant of ['alice', 'bob']) {
+        const [newState, package_] = processKeygenRound1(asStateJson(state), participant).data!;
+        state = newState;
+        round1Packages[participant] = package_;
+      }
+      expect(state.current_round).toBe(2);
+      
+      const round2 = processKeygenRound2(asStateJson(state), 'alice', JSON.stringify(round1Packages));
+      expect(round2.success).toBe(true);
+    });
+
+    it('should refuse to go below the threshold', () => {
+      const [state] = processKeygenRound1(asStateJson(createKeygenCeremony(2, 2).data!), 'bob').data!;
+      const result = removeParticipant(asStateJson(state), 'bob');
+      expect(result.success).toBe(false);
+      expect(result.error).toBe('InsufficientParticipants { required: 2, actual: 1 }');
+    });
+    
+    it('should reject a participant that never joined', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      [state] = processKeygenRound1(asStateJson(state), 'alice').data!;
+      
+      const result = removeParticipant(asStateJson(state), 'mallory');
+      expect(result.error_code).toBe(FrostErrorCode.InvalidParticipant);
+    });
+
+    it('should require a new ceremony once key packages exist', () => {
+      let state = createKeygenCeremony(2, 2).data!;
+      const round1Packages: Record<string, string> = {};
+      for (const participant of ['alice', 'bob']) {
+        const [newState, package_] = processKeygenRound1(asStateJson(state), participant).data!;
+        state = newState;
+        round1Packages[participant] = package_;
+      }
+      [state] = processKeygenRound2(asStateJson(state), 'alice', JSON.stringify(round1Packages)).data!;
+      
+      const result = removeParticipant(asStateJson(state), 'bob');
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+    });
+  });
+
+  describe('Commitment Wire Format', () => {
+    it('should round-trip commitments through fixed-length hex', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['participant_1', 'participant_2'];
+      const state = createSigningCeremony('wire format', signers).data!;
+      const [, commitment] = processSigningRound1(asStateJson(state), signers[0], keyPackages[0]).data!;
+      
+      const wire = commitmentToWire(commitment);
+      expect(wire.success).toBe(true);
+      expect(wire.data!.format).toBe('FROST-secp256k1-SHA256-v1/commitment');
+      expect(wire.data!.identifier).toMatch(/^[0-9a-f]{64}$/);
+      expect(wire.data!.hiding).toMatch(/^0[23][0-9a-f]{64}$/);
+      expect(wire.data!.binding).toMatch(/^0[23][0-9a-f]{64}$/);
+      
+      const decoded = commitmentFromWire(wire.data!);
+      expect(decoded.success).toBe(true);
+      expect(decoded.data).toEqual(commitment);
+    });
+
+    it('should reject fields of the wrong length or format', () => {
+      const wire = {
+        format: 'FROST-secp256k1-SHA256-v1/commitment',
+        identifier: '00'.repeat(31) + '01',
+        hiding: '02' + '11'.repeat(32),
+        binding: '02' + '11'.repeat(31),
+      };
+      expect(commitmentFromWire(wire).success).toBe(false);
+      expect(commitmentFromWire({ ...wire, binding: wire.hiding, format: 'other' }).success).toBe(false);
+    });
+  });
+
+  describe('Signing Approval Summary', () => {
+    function eventSigningPackage(): string {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['participant_1', 'participant_2'];
+      const eventId = Uint8Array.from(Buffer.from(SIGNED_EVENT.id, 'hex'));
+      const state = commitAll(createSigningCeremony(eventId, signers).data!, signers, keyPackages);
+      return buildSigningPackage(asStateJson(state)).data!;
+    }
+
+    it('should show the event behind an event id message', () => {
+      const signingPackage = eventSigningPackage();
+      const result = signingApprovalSummary(signingPackage, JSON.stringify(SIGNED_EVENT));
+      
+      expect(result.success).toBe(true);
+      expect(result.data!.message).toBe(SIGNED_EVENT.id);
+      expect(result.data!.event!.kind).toBe(SIGNED_EVENT.kind);
+      expect(result.data!.event!.content).toBe(SIGNED_EVENT.content);
+      expect(result.data!.signers).toHaveLength(2);
+      expect(result.data!.digest).toMatch(/^[0-9a-f]{64}$/);
+      expect(signingApprovalSummary(signingPackage).data!.digest).toBe(result.data!.digest);
+    });
+
+    it('should reject an event that is not the one being signed', () => {
+      const tampered = { ...SIGNED_EVENT, content: 'Something else' };
+      const result = signingApprovalSummary(eventSigningPackage(), JSON.stringify(tampered));
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.IntegrityError);
+    });
+  });
+
+  describe('Group Key Rotation', () => {
+    it('should produce a record the old group key verifiably signed', () => {
+      const [oldGroupKey, oldShares] = generateFrostShares('', 2, 3).data!;
+      const [newGroupKey] = generateFrostShares('', 2, 3).data!;
+      const [otherGroupKey] = generateFrostShares('', 2, 3).data!;
+      const [first, , third] = Object.values(oldShares);
+      
+      const result = rotateGroupKeyAttestation(oldGroupKey, newGroupKey, { first, third }, 1700000000);
+      expect(result.success).toBe(true);
+      const record = result.data!;
+      expect(record.old_group_key).toMatch(/^0[23][0-9a-f]{64}$/);
+      expect(record.new_group_key).toMatch(/^0[23][0-9a-f]{64}$/);
+      expect(verifyKeyRotation(record).data).toBe(true);
+      
+      const redirected = { ...record, new_group_key: JSON.parse(otherGroupKey).verifying_key };
+      expect(verifyKeyRotation(redirected).data).toBe(false);
+      expect(verifyKeyRotation({ ...record, created_at: record.created_at + 1 }).data).toBe(false);
+    });
+
+    it('should require a threshold of the old key packages', () => {
+      const [oldGroupKey, oldShares] = generateFrostShares('', 2, 3).data!;
+      const [newGroupKey] = generateFrostShares('', 2, 3).data!;
+      
+      const [first] = Object.values(oldShares);
+      
+      const result = rotateGroupKeyAttestation(oldGroupKey, newGroupKey, { first }, 1700000000);
+      expect(result.success).toBe(false);
+      expect(result.error).toBe('InsufficientParticipants { required: 2, actual: 1 }');
+    });
+  });
+
+  describe('Replay Check', () => {
+    const message = new TextEncoder().encode('Pay 1 BTC to mallory');
+
+    it('should flag a message the group already signed', () => {
+      const first = checkMessageNotSigned('group-a', message);
+      expect(first.success).toBe(true);
+      expect(first.data!.already_signed).toBe(false);
+      
+      const repeated = checkMessageNotSigned('group-a', message, first.data!.cache);
+      expect(repeated.data!.already_signed).toBe(true);
+      expect(repeated.data!.cache.message_hashes).toHaveLength(1);
+    });
+
+    it('should keep caches per group', () => {
+      const first = checkMessageNotSigned('group-a', message).data!;
+      
+      expect(checkMessageNotSigned('group-b', message).data!.already_signed).toBe(false);
+      const mixedUp = checkMessageNotSigned('group-b', message, first.cache);
+      expect(mixedUp.success).toBe(false);
+      expect(mixedUp.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('DKG Public Transcript', () => {
+    it('should publish every round 1 commitment without the secrets', () => {
+      let state = createKeygenCeremony(2, 3, 'audited-keygen').data!;
+      for (const participant of ['alice', 'bob', 'charlie']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      
+      const result = dkgPublicTranscript(asStateJson(state));
+      expect(result.success).toBe(true);
+      expect(result.data!.ceremony_id).toBe('audited-keygen');
+      expect(Object.keys(result.data!.packages)).toHaveLength(3);
+      expect(Object.keys(result.data!.packages).sort()).toEqual(Object.values(state.participant_identifiers!).sort());
+      expect(JSON.stringify(result.data)).not.toContain('coefficients');
+    });
+
+    it('should refuse 1-of-1 states, whose round 1 holds the signing key', () => {
+      let state = createKeygenCeremony(1, 1).data!;
+      [state] = processKeygenRound1(asStateJson(state), 'solo').data!;
+      
+      const result = dkgPublicTranscript(asStateJson(state));
+      expect(result.success).toBe(false);
+      expect(JSON.stringify(result)).not.toContain(state.round1_packages.solo);
+    });
+  });
+
+  describe('Self Test', () => {
+    it('should pass when the host has a secure random source', () => {
+      expect(selfTest()).toMatchObject({ success: true, data: true });
+    });
+  });
+
+  describe('Out-of-Order Shares', () => {
+    const message = 'Shares submitted in reverse order';
+
+    it('should aggregate shares arriving in reverse order', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 3, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob', 'carol'];
+      
+      let state = createSigningCeremony(message, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      let signature: string | null = null;
+      for (const i of [2, 1, 0]) {
+        const result = processSigningRound2(asStateJson(state), signers[i], keyPackages[i], signingPackage, groupPublicKey);
+        expect(result.success).toBe(true);
+        [state, signature] = result.data!;
+      }
+      
+      expect(signature).not.toBeNull();
+      expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(true);
+    });
+
+    it('should reject a key package other than the one used in round 1', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      const state = commitAll(createSigningCeremony(message, signers).data!, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      const result = processSigningRound2(asStateJson(state), 'alice', keyPackages[1], signingPackage, groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InvalidParticipant);
+    });
+  });
+
+  describe('State Size Estimates', () => {
+    it('should track the size of a real signing state', () => {
+      const message = 'Message for a size estimate';
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['participant_1', 'participant_2'];
+      
+      let state = commitAll(createSigningCeremony(message, signers).data!, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      signers.forEach((signer, i) => {
+        [state] = processSigningRound2(asStateJson(state), signer, keyPackages[i], signingPackage, groupPublicKey).data!;
+      });
+      
+      const estimate = estimateStateSize(2, 3);
+      expect(estimate.success).toBe(true);
+      const actual = asStateJson(state).length;
+      expect(estimate.data!.signing_bytes).toBeGreaterThan(actual * 0.8);
+      expect(estimate.data!.signing_bytes).toBeLessThan(actual * 1.2);
+    });
+
+    it('should grow with the participant count', () => {
+      const small = estimateStateSize(2, 3).data!;
+      const large = estimateStateSize(2, 10).data!;
+      expect(large.keygen_bytes).toBeGreaterThan(small.keygen_bytes);
+      expect(large.signing_bytes).toBe(small.signing_bytes);
+    });
+
+    it('should reject an impossible threshold', () => {
+      expect(estimateStateSize(4, 3).success).toBe(false);
+    });
+  });
+
+  describe('Share Set Validation', () => {
+    it('should confirm dealer shares interpolate to the group key', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 3, 5).data!;
+      
+      const result = validateShareSet(shares, groupPublicKey, 3);
+      expect(result.success).toBe(true);
+      expect(result.data!.subsets_checked.length).toBeGreaterThan(0);
+      for (const subset of result.data!.subsets_checked) {
+        expect(subset).toHaveLength(3);
+      }
+    });
+    
+    it('should reject shares from another group or a different threshold', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const [, otherShares] = generateFrostShares('', 2, 3).data!;
+      
+      const mixed = { ...shares, intruder: Object.values(otherShares)[0] };
+      expect(validateShareSet(mixed, groupPublicKey, 2).success).toBe(false);
+      expect(validateShareSet(shares, groupPublicKey, 3).success).toBe(false);
+    });
+  });
+
+  describe('Log Callback', () => {
+    afterEach(() => setLogCallback());
+    
+    it('should report ceremony progress without secrets', () => {
+      const events: FrostLogEvent[] = [];
+      setLogCallback((event) => events.push(event));
+      
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 2).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      const message = 'Message that must not be logged';
+      
+      let state = createSigningCeremony(message, signers, 'logged-ceremony').data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      signers.forEach((signer, i) => {
+        [state] = processSigningRound2(asStateJson(state), signer, keyPackages[i], signingPackage, groupPublicKey).data!;
+      });
+      
+      expect(events.map((event) => event.event)).toEqual([
+        'round_started',
+        'package_accepted',
+        'package_accepted',
+        'round_started',
+        'package_accepted',
+        'package_accepted',
+        'aggregation_attempted',
+      ]);
+      expect(events.every((event) => event.ceremony_id === 'logged-ceremony')).toBe(true);
+      expect(events[events.length - 1]).toMatchObject({ shares: 2, success: true });
+      
+      const logged = JSON.stringify(events);
+      expect(logged).not.toContain(message);
+      for (const keyPackage of keyPackages) {
+        expect(logged).not.toContain(JSON.parse(keyPackage).signing_share);
+      }
+    });
+    
+    it('should stop logging once the callback is cleared', () => {
+      const events: FrostLogEvent[] = [];
+      setLogCallback((event) => events.push(event));
+      setLogCallback();
+      
+      createSigningCeremony('Unlogged message', ['alice']);
+      expect(events).toHaveLength(0);
+    });
+  });
+
+  describe('Signing Requests', () => {
+    const message = 'Message requested from a remote signer';
+
+    function requestForBob() {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony(message, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      const request = buildSigningRequest('bob', signingPackage).data!;
+      
+      return { state, keyPackages, signingPackage, groupPublicKey, request };
+    }
+
+    it('should carry only public data to the signer', () => {
+      const { keyPackages, request } = requestForBob();
+      
+      expect(request.participant_id).toBe('bob');
+      expect(request.message).toBe(Buffer.from(message).toString('hex'));
+      for (const keyPackage of keyPackages) {
+        expect(JSON.stringify(request)).not.toContain(JSON.parse(keyPackage).signing_share);
+      }
+    });
+
+    it('should produce a share the coordinator accepts and aggregates', () => {
+      const { state, keyPackages, signingPackage, groupPublicKey, request } = requestForBob();
+      
+      const fulfilled = fulfillSigningRequest(asStateJson(state), request, keyPackages[1]);
+      expect(fulfilled.success).toBe(true);
+      const [, response] = fulfilled.data!;
+      expect(response.participant_id).toBe('bob');
+      expect(response.identifier).toBe(state.signer_identifiers!['bob']);
+      
+      const [aliceState] = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage, groupPublicKey).data!;
+      const result = addSignatureShare(
+        asStateJson(aliceState),
+        response.identifier,
+        JSON.stringify(response.share),
+        signingPackage,
+        groupPublicKey
+      );
+      expect(result.success).toBe(true);
+      expect(verifyFrostSignature(message, result.data![1]!, groupPublicKey).data).toBe(true);
+    });
+
+    it('should reject a request whose message does not match its package', () => {
+      const { state, keyPackages, request } = requestForBob();
+      
+      const tampered = { ...request, message: Buffer.from('Another message').toString('hex') };
+      const result = fulfillSigningRequest(asStateJson(state), tampered, keyPackages[1]);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Message Substitution', () => {
+    it('should refuse to sign a package whose message differs from the committed one', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony('Message the signers committed to', signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const swapped = { ...state, message: Array.from(new TextEncoder().encode('Message swapped in later')) };
+      const signingPackage = buildSigningPackage(asStateJson(swapped)).data!;
+      
+      const result = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage, groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.SigningError);
+      expect(result.error).toContain('message mismatch');
+    });
+  });
+
+  describe('Encoding Helpers', () => {
+    const bytes = Uint8Array.from([0x00, 0xab, 0xcd, 0xef, 0xff]);
+
+    it('should round-trip hex and accept either case', () => {
+      expect(encodeHex(bytes).data).toBe('00abcdefff');
+      expect(decodeHex('00abcdefff').data).toEqual(bytes);
+      expect(decodeHex('00ABCDEFFF').data).toEqual(bytes);
+    });
+
+    it('should reject odd-length or non-hex input', () => {
+      for (const bad of ['abc', '0g', '0x00', ' 00']) {
+        const result = decodeHex(bad);
+        expect(result.success).toBe(false);
+        expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+      }
+    });
+
+    it('should round-trip unpadded base64url', () => {
+      expect(encodeBase64url(bytes).data).toBe('AKvN7_8');
+      expect(decodeBase64url('AKvN7_8').data).toEqual(bytes);
+    });
+
+    it('should reject padding, the standard alphabet, and stray bits', () => {
+      for (const bad of ['AKvN7_8=', 'AKvN7/8', 'AKvN7_9', 'A']) {
+        const result = decodeBase64url(bad);
+        expect(result.success).toBe(false);
+        expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+      }
+    });
+
+    it('should re-encode a signature in each supported format', () => {
+      // R = G (even y), z = 1
+      const generatorX = '79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798';
+      const signatureHex = `02${generatorX}${'00'.repeat(31)}01`;
+      const signatureJson = JSON.stringify(signatureHex);
+      
+      expect(signatureToFormat(signatureJson, 'hex').data).toBe(signatureHex);
+      expect(Buffer.from(signatureToFormat(signatureJson, 'base64').data!, 'base64').toString('hex')).toBe(signatureHex);
+      expect(signatureToFormat(signatureJson, 'compact64').data).toBe(signatureHex.slice(2));
+      
+      const oddR = JSON.stringify(`03${signatureHex.slice(2)}`);
+      expect(signatureToFormat(oddR, 'compact64').error_code).toBe(FrostErrorCode.MalformedInput);
+      const unsupported = signatureToFormat(signatureJson, 'der' as SignatureFormat);
+      expect(unsupported.error_code).toBe(FrostErrorCode.MalformedInput);
+      expect(unsupported.error).toContain('compact64, hex, base64');
+    });
+  });
+
+  describe('Group Key Preview', () => {
+    it('should derive the same key from stored round 1 entries and published packages', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      for (const participant of ['alice', 'bob', 'charlie']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      
+      const fromState = computeGroupKeyPreview(state.round1_packages);
+      expect(fromState.success).toBe(true);
+      expect(fromState.data!.group_public_key).toMatch(/^0[23][0-9a-f]{64}$/);
+      expect(fromState.data!.xonly_pubkey).toBe(fromState.data!.group_public_key.slice(2));
+      
+      const transcript = dkgPublicTranscript(asStateJson(state)).data!;
+      const published = Object.fromEntries(
+        Object.entries(transcript.packages).map(([identifier, pkg]) => [identifier, JSON.stringify(pkg)])
+      );
+      expect(computeGroupKeyPreview(published).data).toEqual(fromState.data);
+    });
+    
+    it('should need round 1 packages from at least two participants', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      [state] = processKeygenRound1(asStateJson(state), 'alice').data!;
+      
+      const result = computeGroupKeyPreview(state.round1_packages);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InsufficientParticipants);
+    });
+  });
+
+  describe('Legacy Round 1 Entries', () => {
+    // Rewrite every round 1 entry in the [secret, package] array form
+    function asLegacyEntries(entries: Record<string, string>): Record<string, string> {
+      return Object.fromEntries(Object.entries(entries).map(([participant, entry]) => {
+        const { secret, package: pkg } = JSON.parse(entry);
+        return [participant, JSON.stringify([secret, pkg])];
+      }));
+    }
+
+    it('should read both forms and flag the legacy one', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      for (const participant of ['alice', 'bob', 'charlie']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      expect(JSON.parse(state.round1_packages.alice).format).toBe('frost-dkg-round1/v2');
+      
+      const current = processKeygenRound2(asStateJson(state), 'alice', JSON.stringify(state.round1_packages));
+      expect(current.success).toBe(true);
+      expect(current.deprecations).toBeUndefined();
+      
+      const legacyEntries = asLegacyEntries(state.round1_packages);
+      const legacy = processKeygenRound2(
+        asStateJson({ ...state, round1_packages: legacyEntries }),
+        'alice',
+        JSON.stringify(legacyEntries)
+      );
+      expect(legacy.success).toBe(true);
+      expect(legacy.deprecations).toEqual(['legacy_round1_array']);
+      
+      const unknownEntries = Object.fromEntries(Object.entries(state.round1_packages).map(
+        ([participant, entry]) => [participant, entry.replace('frost-dkg-round1/v2', 'frost-dkg-round1/v9')]
+      ));
+      const unknown = processKeygenRound2(
+        asStateJson({ ...state, round1_packages: unknownEntries }),
+        'alice',
+        JSON.stringify(unknownEntries)
+      );
+      expect(unknown.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Signer Policies', () => {
+    const highValue = { required: ['cold'], optional: ['alice', 'bob'], min_signers: 2 };
+
+    it('should accept signer sets that meet the policy', () => {
+      const result = checkSignerPolicy(['alice', 'cold'], highValue);
+      expect(result.success).toBe(true);
+      expect(result.data).toEqual({ satisfied: true, failures: [] });
+    });
+
+    it('should name each requirement a signer set misses', () => {
+      const result = checkSignerPolicy(['alice', 'mallory'], highValue);
+      expect(result.success).toBe(true);
+      expect(result.data!.satisfied).toBe(false);
+      expect(result.data!.failures).toEqual([
+        { requirement: 'required_signer', signer: 'cold' },
+        { requirement: 'allowed_signers', signer: 'mallory' },
+      ]);
+      
+      const tooFew = checkSignerPolicy(['cold', 'cold'], highValue);
+      expect(tooFew.data!.failures).toEqual([{ requirement: 'min_signers', required: 2, actual: 1 }]);
+    });
+
+    it('should let anyone join when the policy lists no optional signers', () => {
+      const result = checkSignerPolicy(['cold', 'mallory'], { required: ['cold'], min_signers: 2 });
+      expect(result.data!.satisfied).toBe(true);
+    });
+  });
+
+  describe('Bitcoin Taproot Signing', () => {
+    const sighash = Uint8Array.from(Buffer.from('9f8f2b6d3c5a7e1f0b4d6c8a2e3f5a7b9c1d3e5f7a9b0c2d4e6f8a1b3c5d7e9f', 'hex'));
+
+    it('should tweak the group key as BIP341 specifies', () => {
+      // scriptPubKey vectors from BIP341's wallet test vectors
+      const keyOnly = taprootOutputKey(groupKeyFor('d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d'));
+      expect(keyOnly.data).toBe('53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343');
+      
+      const merkleRoot = Uint8Array.from(Buffer.from('5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21', 'hex'));
+      const withScripts = taprootOutputKey(groupKeyFor('187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27'), merkleRoot);
+      expect(withScripts.data).toBe('147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3');
+    });
+
+    it('should produce a key-path signature valid for the output key', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremonyBitcoin(sighash, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      signers.forEach((signer, i) => {
+        [state] = processSigningRound2(asStateJson(state), signer, keyPackages[i], signingPackage, groupPublicKey).data!;
+      });
+      
+      const signature = JSON.parse(state.final_signature!);
+      const outputKey = taprootOutputKey(groupPublicKey).data!;
+      expect(verifySignatureXonly(sighash, signature, outputKey).data).toBe(true);
+    });
+
+    it('should name the signer whose taproot share does not verify', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremonyBitcoin(sighash, signers).data!;
+      state = commitAll(state, signers, keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      signers.forEach((signer, i) => {
+        [state] = processSigningRound2(asStateJson(state), signer, keyPackages[i], signingPackage, groupPublicKey).data!;
+      });
+      
+      const swapped = { ...state.signature_shares, bob: state.signature_shares['alice'] };
+      const result = aggregateSignature(
+        asStateJson({ ...state, signature_shares: swapped, final_signature: undefined }),
+        signingPackage,
+        groupPublicKey
+      );
+      expect(result.success).toBe(false);
+      expect(result.error).toContain('Invalid signature share from bob');
+    });
+
+    it('should reject sighashes and merkle roots that are not 32 bytes', () => {
+      const short = createSigningCeremonyBitcoin(sighash.slice(1), ['alice', 'bob']);
+      expect(short.success).toBe(false);
+      expect(short.error_code).toBe(FrostErrorCode.MalformedInput);
+      
+      const badRoot = createSigningCeremonyBitcoin(sighash, ['alice', 'bob'], new Uint8Array(31));
+      expect(badRoot.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Loaded Key Packages', () => {
+    it('should sign both rounds from a handle and wipe it afterwards', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 2).data!;
+      const handles = Object.values(shares).map(share => loadKeyPackage(share).data!);
+      const handleJsons = handles.map(handle => JSON.stringify(handle));
+      const signers = ['alice', 'bob'];
+      
+      let state = createSigningCeremony('Signed from loaded key packages', signers).data!;
+      state = commitAll(state, signers, handleJsons);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      // A failed round puts the key package back for a retry
+      const failed = processSigningRound2(asStateJson(state), 'mallory', handleJsons[0], signingPackage, groupPublicKey);
+      expect(failed.success).toBe(false);
+      
+      signers.forEach((signer, i) => {
+        [state] = processSigningRound2(asStateJson(state), signer, handleJsons[i], signingPackage, groupPublicKey).data!;
+      });
+      expect(state.final_signature).toBeDefined();
+      
+      for (const handle of handles) {
+        expect(releaseKeyPackage(handle).data).toBe(false);
+      }
+      const next = createSigningCeremony('Another message', signers).data!;
+      const reused = processSigningRound1(asStateJson(next), 'alice', handleJsons[0]);
+      expect(reused.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+
+    it('should wipe a handle on release', () => {
+      const [, shares] = generateFrostShares('', 2, 3).data!;
+      const handle = loadKeyPackage(Object.values(shares)[0]).data!;
+      expect(releaseKeyPackage(handle).data).toBe(true);
+      expect(releaseKeyPackage(handle).data).toBe(false);
+    });
+  });
+
+  describe('Ceremony Expiry', () => {
+    const lifetime = { createdAt: 1700000000, expiresAt: 1700000600 };
+
+    it('should report expiry from the caller-supplied time', () => {
+      const state = createKeygenCeremony(2, 3, undefined, lifetime).data!;
+      expect(state.created_at).toBe(lifetime.createdAt);
+      expect(checkExpired(asStateJson(state), lifetime.expiresAt - 1).data).toBe(false);
+      expect(checkExpired(asStateJson(state), lifetime.expiresAt).data).toBe(true);
+      
+      const open = createSigningCeremony('No deadline', ['alice', 'bob'], undefined, { createdAt: lifetime.createdAt }).data!;
+      expect(checkExpired(asStateJson(open), 4102444800).data).toBe(false);
+    });
+
+    it('should reject round functions on an expired state', () => {
+      const keygen = createKeygenCeremony(2, 3, undefined, lifetime).data!;
+      const round1 = processKeygenRound1(asStateJson(keygen), 'alice');
+      expect(round1.success).toBe(false);
+      expect(round1.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+      
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signing = createSigningCeremony('Expired message', ['alice', 'bob'], undefined, lifetime).data!;
+      const commit = processSigningRound1(asStateJson(signing), 'alice', keyPackages[0]);
+      expect(commit.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+    });
+
+    it('should reject a lifetime that ends before it starts', () => {
+      const result = createKeygenCeremony(2, 3, undefined, { createdAt: 1700000600, expiresAt: 1700000000 });
+      expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Cross-Group Key Packages', () => {
+    it('should reject a key package from a group other than the expected one', () => {
+      const [groupPublicKey] = generateFrostShares('', 2, 3).data!;
+      const otherKeyPackages = dealerKeyPackages(2, 3);
+      
+      const state = createSigningCeremony('One group only', ['alice', 'bob']).data!;
+      const result = processSigningRound1(asStateJson(state), 'alice', otherKeyPackages[0], groupPublicKey);
+      expect(result.success).toBe(false);
+      expect(result.error_code).toBe(FrostErrorCode.InvalidParticipant);
+    });
+
+    it('should pin the ceremony to the first signer\'s group', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const otherKeyPackages = dealerKeyPackages(2, 3);
+      
+      let state = createSigningCeremony('One group only', ['alice', 'bob']).data!;
+      [state] = processSigningRound1(asStateJson(state), 'alice', keyPackages[0]).data!;
+      expect(state.group_verifying_key).toBeDefined();
+      
+      const mixed = processSigningRound1(asStateJson(state), 'bob', otherKeyPackages[1]);
+      expect(mixed.error_code).toBe(FrostErrorCode.InvalidParticipant);
+      expect(processSigningRound1(asStateJson(state), 'bob', keyPackages[1]).success).toBe(true);
+    });
+  });
+
+  describe('Batched Keygen Round 2', () => {
+    it('should match looped keygen_round2 calls for each held participant', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      for (const participant of ['alice', 'bob', 'carol']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      const round1PackagesJson = JSON.stringify(state.round1_packages);
+      
+      const batch = processKeygenRound2Batch(asStateJson(state), ['alice', 'bob'], round1PackagesJson);
+      expect(batch.success).toBe(true);
+      const [batchedState, packages] = batch.data!;
+      expect(Object.keys(packages)).toEqual(['alice', 'bob']);
+      
+      let looped = state;
+      for (const participant of ['alice', 'bob']) {
+        const [next, package_] = processKeygenRound2(asStateJson(looped), participant, round1PackagesJson).data!;
+        expect(packages[participant]).toBe(package_);
+        expect(JSON.parse(package_)[0].identifier).toBe(state.participant_identifiers![participant]);
+        looped = next;
+      }
+      expect(batchedState.key_packages).toEqual(looped.key_packages);
+    });
+
+    it('should reject a participant listed twice', () => {
+      let state = createKeygenCeremony(2, 2).data!;
+      for (const participant of ['alice', 'bob']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      const result = processKeygenRound2Batch(asStateJson(state), ['alice', 'alice'], JSON.stringify(state.round1_packages));
+      expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Packaged Keygen Results', () => {
+    function completedKeygenState(): KeygenState {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 2).data!;
+      const [alice, bob] = Object.values(shares);
+      return {
+        threshold: 2,
+        max_participants: 2,
+        current_round: 2,
+        round1_packages: {},
+        key_packages: { alice, bob },
+        group_public_key: groupPublicKey,
+      };
+    }
+
+    it('should package the group key with every share', () => {
+      const state = completedKeygenState();
+      const result = packageKeygenResult(asStateJson(state));
+      expect(result.success).toBe(true);
+      expect(result.data).toMatchObject({
+        group_public_key: state.group_public_key,
+        threshold: 2,
+        max_participants: 2,
+        shares: state.key_packages,
+      });
+      expect(result.data!.share_encryption).toBeUndefined();
+    });
+
+    it('should encrypt shares that WebCrypto can open with the participant label', async () => {
+      const state = completedKeygenState();
+      const keys = { alice: 'aa'.repeat(32), bob: 'bb'.repeat(32) };
+      const result = packageKeygenResult(asStateJson(state), keys);
+      expect(result.data!.share_encryption).toBe('aes-256-gcm');
+      
+      const sealed = Buffer.from(result.data!.shares.alice, 'hex');
+      const key = await crypto.subtle.importKey('raw', Buffer.from(keys.alice, 'hex'), 'AES-GCM', false, ['decrypt']);
+      const opened = await crypto.subtle.decrypt(
+        { name: 'AES-GCM', iv: sealed.subarray(0, 12), additionalData: new TextEncoder().encode('alice') },
+        key,
+        sealed.subarray(12)
+      );
+      expect(new TextDecoder().decode(opened)).toBe(state.key_packages.alice);
+      
+      // The label is bound as associated data, so shares can't be swapped
+      await expect(crypto.subtle.decrypt(
+        { name: 'AES-GCM', iv: sealed.subarray(0, 12), additionalData: new TextEncoder().encode('bob') },
+        key,
+        sealed.subarray(12)
+      )).rejects.toThrow();
+    });
+
+    it('should refuse to leave a share without a key in the clear', () => {
+      const result = packageKeygenResult(asStateJson(completedKeygenState()), { alice: 'aa'.repeat(32) });
+      expect(result.error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+
+    it('should refuse to package a ceremony that has not finished', () => {
+      const result = packageKeygenResult(asStateJson(createKeygenCeremony(2, 2).data!));
+      expect(result.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+    });
+
+    it('should rewrap a share under a new key, bound to its label', async () => {
+      const state = completedKeygenState();
+      const oldKey = 'aa'.repeat(32);
+      const newKey = 'cc'.repeat(32);
+      const stored = packageKeygenResult(asStateJson(state), { alice: oldKey, bob: oldKey }).data!.shares.alice;
+      
+      const rewrapped = rewrapShare(stored, oldKey, newKey, 'alice').data!;
+      const sealed = Buffer.from(rewrapped, 'hex');
+      const key = await crypto.subtle.importKey('raw', Buffer.from(newKey, 'hex'), 'AES-GCM', false, ['decrypt']);
+      const opened = await crypto.subtle.decrypt(
+        { name: 'AES-GCM', iv: sealed.subarray(0, 12), additionalData: new TextEncoder().encode('alice') },
+        key,
+        sealed.subarray(12)
+      );
+      expect(new TextDecoder().decode(opened)).toBe(state.key_packages.alice);
+      
+      expect(rewrapShare(stored, 'dd'.repeat(32), newKey, 'alice').error_code).toBe(FrostErrorCode.IntegrityError);
+      expect(rewrapShare(stored, oldKey, newKey, 'bob').error_code).toBe(FrostErrorCode.IntegrityError);
+    });
+  });
+
+  describe('Round 1 Proof of Knowledge', () => {
+    it('should name the participant whose proof does not verify', () => {
+      let state = createKeygenCeremony(2, 3).data!;
+      for (const participant of ['alice', 'bob', 'carol']) {
+        [state] = processKeygenRound1(asStateJson(state), participant).data!;
+      }
+      const bob = JSON.parse(state.round1_packages.bob);
+      bob.package.proof_of_knowledge = JSON.parse(state.round1_packages.carol).package.proof_of_knowledge;
+      const round1Packages = { ...state.round1_packages, bob: JSON.stringify(bob) };
+      
+      const result = processKeygenRound2(asStateJson(state), 'alice', JSON.stringify(round1Packages));
+      expect(result.error_code).toBe(FrostErrorCode.KeygenError);
+      expect(result.error).toContain('Invalid proof of knowledge from bob');
+    });
+  });
+
+  describe('Keygen Commit-Reveal', () => {
+    const pubkeys: Record<string, string> = { alice: 'aa'.repeat(32), bob: 'bb'.repeat(32) };
+    const privateEntry = (participant: string) =>
+      processKeygenRound1(asStateJson(createKeygenCeremony(2, 2).data!), participant, pubkeys[participant]).data![1];
+    
+    it('should record revealed packages that match their commitments', () => {
+      let state = enableCommitReveal(asStateJson(createKeygenCeremony(2, 2).data!)).data!;
+      expect(state.commit_reveal).toBe(true);
+      
+      const entries = { alice: privateEntry('alice'), bob: privateEntry('bob') };
+      for (const [participant, entry] of Object.entries(entries)) {
+        state = keygenCommit(asStateJson(state), participant, round1PackageHash(entry).data!).data!;
+      }
+      
+      const regenerated = processKeygenRound1(asStateJson(state), 'bob', pubkeys.bob, undefined, privateEntry('bob'));
+      expect(regenerated.error_code).toBe(FrostErrorCode.InvalidParticipant);
+      
+      for (const [participant, entry] of Object.entries(entries)) {
+        const result = processKeygenRound1(asStateJson(state), participant, pubkeys[participant], undefined, entry);
+        expect(result.success).toBe(true);
+        [state] = result.data!;
+      }
+      expect(state.current_round).toBe(2);
+    });
+    
+    it('should hold back reveals until every participant has committed', () => {
+      const entry = privateEntry('alice');
+      let state = enableCommitReveal(asStateJson(createKeygenCeremony(2, 2).data!)).data!;
+      state = keygenCommit(asStateJson(state), 'alice', round1PackageHash(entry).data!).data!;
+      
+      const result = processKeygenRound1(asStateJson(state), 'alice', pubkeys.alice, undefined, entry);
+      expect(result.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+    });
+  });
+
+  describe('Signer-side Signing Package Check', () => {
+    it('should flag a coordinator package for a different message', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const message = new TextEncoder().encode('approved message');
+      let state = createSigningCeremony(message, ['alice', 'bob']).data!;
+      const commitments = ['alice', 'bob'].map((signer, i) => {
+        const [next, commitment] = processSigningRound1(asStateJson(state), signer, keyPackages[i]).data!;
+        state = next;
+        return commitment;
+      });
+      
+      const honest = buildSigningPackage(asStateJson(state)).data!;
+      expect(rebuildAndCompareSigningPackage(message, commitments, honest).data).toBe(true);
+      
+      // A dishonest coordinator drops the bindings that would give the swap away
+      const unbound = commitments.map(({ identifier, commitment }) => ({ identifier, commitment }));
+      const otherState = createSigningCeremony('another message', ['alice', 'bob']).data!;
+      const substituted = buildSigningPackage(asStateJson(otherState), unbound).data!;
+      expect(rebuildAndCompareSigningPackage(message, commitments, substituted).data).toBe(false);
+    });
+  });
+
+  describe('Commitment Fingerprints', () => {
+    it('should fingerprint each signer once and keep the fingerprints through round 2', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const state = commitAll(createSigningCeremony('audited message', ['alice', 'bob']).data!, ['alice', 'bob'], keyPackages);
+      
+      const fingerprints = commitmentFingerprints(asStateJson(state)).data!;
+      expect(Object.keys(fingerprints).sort()).toEqual(Object.values(state.signer_identifiers!).sort());
+      Object.values(fingerprints).forEach(fingerprint => expect(fingerprint).toMatch(/^[0-9a-f]{64}$/));
+      
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      const [afterShare] = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage).data!;
+      expect(commitmentFingerprints(asStateJson(afterShare)).data).toEqual(fingerprints);
+    });
+  });
+
+  describe('Final Signing Package', () => {
+    it('should refuse round 2 over a package missing a signer\'s commitment', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const signers = ['alice', 'bob', 'carol'];
+      let state = createSigningCeremony('final package', signers).data!;
+      const commitments = signers.map((signer, i) => {
+        const [next, commitment] = processSigningRound1(asStateJson(state), signer, keyPackages[i]).data!;
+        state = next;
+        return commitment;
+      });
+      
+      const partial = buildSigningPackage(asStateJson(state), commitments.slice(0, 2)).data!;
+      const result = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], partial);
+      expect(result.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+      expect(result.error).toContain('carol');
+    });
+  });
+
+  describe('NIP-46 Connect Response', () => {
+    const clientPubkey = '3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d';
+    
+    it('should acknowledge a bunker connect request with its id', () => {
+      const content = nip46ConnectAck('3047714669', clientPubkey).data!;
+      expect(JSON.parse(content)).toEqual({ id: '3047714669', result: 'ack' });
+    });
+    
+    it('should return the nostrconnect secret as the result', () => {
+      const content = nip46ConnectAck('3047714669', clientPubkey, '0s8j2djs').data!;
+      expect(JSON.parse(content)).toEqual({ id: '3047714669', result: '0s8j2djs' });
+    });
+    
+    it('should reject a client pubkey that is not a valid x-only key', () => {
+      expect(nip46ConnectAck('3047714669', 'ff'.repeat(32)).error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('NIP-44 Encryption', () => {
+    // nip44.vectors.json: secret keys 1 and 2
+    const sec1 = '00'.repeat(31) + '01';
+    const sec2 = '00'.repeat(31) + '02';
+    const pub1 = '79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798';
+    const pub2 = 'c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5';
+    
+    it('should match the spec conversation key and payload vectors', () => {
+      expect(nip44ConversationKey(sec1, pub2).data).toBe('c41c775356fd92eadc63ff5a0dc1da211b268cbea22316767095b2871ea1412d');
+      expect(nip44ConversationKey(sec2, pub1).data).toBe('c41c775356fd92eadc63ff5a0dc1da211b268cbea22316767095b2871ea1412d');
+      expect(nip44ConversationKey(
+        '315e59ff51cb9209768cf7da80791ddcaae56ac9775eb25b6dee1234bc5d2268',
+        'c2f9d9948dc8c7c38321e4b85c8558872eafa0641cd269db76848a6073e69133'
+      ).data).toBe('3dfef0ce2a4d80a25e7a328accf73448ef67096f65f79588e358d9a0eb9013f1');
+      
+      const nonce = new Uint8Array(32);
+      nonce[31] = 1;
+      const payload = nip44Encrypt('a', sec1, pub2, nonce).data!;
+      expect(payload).toBe('AgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABee0G5VSK0/9YypIObAtDKfYEAjD35uVkHyB0F4DwrcNaCXlCWZKaArsGrY6M9wnuTMxWfp1RTN9Xga8no+kF5Vsb');
+      expect(nip44Decrypt(payload, sec2, pub1).data).toBe('a');
+    });
+    
+    it('should round trip a NIP-46 response and reject a tampered payload', () => {
+      const content = JSON.stringify({ id: '3047714669', result: 'ack' });
+      const payload = nip44Encrypt(content, sec1, pub2).data!;
+      expect(nip44Decrypt(payload, sec2, pub1).data).toBe(content);
+      
+      const tampered = payload.slice(0, 60) + (payload[60] === 'A' ? 'B' : 'A') + payload.slice(61);
+      expect(nip44Decrypt(tampered, sec2, pub1).error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Threshold ECDH', () => {
+    const peerSecret = '00'.repeat(31) + '02';
+    const peerPubkey = 'c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5';
+    
+    it('should derive the conversation key the peer derives against the group key', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares).slice(0, 2);
+      const signers = keyPackages.map(share => JSON.parse(share).identifier);
+      
+      let state = ecdhRound1(peerPubkey, signers, groupPublicKey, 2);
+      expect(state.success).toBe(true);
+      for (const keyPackage of keyPackages) {
+        state = ecdhRound2(JSON.stringify(state), keyPackage);
+        expect(state.success).toBe(true);
+      }
+      const conversationKey = ecdhAggregate(JSON.stringify(state)).data!;
+      
+      const groupXOnly = JSON.parse(groupPublicKey).verifying_key.slice(2);
+      expect(nip44ConversationKey(peerSecret, groupXOnly).data).toBe(conversationKey);
+      
+      const payload = nip44EncryptWithKey('{"id":"1","result":"ack"}', conversationKey).data!;
+      expect(nip44Decrypt(payload, peerSecret, groupXOnly).data).toBe('{"id":"1","result":"ack"}');
+    });
+    
+    it('should not aggregate before every signer contributes', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const signers = Object.values(shares).slice(0, 2).map(share => JSON.parse(share).identifier);
+      
+      const state = ecdhRound1(peerPubkey, signers, groupPublicKey, 2);
+      expect(ecdhAggregate(JSON.stringify(state)).error_code).toBe(FrostErrorCode.InvalidStateTransition);
+    });
+  });
+
+  describe('Aggregate Nonce', () => {
+    it('should match the R of the final signature', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      let state = commitAll(createSigningCeremony('nonce check', ['alice', 'bob']).data!, ['alice', 'bob'], keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      const nonce = aggregateNonce(signingPackage, groupPublicKey).data!;
+      expect(nonce).toMatch(/^0[23][0-9a-f]{64}$/);
+      
+      [state] = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage).data!;
+      const [, signature] = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage, groupPublicKey).data!;
+      expect(JSON.parse(signature!).slice(0, 66)).toBe(nonce);
+    });
+  });
+
+  describe('Dealer Identifier Table', () => {
+    it('should sign with dealer shares pinned to the dealer identifiers', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] }]).data!;
+      const { alice, bob } = dealt.identifiers;
+      const message = 'dealt and signed';
+      
+      let state = createSigningCeremony(message, { alice, bob }).data!;
+      expect(processSigningRound1(asStateJson(state), 'alice', dealt.shares.bob).error_code).toBe(FrostErrorCode.InvalidParticipant);
+      
+      state = commitAll(state, ['alice', 'bob'], [dealt.shares.alice, dealt.shares.bob]);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      [state] = processSigningRound2(asStateJson(state), 'alice', dealt.shares.alice, signingPackage).data!;
+      const [, signature] = processSigningRound2(asStateJson(state), 'bob', dealt.shares.bob, signingPackage, dealt.group_public_key).data!;
+      
+      expect(verifyFrostSignature(message, signature!, dealt.group_public_key).data).toBe(true);
+    });
+  });
+
+  describe('Share Checksums', () => {
+    it('should sign with a checksummed share and catch a flipped byte on load', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const envelope = checksumShare(keyPackages[0]).data!;
+      const state = createSigningCeremony('checksummed', ['alice', 'bob']).data!;
+      
+      expect(processSigningRound1(asStateJson(state), 'alice', envelope).success).toBe(true);
+      
+      const stored = JSON.parse(envelope);
+      const signingShare: string = JSON.parse(stored.share).signing_share;
+      const flipped = signingShare.slice(0, 10) + (signingShare[10] === '0' ? '1' : '0') + signingShare.slice(11);
+      const corrupted = JSON.stringify({ ...stored, share: stored.share.replace(signingShare, flipped) });
+      
+      const result = processSigningRound1(asStateJson(state), 'alice', corrupted);
+      expect(result.error_code).toBe(FrostErrorCode.CorruptedShare);
+    });
+
+    it('should restore a signing share from its mnemonic and catch a wrong word', () => {
+      const [keyPackage] = dealerKeyPackages(2, 3);
+      const mnemonic = shareToMnemonic(keyPackage).data!;
+      const words = mnemonic.split(' ');
+      expect(words).toHaveLength(75);
+      
+      const restored = JSON.parse(shareFromMnemonic(mnemonic).data!);
+      const original = JSON.parse(keyPackage);
+      expect(restored.identifier).toBe(original.identifier);
+      expect(restored.signing_share).toBe(original.signing_share);
+      
+      words[20] = words[20] === 'zoo' ? 'zone' : 'zoo';
+      expect(shareFromMnemonic(words.join(' ')).error_code).toBe(FrostErrorCode.CorruptedShare);
+      
+      words[20] = 'notaword';
+      expect(shareFromMnemonic(words.join(' ')).error_code).toBe(FrostErrorCode.MalformedInput);
+      expect(shareFromMnemonic(words.slice(0, 74).join(' ')).error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Weighted Participants', () => {
+    it('should let a weight-3 server sign a 4-of-6 with one other participant', () => {
+      const labels = virtualParticipants({ server: 3, alice: 1, bob: 1, carol: 1 }).data!;
+      expect(labels).toEqual(['alice#1', 'bob#1', 'carol#1', 'server#1', 'server#2', 'server#3']);
+      
+      const [dealt] = generateFrostSharesBatch([{ threshold: 4, max_participants: 6, labels }]).data!;
+      const serverShares = Object.fromEntries(Object.entries(dealt.shares).filter(([label]) => label.startsWith('server#')));
+      const message = 'weighted';
+      
+      let state = createSigningCeremony(message, ['server#1', 'server#2', 'server#3', 'alice#1']).data!;
+      let commitments;
+      [state, commitments] = processSigningRound1Weighted(asStateJson(state), 'server', serverShares).data!;
+      expect(commitments).toHaveLength(3);
+      state = commitAll(state, ['alice#1'], [dealt.shares['alice#1']]);
+      
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      [state] = processSigningRound2Weighted(asStateJson(state), 'server', serverShares, signingPackage).data!;
+      const [, signature] = processSigningRound2(
+        asStateJson(state), 'alice#1', dealt.shares['alice#1'], signingPackage, dealt.group_public_key
+      ).data!;
+      
+      expect(verifyFrostSignature(message, signature!, dealt.group_public_key).data).toBe(true);
+    });
+    
+    it('should reject a share that belongs to another participant', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 3, labels: ['server#1', 'server#2', 'alice#1'] }]).data!;
+      const state = createSigningCeremony('weighted', ['server#1', 'alice#1']).data!;
+      
+      const result = processSigningRound1Weighted(asStateJson(state), 'server', { 'alice#1': dealt.shares['alice#1'] });
+      expect(result.error_code).toBe(FrostErrorCode.InvalidParticipant);
+    });
+    
+    it('should not sign when the weighted shares alone fall short of the threshold', () => {
+      const labels = ['server#1', 'server#2', 'server#3', 'alice#1'];
+      const [dealt] = generateFrostSharesBatch([{ threshold: 4, max_participants: 4, labels }]).data!;
+      const serverShares = Object.fromEntries(Object.entries(dealt.shares).filter(([label]) => label.startsWith('server#')));
+      
+      let state = createSigningCeremony('weighted', labels.slice(0, 3)).data!;
+      [state] = processSigningRound1Weighted(asStateJson(state), 'server', serverShares).data!;
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      const result = processSigningRound2Weighted(asStateJson(state), 'server', serverShares, signingPackage, dealt.group_public_key);
+      expect(result.error_code).toBe(FrostErrorCode.InsufficientParticipants);
+    });
+  });
+
+  describe('Participation Pre-flight', () => {
+    it('should accept a member share and explain why others cannot sign', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const [otherGroupPublicKey] = generateFrostShares('', 2, 3).data!;
+      const share = Object.values(shares)[0];
+      
+      const usable = canParticipate(share, groupPublicKey, 2).data!;
+      expect(usable.member).toBe(true);
+      expect(usable.can_reach_threshold).toBe(true);
+      expect(usable.reasons).toEqual([]);
+      
+      expect(canParticipate(share, groupPublicKey, 4).data!.reasons).toEqual(['threshold_mismatch', 'threshold_unreachable']);
+      
+      const foreign = canParticipate(share, otherGroupPublicKey, 2).data!;
+      expect(foreign.member).toBe(false);
+      expect(foreign.reasons).toContain('other_group');
+    });
+
+    it('should audit a group and list each discrepancy with its policy', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] }]).data!;
+      const members = ['alice', 'bob', 'carol'];
+      expect(auditGroup(dealt.group_public_key, dealt.shares, 2, members).data).toEqual({ healthy: true, findings: [] });
+      
+      const audit = auditGroup(dealt.group_public_key, { alice: dealt.shares.alice, dave: dealt.shares.carol }, 2, members).data!;
+      expect(audit.healthy).toBe(false);
+      expect(audit.findings).toEqual([
+        { finding: 'missing_member', member: 'bob' },
+        { finding: 'missing_member', member: 'carol' },
+        { finding: 'extra_member', member: 'dave' },
+      ]);
+    });
+
+    it('should flag a share from another group and a threshold that differs from the policy', () => {
+      const [dealt, other] = generateFrostSharesBatch([
+        { threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] },
+        { threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] },
+      ]).data!;
+      const shares = { alice: dealt.shares.alice, bob: other.shares.bob, carol: dealt.shares.carol };
+      
+      const audit = auditGroup(dealt.group_public_key, shares, 3, ['alice', 'bob', 'carol']).data!;
+      expect(audit.healthy).toBe(false);
+      expect(audit.findings).toContainEqual({ finding: 'other_group', member: 'bob' });
+      expect(audit.findings).toContainEqual({ finding: 'threshold_mismatch', member: 'alice', threshold: 2 });
+    });
+  });
+
+  describe('Resume From Transcript', () => {
+    it('should collect surviving signers\' shares after the coordinator state is lost', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 2, labels: ['alice', 'bob'] }]).data!;
+      const text = 'resumed message';
+      const message = new TextEncoder().encode(text);
+      let signerState = createSigningCeremony(message, ['alice', 'bob']).data!;
+      const commitments = ['alice', 'bob'].map((signer) => {
+        const [next, commitment] = processSigningRound1(asStateJson(signerState), signer, dealt.shares[signer]).data!;
+        signerState = next;
+        return commitment;
+      });
+      
+      let [coordinator, signingPackage] = resumeSigningFromTranscript(message, commitments).data!;
+      expect(coordinator.current_round).toBe(2);
+      expect(coordinator.threshold).toBe(2);
+      expect(signingPackage).toBe(buildSigningPackage(asStateJson(signerState)).data!);
+      
+      // A package over fresh commitments is not the one in the transcript
+      const fresh = commitAll(createSigningCeremony(message, ['alice', 'bob']).data!, ['alice', 'bob'], [dealt.shares.alice, dealt.shares.bob]);
+      const freshPackage = buildSigningPackage(asStateJson(fresh)).data!;
+      const [freshSigned] = processSigningRound2(asStateJson(fresh), 'bob', dealt.shares.bob, freshPackage).data!;
+      const substituted = addSignatureShare(
+        asStateJson(coordinator), dealt.identifiers.bob, freshSigned.signature_shares.bob, freshPackage, dealt.group_public_key
+      );
+      expect(substituted.error_code).toBe(FrostErrorCode.SigningError);
+      expect(substituted.error).toContain('commitment mismatch');
+      
+      let signature: string | null = null;
+      for (const signer of ['alice', 'bob']) {
+        const [signed] = processSigningRound2(asStateJson(signerState), signer, dealt.shares[signer], signingPackage).data!;
+        [coordinator, signature] = addSignatureShare(
+          asStateJson(coordinator), dealt.identifiers[signer], signed.signature_shares[signer], signingPackage, dealt.group_public_key
+        ).data!;
+      }
+      
+      expect(verifyFrostSignature(text, signature!, dealt.group_public_key).data).toBe(true);
+    });
+  });
+
+  describe('Ceremony Cap', () => {
+    afterEach(() => {
+      ['first', 'second', 'third'].forEach((ceremonyId) => closeCeremony(ceremonyId));
+      setMaxCeremonies(1024);
+    });
+    
+    it('should open ceremonies up to the cap and refuse one past it', () => {
+      setMaxCeremonies(2);
+      expect(openCeremony('first').data).toBe(1);
+      expect(openCeremony('second').data).toBe(2);
+      expect(activeCeremonyCount()).toBe(2);
+      
+      expect(openCeremony('third').error_code).toBe(FrostErrorCode.TooManyCeremonies);
+      
+      expect(closeCeremony('first').data).toBe(true);
+      expect(openCeremony('third').data).toBe(2);
+    });
+  });
+
+  describe('Signer Inclusion Proofs', () => {
+    it('should prove a share was part of the final signature', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 2, labels: ['alice', 'bob'] }]).data!;
+      let state = createSigningCeremony('audited', ['alice', 'bob']).data!;
+      const commitments = ['alice', 'bob'].map((signer) => {
+        const [next, commitment] = processSigningRound1(asStateJson(state), signer, dealt.shares[signer]).data!;
+        state = next;
+        return commitment;
+      });
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      [state] = processSigningRound2(asStateJson(state), 'alice', dealt.shares.alice, signingPackage).data!;
+      let signature: string | null;
+      [state, signature] = processSigningRound2(asStateJson(state), 'bob', dealt.shares.bob, signingPackage, dealt.group_public_key).data!;
+      
+      const proof = signerInclusionProof(
+        dealt.identifiers.bob, state.signature_shares.bob, commitments, signingPackage, dealt.group_public_key
+      ).data!;
+      expect(JSON.stringify(proof)).not.toContain(JSON.parse(dealt.shares.bob).signing_share);
+      expect(verifySignerInclusionProof(proof, signature!).data).toBe(true);
+      
+      const forged = signerInclusionProof(
+        dealt.identifiers.bob, state.signature_shares.alice, commitments, signingPackage, dealt.group_public_key
+      );
+      expect(forged.error_code).toBe(FrostErrorCode.SigningError);
+    });
+  });
+
+  describe('Deterministic Split', () => {
+    it('should give identical shares for the same key, parameters, and domain', () => {
+      const privateKey = '00'.repeat(31) + '03';
+      const first = splitDeterministic(privateKey, 2, 3, 'migration-2026').data!;
+      const again = splitDeterministic(privateKey, 2, 3, 'migration-2026').data!;
+      expect(again.shares).toEqual(first.shares);
+      expect(again.group_public_key).toBe(first.group_public_key);
+      
+      const otherDomain = splitDeterministic(privateKey, 2, 3, 'migration-2027').data!;
+      expect(otherDomain.group_public_key).toBe(first.group_public_key);
+      expect(otherDomain.shares).not.toEqual(first.shares);
+    });
+  });
+
+  describe('Nostr Key Compatibility', () => {
+    it('should report the parity of imported keys and even-y dealer keys', () => {
+      const generatorX = '79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798';
+      // -1 mod n: its public key is -G, which has odd y
+      const negatedOne = 'fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140';
+      
+      const dealt = splitDeterministic(negatedOne, 2, 3, 'parity').data!;
+      const compatibility = assertNostrCompatible(dealt.group_public_key).data!;
+      expect(compatibility.xonly_pubkey).toBe(generatorX);
+      expect(compatibility.parity_adjustment_needed).toBe(false);
+      
+      for (let i = 0; i < 4; i++) {
+        const [groupPublicKey] = generateFrostShares('', 2, 3).data!;
+        expect(assertNostrCompatible(groupPublicKey).data!.parity_adjustment_needed).toBe(false);
+      }
+    });
+  });
+
+  describe('Ceremony Kind', () => {
+    it('should tag each state and reject it in the other ceremony', () => {
+      const keygenState = createKeygenCeremony(2, 3).data!;
+      const signingState = createSigningCeremony('message', ['alice', 'bob']).data!;
+      expect(keygenState.kind).toBe('keygen');
+      expect(signingState.kind).toBe('signing');
+      
+      const [share] = dealerKeyPackages(2, 3);
+      const crossed = [
+        processKeygenRound1(asStateJson(signingState), 'alice'),
+        processSigningRound1(asStateJson(keygenState), 'alice', share),
+      ];
+      for (const result of crossed) {
+        expect(result.error_code).toBe(FrostErrorCode.InvalidStateTransition);
+        expect(result.error).toContain('wrong ceremony kind');
+      }
+      
+      expect(getCeremonyStatus(signingState)).toBe('SIGNING_ROUND_1');
+    });
+
+    it('should read a state by its kind tag rather than by its fields', () => {
+      const lifetime = { createdAt: 1700000000, expiresAt: 1700000600 };
+      const keygenState = createKeygenCeremony(2, 3, undefined, lifetime).data!;
+      
+      const mislabeled = JSON.stringify({ success: true, data: { ...keygenState, kind: 'signing' } });
+      expect(checkExpired(mislabeled, 0).error_code).toBe(FrostErrorCode.MalformedInput);
+      expect(isComplete(mislabeled).error_code).toBe(FrostErrorCode.MalformedInput);
+      
+      // States saved before the tag existed are still told apart by their fields
+      const legacy = asStateJson({ ...keygenState, kind: undefined });
+      expect(checkExpired(legacy, lifetime.expiresAt).data).toBe(true);
+      expect(isComplete(legacy).data!.ceremony).toBe('keygen');
+    });
+  });
+
+  describe('Aggregate Preview', () => {
+    it('should return a verified signature without finalizing, and reject a bad share set', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 2, labels: ['alice', 'bob'] }]).data!;
+      let state = createSigningCeremony('preview', ['alice', 'bob']).data!;
+      for (const signer of ['alice', 'bob']) {
+        [state] = processSigningRound1(asStateJson(state), signer, dealt.shares[signer]).data!;
+      }
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      for (const signer of ['alice', 'bob']) {
+        [state] = processSigningRound2(asStateJson(state), signer, dealt.shares[signer], signingPackage, dealt.group_public_key).data!;
+      }
+      
+      const { alice, bob } = dealt.identifiers;
+      const signature = tryAggregatePreview(
+        signingPackage, { [alice]: state.signature_shares.alice, [bob]: state.signature_shares.bob }, dealt.group_public_key
+      ).data!;
+      expect(verifyFrostSignature('preview', signature, dealt.group_public_key).data).toBe(true);
+      
+      const rejected = tryAggregatePreview(
+        signingPackage, { [alice]: state.signature_shares.alice, [bob]: state.signature_shares.alice }, dealt.group_public_key
+      );
+      expect(rejected.error_code).toBe(FrostErrorCode.SigningError);
+      expect(rejected.data).toBeNull();
+    });
+  });
+
+  describe('Threshold of One', () => {
+    it('should complete a 1-of-5 ceremony with the first signer alone', () => {
+      const labels = ['alice', 'bob', 'carol', 'dave', 'erin'];
+      const [dealt] = generateFrostSharesBatch([{ threshold: 1, max_participants: 5, labels }]).data!;
+      let state = createSigningCeremony('any one of five', labels).data!;
+      
+      [state] = processSigningRound1(asStateJson(state), 'dave', dealt.shares.dave).data!;
+      expect(state.current_round).toBe(2);
+      expect(state.signers).toEqual(['dave']);
+      expect(processSigningRound1(asStateJson(state), 'erin', dealt.shares.erin).error_code)
+        .toBe(FrostErrorCode.InvalidStateTransition);
+      
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      const [, signature] = processSigningRound2(
+        asStateJson(state), 'dave', dealt.shares.dave, signingPackage, dealt.group_public_key
+      ).data!;
+      expect(verifyFrostSignature('any one of five', signature!, dealt.group_public_key).data).toBe(true);
+    });
+  });
+
+  describe('Commitments Bundle', () => {
+    it('should build the same signing package offline as online', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 2, labels: ['alice', 'bob'] }]).data!;
+      let state = createSigningCeremony('air-gapped', ['alice', 'bob']).data!;
+      for (const signer of ['alice', 'bob']) {
+        [state] = processSigningRound1(asStateJson(state), signer, dealt.shares[signer]).data!;
+      }
+      const onlinePackage = buildSigningPackage(asStateJson(state)).data!;
+      
+      const bundle = exportCommitmentsBundle(asStateJson(state)).data!;
+      expect(bundle.format).toBe('frost-commitments-bundle/v1');
+      expect(buildSigningPackageFromBundle('air-gapped', bundle).data).toBe(onlinePackage);
+      
+      const tampered = { ...bundle, commitments: bundle.commitments.slice(1) };
+      expect(buildSigningPackageFromBundle('air-gapped', tampered).error_code).toBe(FrostErrorCode.IntegrityError);
+    });
+  });
+
+  describe('Stale Commitments', () => {
+    it('should reject a commitment replayed from an earlier ceremony', () => {
+      const keyPackages = dealerKeyPackages(2, 3);
+      const commitBoth = (ceremonyId: string) => {
+        let state = createSigningCeremony('same message', ['alice', 'bob'], ceremonyId).data!;
+        const commitments = ['alice', 'bob'].map((signer, i) => {
+          const [next, commitment] = processSigningRound1(asStateJson(state), signer, keyPackages[i]).data!;
+          state = next;
+          return commitment;
+        });
+        return { state, commitments };
+      };
+      
+      const earlier = commitBoth('ceremony-1');
+      const current = commitBoth('ceremony-2');
+      current.commitments.forEach(commitment => expect(commitment.ceremony_binding).toMatch(/^[0-9a-f]{64}$/));
+      
+      const replayed = [earlier.commitments[0], current.commitments[1]];
+      const result = buildSigningPackage(asStateJson(current.state), replayed);
+      expect(result.error_code).toBe(FrostErrorCode.SigningError);
+      expect(result.error).toContain('stale commitment');
+      expect(result.data).toBeNull();
+      
+      const unbound = replayed.map(({ identifier, commitment }) => ({ identifier, commitment }));
+      const stalePackage = buildSigningPackage(asStateJson(current.state), unbound).data!;
+      const signed = processSigningRound2(asStateJson(current.state), 'alice', keyPackages[0], stalePackage);
+      expect(signed.error_code).toBe(FrostErrorCode.SigningError);
+      expect(signed.error).toContain('stale commitment');
+    });
+  });
+
+  describe('Domain Attestations', () => {
+    it('should sign an attestation that verifiers rebuild from the domain and pubkey', () => {
+      const pubkey = '3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d';
+      const message = buildAttestationMessage('Auth.Example.com', pubkey.toUpperCase()).data!;
+      expect(message).toBe(`nip05-attestation/v1\nauth.example.com\n${pubkey}`);
+      
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      let state = commitAll(createSigningCeremony(message, ['alice', 'bob']).data!, ['alice', 'bob'], keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      [state] = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage).data!;
+      const [, signature] = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage, groupPublicKey).data!;
+      
+      expect(verifyAttestation('auth.example.com', pubkey, signature!, groupPublicKey).data).toBe(true);
+      expect(verifyAttestation('other.example.com', pubkey, signature!, groupPublicKey).data).toBe(false);
+      expect(buildAttestationMessage('bob@example.com', pubkey).error_code).toBe(FrostErrorCode.MalformedInput);
+    });
+  });
+
+  describe('Group Key Mismatch', () => {
+    it('should name the key package when aggregating with another group\'s public key', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const [otherGroupPublicKey] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      let state = commitAll(createSigningCeremony('message', ['alice', 'bob']).data!, ['alice', 'bob'], keyPackages);
+      const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+      [state] = processSigningRound2(asStateJson(state), 'alice', keyPackages[0], signingPackage).data!;
+      
+      const mismatched = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage, otherGroupPublicKey);
+      expect(mismatched.error_code).toBe(FrostErrorCode.SigningError);
+      expect(mismatched.error).toContain('key package / group key mismatch');
+      
+      const signed = processSigningRound2(asStateJson(state), 'bob', keyPackages[1], signingPackage, groupPublicKey);
+      expect(signed.success).toBe(true);
+    });
+  });
+
+  describe('Signer Identifiers at Creation', () => {
+    it('should store the identifier of every signer when the ceremony is created', () => {
+      const [dealt] = generateFrostSharesBatch([{ threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] }]).data!;
+      const table = { alice: dealt.identifiers.alice, bob: dealt.identifiers.bob };
+      
+      const state = createSigningCeremonyForGroup('message', table, dealt.group_public_key).data!;
+      expect(state.signer_identifiers).toEqual(table);
+      expect(state.group_verifying_key).toBeDefined();
+      
+      const unresolved = createSigningCeremonyForGroup('message', ['alice', 'bob'], dealt.group_public_key);
+      expect(unresolved.error_code).toBe(FrostErrorCode.InvalidParticipant);
+    });
+  });
+
+  describe('App Subkeys', () => {
+    it('should sign under two different app subkeys of one group', () => {
+      const [groupPublicKey, shares] = generateFrostShares('', 2, 3).data!;
+      const keyPackages = Object.values(shares);
+      const message = 'per-app message';
+      
+      const signatures = ['chat.example', 'wallet.example'].map(appId => {
+        const subkey = deriveAppSubkey(groupPublicKey, appId).data!;
+        const appKeyPackages = keyPackages.map(keyPackage => deriveAppKeyPackage(keyPackage, appId).data!);
+        let state = commitAll(createSigningCeremony(message, ['alice', 'bob']).data!, ['alice', 'bob'], appKeyPackages);
+        const signingPackage = buildSigningPackage(asStateJson(state)).data!;
+        [state] = processSigningRound2(asStateJson(state), 'alice', appKeyPackages[0], signingPackage).data!;
+        const [, signature] = processSigningRound2(asStateJson(state), 'bob', appKeyPackages[1], signingPackage, subkey).data!;
+        expect(verifyFrostSignature(message, signature!, subkey).data).toBe(true);
+        expect(verifyFrostSignature(message, signature!, groupPublicKey).data).toBe(false);
+        return { subkey, signature: signature! };
+      });
+      
+      expect(signatures[0].subkey).not.toBe(signatures[1].subkey);
+      expect(verifyFrostSignature(message, signatures[0].signature, signatures[1].subkey).data).toBe(false);
+    });
+  });
+
+  describe('Signing Preflight', () => {
+    it('should return the signer table or the first problem with the request', () => {
+      const [dealt, other] = generateFrostSharesBatch([
+        { threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] },
+        { threshold: 2, max_participants: 3, labels: ['alice', 'bob', 'carol'] },
+      ]).data!;
+      const eventId = Uint8Array.from(Buffer.from(SIGNED_EVENT.id, 'hex'));
+      const signers = { alice: dealt.shares.alice, bob: dealt.shares.bob };
+      
+      const ready = preflightSigning(eventId, signers, dealt.group_public_key, 2);
+      expect(ready.data!.signers).toEqual({ alice: dealt.identifiers.alice, bob: dealt.identifiers.bob });
+      
+      const notEventId = preflightSigning(new TextEncoder().encode('hello'), signers, dealt.group_public_key, 2);
+      expect(notEventId.error_code).toBe(FrostErrorCode.MalformedInput);
+      const tooFew = preflightSigning(eventId, { alice: dealt.shares.alice }, dealt.group_public_key, 2);
+      expect(tooFew.error_code).toBe(FrostErrorCode.InsufficientParticipants);
+      const unloadable = preflightSigning(eventId, { ...signers, bob: '{}' }, dealt.group_public_key, 2);
+      expect(unloadable.error_code).toBe(FrostErrorCode.MalformedInput);
+      const outsider = preflightSigning(eventId, { ...signers, bob: other.shares.bob }, dealt.group_public_key, 2);
+      expect(outsider.error_code).toBe(FrostErrorCode.InvalidParticipant);
+    });
+  });
+});